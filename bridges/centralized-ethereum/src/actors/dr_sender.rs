@@ -10,6 +10,7 @@ use serde_json::json;
 use std::{fmt, time::Duration};
 use witnet_data_structures::{
     chain::{DataRequestOutput, Hash},
+    mainnet_validations::current_active_wips,
     proto::ProtobufConvert,
     radon_error::RadonErrors,
 };
@@ -263,7 +264,7 @@ fn deserialize_and_validate_dr_bytes(
                 });
             }
 
-            validate_rad_request(&dr_output.data_request)
+            validate_rad_request(&dr_output.data_request, &current_active_wips())
                 .map_err(|e| DrSenderError::RadonValidation { msg: e.to_string() })?;
 
             // Check if we want to claim this data request:
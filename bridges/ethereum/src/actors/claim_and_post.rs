@@ -22,6 +22,7 @@ use web3::{contract, futures::Future, types::U256};
 use witnet_crypto::hash::{calculate_sha256, Sha256};
 use witnet_data_structures::{
     chain::{DataRequestOutput, Hashable, KeyedSignature},
+    mainnet_validations::current_active_wips,
     proto::ProtobufConvert,
 };
 use witnet_util::timestamp::get_local_timestamp;
@@ -97,7 +98,7 @@ fn try_to_claim_local_query(
 
                 let dr_output: DataRequestOutput =
                     match ProtobufConvert::from_pb_bytes(&dr_bytes).and_then(|dr: DataRequestOutput| {
-                        validate_rad_request(&dr.data_request)?;
+                        validate_rad_request(&dr.data_request, &current_active_wips())?;
                         Ok(dr)
                     }) {
                         Ok(x) => {
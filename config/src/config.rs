@@ -305,6 +305,22 @@ pub struct JsonRPC {
     pub server_address: SocketAddr,
     /// Enable methods not suitable for shared nodes
     pub enable_sensitive_methods: bool,
+    /// Socket address for serving JSON-RPC over HTTP POST requests, or `None` to disable it
+    #[partial_struct(skip)]
+    #[partial_struct(serde(default))]
+    pub http_address: Option<SocketAddr>,
+    /// Socket address for serving JSON-RPC over WebSocket, including subscriptions, or `None` to
+    /// disable it
+    #[partial_struct(skip)]
+    #[partial_struct(serde(default))]
+    pub ws_address: Option<SocketAddr>,
+    /// Values of the `Host` header accepted by the HTTP and WebSocket servers, besides the
+    /// addresses they are bound to. Wildcards are supported, e.g. `*.example.com:*`
+    pub allowed_hosts: Vec<String>,
+    /// Origins of the web pages that are allowed to use the HTTP and WebSocket servers, e.g.
+    /// `https://example.com`. `*` allows any origin. Requests without an `Origin` header, that
+    /// is, not sent from a browser, are always accepted
+    pub allowed_origins: Vec<String>,
 }
 
 /// Mining-related configuration
@@ -328,6 +344,74 @@ pub struct Mining {
         rename = "data_request_timeout_milliseconds"
     ))]
     pub data_request_timeout: Duration,
+    /// Timeout for receiving the response headers of each of the sources of a data request.
+    /// Set to 0 to disable this timeout.
+    #[partial_struct(serde(
+        default,
+        deserialize_with = "from_millis",
+        serialize_with = "to_millis",
+        rename = "data_request_connect_timeout_milliseconds"
+    ))]
+    pub data_request_connect_timeout: Duration,
+    /// Timeout for receiving the response body of each of the sources of a data request, once
+    /// the headers have been received.
+    /// Set to 0 to disable this timeout.
+    #[partial_struct(serde(
+        default,
+        deserialize_with = "from_millis",
+        serialize_with = "to_millis",
+        rename = "data_request_read_timeout_milliseconds"
+    ))]
+    pub data_request_read_timeout: Duration,
+    /// How many times the retrieval of a source is retried after a transient error, such as a
+    /// timeout or a 5xx HTTP status code. Only HTTP-GET sources are retried, and never after
+    /// `data_request_timeout` has elapsed since the first attempt.
+    pub data_request_max_retries: u32,
+    /// Time to wait before retrying the retrieval of a source. It is doubled for each retry.
+    #[partial_struct(serde(
+        default,
+        deserialize_with = "from_millis",
+        serialize_with = "to_millis",
+        rename = "data_request_retry_backoff_milliseconds"
+    ))]
+    pub data_request_retry_backoff: Duration,
+    /// Maximum size in bytes of the response body of each of the sources of a data request.
+    /// Set to 0 to disable this limit.
+    pub data_request_max_response_size: usize,
+    /// Maximum number of data request source responses that are cached during an epoch, so that
+    /// data requests using the same source do not need to retrieve it again.
+    /// Set to 0 to disable the cache.
+    pub data_request_cache_size: usize,
+    /// Maximum number of requests to the same host that can be in flight at the same time.
+    /// Set to 0 to disable this limit.
+    pub data_request_max_concurrent_requests_per_host: usize,
+    /// Minimum time between the start of two requests to the same host.
+    /// Set to 0 to disable this limit.
+    #[partial_struct(serde(
+        default,
+        deserialize_with = "from_millis",
+        serialize_with = "to_millis",
+        rename = "data_request_min_interval_per_host_milliseconds"
+    ))]
+    pub data_request_min_interval_per_host: Duration,
+    /// URL of the proxy through which the requests of data request sources are sent, e.g.
+    /// `http://proxy.example.com:3128` or `socks5h://127.0.0.1:1080`.
+    /// No proxy is used if this is not set. If the proxy configuration is invalid, the node logs
+    /// an error and does not use any proxy.
+    #[partial_struct(skip)]
+    #[partial_struct(serde(default))]
+    pub data_request_proxy_url: Option<String>,
+    /// Username for authenticating with the proxy
+    #[partial_struct(skip)]
+    #[partial_struct(serde(default))]
+    pub data_request_proxy_username: Option<String>,
+    /// Password for authenticating with the proxy
+    #[partial_struct(skip)]
+    #[partial_struct(serde(default))]
+    pub data_request_proxy_password: Option<String>,
+    /// Hosts whose requests are not sent through the proxy, including their subdomains.
+    /// `*` bypasses the proxy for all hosts.
+    pub data_request_proxy_bypass: Vec<String>,
     /// Genesis block path
     pub genesis_path: String,
     /// Percentage to redistribute mint reward in another address
@@ -742,6 +826,16 @@ impl JsonRPC {
                 .enable_sensitive_methods
                 .to_owned()
                 .unwrap_or_else(|| defaults.jsonrpc_enable_sensitive_methods()),
+            http_address: config.http_address,
+            ws_address: config.ws_address,
+            allowed_hosts: config
+                .allowed_hosts
+                .clone()
+                .unwrap_or_else(|| defaults.jsonrpc_allowed_hosts()),
+            allowed_origins: config
+                .allowed_origins
+                .clone()
+                .unwrap_or_else(|| defaults.jsonrpc_allowed_origins()),
         }
     }
 
@@ -750,6 +844,10 @@ impl JsonRPC {
             enabled: Some(self.enabled),
             server_address: Some(self.server_address),
             enable_sensitive_methods: Some(self.enable_sensitive_methods),
+            http_address: self.http_address,
+            ws_address: self.ws_address,
+            allowed_hosts: Some(self.allowed_hosts.clone()),
+            allowed_origins: Some(self.allowed_origins.clone()),
         }
     }
 }
@@ -765,6 +863,45 @@ impl Mining {
                 .data_request_timeout
                 .to_owned()
                 .unwrap_or_else(|| defaults.mining_data_request_timeout()),
+            data_request_connect_timeout: config
+                .data_request_connect_timeout
+                .to_owned()
+                .unwrap_or_else(|| defaults.mining_data_request_connect_timeout()),
+            data_request_read_timeout: config
+                .data_request_read_timeout
+                .to_owned()
+                .unwrap_or_else(|| defaults.mining_data_request_read_timeout()),
+            data_request_max_retries: config
+                .data_request_max_retries
+                .to_owned()
+                .unwrap_or_else(|| defaults.mining_data_request_max_retries()),
+            data_request_retry_backoff: config
+                .data_request_retry_backoff
+                .to_owned()
+                .unwrap_or_else(|| defaults.mining_data_request_retry_backoff()),
+            data_request_max_response_size: config
+                .data_request_max_response_size
+                .to_owned()
+                .unwrap_or_else(|| defaults.mining_data_request_max_response_size()),
+            data_request_cache_size: config
+                .data_request_cache_size
+                .to_owned()
+                .unwrap_or_else(|| defaults.mining_data_request_cache_size()),
+            data_request_max_concurrent_requests_per_host: config
+                .data_request_max_concurrent_requests_per_host
+                .to_owned()
+                .unwrap_or_else(|| defaults.mining_data_request_max_concurrent_requests_per_host()),
+            data_request_min_interval_per_host: config
+                .data_request_min_interval_per_host
+                .to_owned()
+                .unwrap_or_else(|| defaults.mining_data_request_min_interval_per_host()),
+            data_request_proxy_url: config.data_request_proxy_url.clone(),
+            data_request_proxy_username: config.data_request_proxy_username.clone(),
+            data_request_proxy_password: config.data_request_proxy_password.clone(),
+            data_request_proxy_bypass: config
+                .data_request_proxy_bypass
+                .clone()
+                .unwrap_or_else(|| defaults.mining_data_request_proxy_bypass()),
             data_request_max_retrievals_per_epoch: config
                 .data_request_max_retrievals_per_epoch
                 .to_owned()
@@ -793,6 +930,20 @@ impl Mining {
         PartialMining {
             enabled: Some(self.enabled),
             data_request_timeout: Some(self.data_request_timeout),
+            data_request_connect_timeout: Some(self.data_request_connect_timeout),
+            data_request_read_timeout: Some(self.data_request_read_timeout),
+            data_request_max_retries: Some(self.data_request_max_retries),
+            data_request_retry_backoff: Some(self.data_request_retry_backoff),
+            data_request_max_response_size: Some(self.data_request_max_response_size),
+            data_request_cache_size: Some(self.data_request_cache_size),
+            data_request_max_concurrent_requests_per_host: Some(
+                self.data_request_max_concurrent_requests_per_host,
+            ),
+            data_request_min_interval_per_host: Some(self.data_request_min_interval_per_host),
+            data_request_proxy_url: self.data_request_proxy_url.clone(),
+            data_request_proxy_username: self.data_request_proxy_username.clone(),
+            data_request_proxy_password: self.data_request_proxy_password.clone(),
+            data_request_proxy_bypass: Some(self.data_request_proxy_bypass.clone()),
             data_request_max_retrievals_per_epoch: Some(self.data_request_max_retrievals_per_epoch),
             genesis_path: Some(self.genesis_path.clone()),
             mint_external_percentage: Some(self.mint_external_percentage),
@@ -874,6 +1025,11 @@ pub struct Wallet {
     #[partial_struct(skip)]
     #[partial_struct(serde(default))]
     pub concurrency: Option<usize>,
+    /// Record the responses of the data request retrievals run by the wallet into a cassette
+    /// file, or replay them from it.
+    #[partial_struct(skip)]
+    #[partial_struct(serde(default))]
+    pub rad_cassette: Option<RadCassette>,
     /// Database path.
     pub db_path: PathBuf,
     /// Database file name.
@@ -913,6 +1069,7 @@ impl Wallet {
             node_url: config.node_url.clone(),
             node_sync_batch_size: config.node_sync_batch_size.unwrap_or(50),
             concurrency: config.concurrency,
+            rad_cassette: config.rad_cassette.clone(),
             db_path: config.db_path.clone().unwrap_or_else(dirs::data_dir),
             db_file_name: config
                 .db_file_name
@@ -955,6 +1112,7 @@ impl Wallet {
             node_url: self.node_url.clone(),
             node_sync_batch_size: Some(self.node_sync_batch_size),
             concurrency: self.concurrency,
+            rad_cassette: self.rad_cassette.clone(),
             db_path: Some(self.db_path.clone()),
             db_file_name: Some(self.db_file_name.clone()),
             db_encrypt_hash_iterations: Some(self.db_encrypt_hash_iterations),
@@ -971,6 +1129,24 @@ impl Wallet {
     }
 }
 
+/// Whether a cassette file is used for recording or replaying retrieval responses
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+pub enum RadCassetteMode {
+    #[serde(rename = "record")]
+    Record,
+    #[serde(rename = "replay")]
+    Replay,
+}
+
+/// Cassette file used for recording or replaying retrieval responses
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct RadCassette {
+    /// Whether to record or replay the responses
+    pub mode: RadCassetteMode,
+    /// Path of the cassette file
+    pub path: PathBuf,
+}
+
 /// Rocksdb-specific configuration
 #[derive(PartialStruct, Serialize, Debug, Clone, PartialEq)]
 #[partial_struct(derive(Deserialize, Serialize, Default, Debug, Clone, PartialEq))]
@@ -1284,19 +1460,33 @@ mod tests {
         let config = JsonRPC::from_partial(&partial_config, &Testnet);
 
         assert_eq!(config.server_address, Testnet.jsonrpc_server_address());
+        assert_eq!(config.http_address, None);
+        assert_eq!(config.ws_address, None);
+        assert_eq!(config.allowed_hosts, Testnet.jsonrpc_allowed_hosts());
+        assert_eq!(config.allowed_origins, Testnet.jsonrpc_allowed_origins());
     }
 
     #[test]
     fn test_jsonrpc_from_partial() {
         let addr: SocketAddr = "127.0.0.1:4000".parse().unwrap();
+        let http_addr: SocketAddr = "127.0.0.1:4001".parse().unwrap();
+        let ws_addr: SocketAddr = "127.0.0.1:4002".parse().unwrap();
         let partial_config = PartialJsonRPC {
             enabled: None,
             server_address: Some(addr),
             enable_sensitive_methods: None,
+            http_address: Some(http_addr),
+            ws_address: Some(ws_addr),
+            allowed_hosts: Some(vec!["node.example.com:*".to_string()]),
+            allowed_origins: Some(vec!["*".to_string()]),
         };
         let config = JsonRPC::from_partial(&partial_config, &Testnet);
 
         assert_eq!(config.server_address, addr);
+        assert_eq!(config.http_address, Some(http_addr));
+        assert_eq!(config.ws_address, Some(ws_addr));
+        assert_eq!(config.allowed_hosts, vec!["node.example.com:*".to_string()]);
+        assert_eq!(config.allowed_origins, vec!["*".to_string()]);
     }
 
     #[test]
@@ -182,6 +182,19 @@ pub trait Defaults {
         true
     }
 
+    /// Only accept the addresses the JSON-RPC servers are bound to as `Host`
+    fn jsonrpc_allowed_hosts(&self) -> Vec<String> {
+        vec![]
+    }
+
+    /// Only allow web pages served from localhost to use the JSON-RPC servers
+    fn jsonrpc_allowed_origins(&self) -> Vec<String> {
+        vec![
+            "http://localhost:*".to_string(),
+            "http://127.0.0.1:*".to_string(),
+        ]
+    }
+
     /// MiningManager, enabled by default
     fn mining_enabled(&self) -> bool {
         true
@@ -192,6 +205,53 @@ pub trait Defaults {
         Duration::from_secs(2)
     }
 
+    // The limits of each data request source match `witnet_rad::transport::RetrievalLimits::default()`
+
+    /// Timeout for receiving the response headers of each data request source
+    fn mining_data_request_connect_timeout(&self) -> Duration {
+        Duration::from_secs(10)
+    }
+
+    /// Timeout for receiving the response body of each data request source
+    fn mining_data_request_read_timeout(&self) -> Duration {
+        Duration::from_secs(10)
+    }
+
+    /// Retry each data request source twice after a transient error
+    fn mining_data_request_max_retries(&self) -> u32 {
+        2
+    }
+
+    /// Wait 250 milliseconds before retrying a data request source
+    fn mining_data_request_retry_backoff(&self) -> Duration {
+        Duration::from_millis(250)
+    }
+
+    /// Limit the response body of each data request source to 10 MiB
+    fn mining_data_request_max_response_size(&self) -> usize {
+        10 * 1024 * 1024
+    }
+
+    /// Cache up to 256 data request source responses per epoch
+    fn mining_data_request_cache_size(&self) -> usize {
+        256
+    }
+
+    /// Allow up to 4 concurrent requests to the same host
+    fn mining_data_request_max_concurrent_requests_per_host(&self) -> usize {
+        4
+    }
+
+    /// Do not limit the rate of requests to the same host
+    fn mining_data_request_min_interval_per_host(&self) -> Duration {
+        Duration::from_millis(0)
+    }
+
+    /// Send the requests to every host through the proxy, if there is one
+    fn mining_data_request_proxy_bypass(&self) -> Vec<String> {
+        vec![]
+    }
+
     /// Set the limit of retrievals per epoch to 65_535.
     /// This in practice equals no limit enforcement.
     fn mining_data_request_max_retrievals_per_epoch(&self) -> u16 {
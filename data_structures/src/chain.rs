@@ -1560,6 +1560,12 @@ pub enum RADType {
     /// HTTP GET request
     #[serde(rename = "HTTP-GET")]
     HttpGet,
+    /// HTTP POST request
+    #[serde(rename = "HTTP-POST")]
+    HttpPost,
+    /// Random bytes generated locally by each witness
+    #[serde(rename = "RNG")]
+    Rng,
 }
 
 impl Default for RADType {
@@ -1601,6 +1607,9 @@ impl RADRequest {
     }
 }
 
+/// Maximum total weight of the extra HTTP headers of a single retrieval source
+pub const MAX_RETRIEVAL_HEADERS_WEIGHT: u32 = 1_024;
+
 /// Retrieve script and source
 #[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize, ProtobufConvert, Hash, Default)]
 #[protobuf_convert(
@@ -1610,20 +1619,57 @@ impl RADRequest {
 pub struct RADRetrieve {
     /// Kind of retrieval
     pub kind: RADType,
-    /// URL
+    /// URL, which is empty for RNG sources
     pub url: String,
     /// Serialized RADON script
     pub script: Vec<u8>,
+    /// Body of the request, only used in HTTP-POST requests
+    #[serde(default)]
+    pub body: Vec<u8>,
+    /// Value of the `Content-Type` header, only used in HTTP-POST requests
+    #[serde(default)]
+    pub content_type: String,
+    /// Extra HTTP headers to be sent along with the request, as (name, value) pairs
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    /// Whether the response body is passed to the script as `Bytes`, instead of being decoded
+    /// into a `String`
+    #[serde(default)]
+    pub binary: bool,
 }
 
 impl RADRetrieve {
     /// Return the weight, used to enforce the block size limit.
     pub fn weight(&self) -> u32 {
         // RADType: 1 byte
+        // binary: 1 byte, only if set
         let script_weight = u32::try_from(self.script.len()).unwrap_or(u32::MAX);
         let url_weight = u32::try_from(self.url.len()).unwrap_or(u32::MAX);
+        let body_weight = u32::try_from(self.body.len()).unwrap_or(u32::MAX);
+        let content_type_weight = u32::try_from(self.content_type.len()).unwrap_or(u32::MAX);
+
+        script_weight
+            .saturating_add(url_weight)
+            .saturating_add(body_weight)
+            .saturating_add(content_type_weight)
+            .saturating_add(self.headers_weight())
+            .saturating_add(u32::from(self.binary))
+            .saturating_add(1)
+    }
+
+    /// Return the weight of the extra HTTP headers, which cannot exceed
+    /// `MAX_RETRIEVAL_HEADERS_WEIGHT`.
+    pub fn headers_weight(&self) -> u32 {
+        let mut headers_weight: u32 = 0;
+        for (name, value) in self.headers.iter() {
+            let name_weight = u32::try_from(name.len()).unwrap_or(u32::MAX);
+            let value_weight = u32::try_from(value.len()).unwrap_or(u32::MAX);
+            headers_weight = headers_weight
+                .saturating_add(name_weight)
+                .saturating_add(value_weight);
+        }
 
-        script_weight.saturating_add(url_weight).saturating_add(1)
+        headers_weight
     }
 }
 
@@ -3966,6 +4012,21 @@ mod tests {
         tally_txs
     }
 
+    #[test]
+    fn test_rad_retrieve_weight() {
+        let mut retrieve = RADRetrieve {
+            kind: RADType::HttpGet,
+            url: "https://example.com".to_string(),
+            script: vec![0x80],
+            ..RADRetrieve::default()
+        };
+        // kind + url + script
+        assert_eq!(retrieve.weight(), 1 + 19 + 1);
+
+        retrieve.binary = true;
+        assert_eq!(retrieve.weight(), 1 + 19 + 1 + 1);
+    }
+
     #[test]
     fn test_block_hashable_trait() {
         let block = block_example();
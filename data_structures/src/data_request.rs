@@ -427,7 +427,7 @@ pub fn calculate_witness_reward(
 /// Count how many commitments will be considered "errors" and how many will
 /// be considered "lies". This tells apart the case in which the committed value was
 /// an error from any other type of out-of-consensus situation, like non-reveals.
-fn calculate_errors_and_liars_count(errors: &[bool], liars: &[bool]) -> (usize, usize) {
+pub fn calculate_errors_and_liars_count(errors: &[bool], liars: &[bool]) -> (usize, usize) {
     liars
         .iter()
         .zip(errors.iter())
@@ -3,7 +3,9 @@
 use failure::Fail;
 use std::num::ParseIntError;
 
-use crate::chain::{DataRequestOutput, Epoch, Hash, HashParseError, OutputPointer, PublicKeyHash};
+use crate::chain::{
+    DataRequestOutput, Epoch, Hash, HashParseError, OutputPointer, PublicKeyHash, RADType,
+};
 
 /// The error type for operations on a [`ChainInfo`](ChainInfo)
 #[derive(Debug, PartialEq, Fail)]
@@ -485,6 +487,45 @@ pub enum DataRequestError {
     UnfinishedDataRequest,
     #[fail(display = "The data request is not valid since it has no retrieval sources")]
     NoRetrievalSources,
+    #[fail(
+        display = "The data request is not valid since a retrieval source of kind {:?} has a non-empty `{}` field",
+        kind, field
+    )]
+    UnexpectedRetrievalField { kind: RADType, field: &'static str },
+    #[fail(
+        display = "The data request is not valid since retrieval sources of kind {:?} are not supported yet",
+        kind
+    )]
+    UnsupportedRetrievalKind { kind: RADType },
+    #[fail(
+        display = "The data request is not valid since a retrieval source has an invalid content type: {:?}",
+        content_type
+    )]
+    InvalidContentType { content_type: String },
+    #[fail(
+        display = "The data request is not valid since a retrieval source has an invalid HTTP header: {:?}: {:?}",
+        name, value
+    )]
+    InvalidHttpHeader { name: String, value: String },
+    #[fail(
+        display = "The data request is not valid since the `Content-Type` of a retrieval source must be set through its `content_type` field"
+    )]
+    ContentTypeInHeaders,
+    #[fail(
+        display = "The data request is not valid since the HTTP headers of a retrieval source are too heavy: {} > {}",
+        weight, max_weight
+    )]
+    HeadersTooHeavy { weight: u32, max_weight: u32 },
+    #[fail(
+        display = "The data request is not valid since it mixes RNG sources with other sources or does not combine them properly: {}",
+        reason
+    )]
+    InvalidRngRequest { reason: &'static str },
+    #[fail(
+        display = "The number of reveals ({}) cannot be greater than the number of commits ({})",
+        reveals, commits
+    )]
+    MoreRevealsThanCommits { reveals: usize, commits: usize },
 }
 
 /// Possible errors when converting between epoch and timestamp
@@ -30,6 +30,9 @@ pub const SECOND_HARD_FORK: Epoch = 376320;
 /// 3 June 2021  @ 9:00:00 UTC
 pub const THIRD_HARD_FORK: Epoch = 445440;
 
+/// WIPs that are not scheduled for activation in mainnet yet
+const PENDING_WIPS: [&str; 4] = ["WIP0017", "WIP0019", "WIP0020", "WIP0021"];
+
 /// TAPI Engine
 #[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
 pub struct TapiEngine {
@@ -119,6 +122,11 @@ impl TapiEngine {
                 self.wip_activation
                     .insert("WIP0009-0011-0012".to_string(), 0);
                 self.wip_activation.insert("THIRD_HARD_FORK".to_string(), 0);
+                // The WIPs that are not scheduled in mainnet yet are also active since epoch 0, so
+                // that their features can be tested
+                for wip in &PENDING_WIPS {
+                    self.wip_activation.insert(wip.to_string(), 0);
+                }
             }
         };
 
@@ -373,6 +381,42 @@ impl ActiveWips {
     pub fn wip0016(&self) -> bool {
         self.wip_active("WIP0014-0016")
     }
+
+    // WIP 0017 allows more filters and reducers in the aggregation and tally stages
+    pub fn wip0017(&self) -> bool {
+        self.wip_active("WIP0017")
+    }
+
+    // WIP 0019 adds RNG retrieval sources and the HashConcatenate reducer
+    pub fn wip0019(&self) -> bool {
+        self.wip_active("WIP0019")
+    }
+
+    // WIP 0020 adds HTTP-POST requests, extra HTTP headers and binary responses to retrieval
+    // sources
+    pub fn wip0020(&self) -> bool {
+        self.wip_active("WIP0020")
+    }
+
+    // WIP 0021 adds new RADON operators and errors, and limits the execution cost of the retrieval
+    // and aggregation stages
+    pub fn wip0021(&self) -> bool {
+        self.wip_active("WIP0021")
+    }
+}
+
+/// Returns the WIPs that are known to be active in the current environment at any future epoch.
+///
+/// This is meant for validating data requests outside of the chain, e.g. in the wallet, the CLI or
+/// the bridges, where the WIPs activated through voting are not known.
+pub fn current_active_wips() -> ActiveWips {
+    let mut tapi_engine = TapiEngine::default();
+    tapi_engine.initialize_wip_information(crate::get_environment());
+
+    ActiveWips {
+        active_wips: tapi_engine.wip_activation,
+        block_epoch: Epoch::MAX,
+    }
 }
 
 #[cfg(test)]
@@ -669,10 +713,35 @@ mod tests {
         let mut t_testnet = TapiEngine::default();
         let (_epoch, _old_wips) = t_testnet.initialize_wip_information(Environment::Testnet);
 
-        // The keys of the wip_activation map should be the same
+        // The keys of the wip_activation map should be the same, plus the pending WIPs in testnet
+        let mut mainnet_wips = t_mainnet
+            .wip_activation
+            .keys()
+            .cloned()
+            .collect::<HashSet<_>>();
+        mainnet_wips.extend(PENDING_WIPS.iter().map(|wip| wip.to_string()));
         assert_eq!(
-            t_testnet.wip_activation.keys().collect::<HashSet<_>>(),
-            t_mainnet.wip_activation.keys().collect::<HashSet<_>>(),
+            t_testnet
+                .wip_activation
+                .keys()
+                .cloned()
+                .collect::<HashSet<_>>(),
+            mainnet_wips,
         )
     }
+
+    #[test]
+    fn test_pending_wips_active_in_testnet() {
+        let mut t = TapiEngine::default();
+        t.initialize_wip_information(Environment::Testnet);
+        let active_wips = ActiveWips {
+            active_wips: t.wip_activation,
+            block_epoch: 0,
+        };
+
+        assert!(active_wips.wip0017());
+        assert!(active_wips.wip0019());
+        assert!(active_wips.wip0020());
+        assert!(active_wips.wip0021());
+    }
 }
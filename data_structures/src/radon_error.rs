@@ -6,6 +6,8 @@ use num_enum::{IntoPrimitive, TryFromPrimitive};
 use serde::Serialize;
 use serde_cbor::Value as SerdeCborValue;
 
+use crate::mainnet_validations::ActiveWips;
+
 #[derive(Clone, Copy, Debug, Eq, IntoPrimitive, PartialEq, Serialize, TryFromPrimitive)]
 #[repr(u8)]
 /// List of RADON-level errors.
@@ -26,9 +28,15 @@ pub enum RadonErrors {
     RequestTooManySources = 0x10,
     /// The script contains too many calls.
     ScriptTooManyCalls = 0x11,
+    /// The execution of the script exceeded the cost budget of its stage.
+    ScriptTooExpensive = 0x12,
     // Operator errors
     /// The operator does not exist.
     UnsupportedOperator = 0x20,
+    /// A string could not be parsed as a date with the given format.
+    ParseDateTime = 0x21,
+    /// A timestamp is older than the maximum age allowed by the script.
+    StaleTimestamp = 0x22,
     // Retrieval-specific errors
     /// At least one of the sources could not be retrieved, but returned HTTP error.
     HTTPError = 0x30,
@@ -72,6 +80,19 @@ pub enum RadonErrors {
     UnhandledIntercept = 0xFF,
 }
 
+impl RadonErrors {
+    /// Whether this error can be decoded from the reveals of a data request.
+    pub fn is_active(self, active_wips: &ActiveWips) -> bool {
+        match self {
+            // The errors that are newer than the protocol are unknown until WIP0021
+            RadonErrors::ScriptTooExpensive
+            | RadonErrors::ParseDateTime
+            | RadonErrors::StaleTimestamp => active_wips.wip0021(),
+            _ => true,
+        }
+    }
+}
+
 /// Use `RadonErrors::Unknown` as the default value of `RadonErrors`.
 impl Default for RadonErrors {
     fn default() -> Self {
@@ -3,7 +3,7 @@ use std::time::{Duration, SystemTime};
 
 use serde::Serialize;
 
-use crate::radon_error::ErrorLike;
+use crate::{mainnet_validations::ActiveWips, radon_error::ErrorLike};
 
 /// A high level data structure aimed to be used as the return type of RAD executor methods:
 ///
@@ -26,6 +26,8 @@ where
     pub result: RT,
     /// Keep track of how many milliseconds did the execution take to complete or fail.
     pub running_time: Duration,
+    /// The execution cost charged for the calls that were executed, including those in subscripts.
+    pub execution_cost: u64,
 }
 
 /// Implementations, factories and convenience methods for `RadonReport`.
@@ -42,6 +44,7 @@ where
             partial_results: None,
             result: intercepted,
             running_time: context.duration(),
+            execution_cost: context.execution_cost,
         }
     }
 
@@ -62,6 +65,7 @@ where
             partial_results: Some(intercepted),
             result,
             running_time: context.duration(),
+            execution_cost: context.execution_cost,
         }
     }
 
@@ -125,12 +129,19 @@ where
     pub call_operator: Option<usize>,
     /// The timestamp when the execution of the script finished.
     pub completion_time: Option<SystemTime>,
+    /// The execution cost charged so far for the calls that have been processed, including those
+    /// in subscripts.
+    pub execution_cost: u64,
     /// Metadata that is specific to the stage of the script.
     pub stage: Stage<RT>,
     /// The timestamp when the execution of the script began.
     pub start_time: Option<SystemTime>,
     /// The index of the last script or subscript in a stage that has been processed.
     pub script_index: Option<usize>,
+    /// The WIPs that are active for the data request, which decide what features of the RADON
+    /// engine are enabled. If `None`, all of them are.
+    #[serde(skip)]
+    pub active_wips: Option<ActiveWips>,
 }
 
 impl<RT> Default for ReportContext<RT>
@@ -144,9 +155,11 @@ where
             call_index: None,
             call_operator: None,
             completion_time: None,
+            execution_cost: 0,
             stage: Stage::Contextless,
             start_time: None,
             script_index: None,
+            active_wips: None,
         }
     }
 }
@@ -228,6 +241,10 @@ where
     /// * `element_index` is the index of the element inside the array that serves as the input of
     ///     the subscript.
     pub subscript_partial_results: Vec<Vec<Vec<RT>>>,
+    /// Timestamp of the epoch in which the data request is resolved, in seconds since the UNIX
+    /// epoch, which is what the date operators compare dates against. If it is `None`, as when
+    /// trying a data request locally, the current time is used instead.
+    pub timestamp: Option<i64>,
 }
 
 impl<RT> Default for RetrievalMetadata<RT>
@@ -237,6 +254,7 @@ where
     fn default() -> Self {
         Self {
             subscript_partial_results: vec![],
+            timestamp: None,
         }
     }
 }
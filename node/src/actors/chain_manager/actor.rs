@@ -6,6 +6,7 @@ use crate::{
     actors::{
         epoch_manager::{EpochManager, EpochManagerError::CheckpointZeroInTheFuture},
         messages::{AddBlocks, GetEpoch, GetEpochConstants, SetLastBeacon, Subscribe},
+        rad_manager::retrieval_cache::RetrievalCacheLimits,
         sessions_manager::SessionsManager,
         storage_keys,
     },
@@ -25,6 +26,7 @@ use witnet_data_structures::{
     vrf::VrfCtx,
 };
 
+use witnet_rad::{proxy::RetrievalProxy, transport::RetrievalLimits};
 use witnet_util::timestamp::pretty_print;
 
 /// Implement Actor trait for `ChainManager`
@@ -83,6 +85,46 @@ impl ChainManager {
                     act.data_request_timeout = Some(config.mining.data_request_timeout);
                 }
 
+                // A value of 0 disables the corresponding limit
+                let non_zero_duration =
+                    |duration: Duration| Some(duration).filter(|d| *d != Duration::new(0, 0));
+                let mining = &config.mining;
+                act.data_request_retrieval_limits = RetrievalLimits {
+                    connect_timeout: non_zero_duration(mining.data_request_connect_timeout),
+                    read_timeout: non_zero_duration(mining.data_request_read_timeout),
+                    max_retries: mining.data_request_max_retries,
+                    retry_backoff: mining.data_request_retry_backoff,
+                    // Retrying after the data request has timed out would be useless
+                    max_retry_duration: act.data_request_timeout,
+                    max_response_size: Some(mining.data_request_max_response_size)
+                        .filter(|size| *size != 0),
+                };
+                act.data_request_cache_limits = RetrievalCacheLimits {
+                    max_entries: mining.data_request_cache_size,
+                    max_concurrent_per_host: Some(mining.data_request_max_concurrent_requests_per_host)
+                        .filter(|max| *max != 0),
+                    min_interval_per_host: non_zero_duration(mining.data_request_min_interval_per_host),
+                };
+                act.data_request_proxy = mining.data_request_proxy_url.as_ref().and_then(|url| {
+                    match RetrievalProxy::new(
+                        url,
+                        mining.data_request_proxy_username.as_deref(),
+                        mining.data_request_proxy_password.as_deref(),
+                        mining.data_request_proxy_bypass.clone(),
+                    ) {
+                        Ok(proxy) => {
+                            log::info!("Data request sources will be retrieved through proxy {}", proxy.url());
+
+                            Some(proxy)
+                        }
+                        Err(e) => {
+                            log::error!("Invalid data request proxy configuration, data request sources will be retrieved without a proxy: {}", e);
+
+                            None
+                        }
+                    }
+                });
+
                 // Set the retrievals limit per epoch, as read from the configuration
                 act.data_request_max_retrievals_per_epoch = config.mining.data_request_max_retrievals_per_epoch;
 
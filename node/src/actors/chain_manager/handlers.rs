@@ -14,6 +14,7 @@ use witnet_data_structures::{
         SuperBlockVote,
     },
     error::{ChainInfoError, TransactionError::DataRequestNotFound},
+    mainnet_validations::ActiveWips,
     transaction::{DRTransaction, Transaction, VTTransaction},
     transaction_factory::{self, NodeBalance},
     types::LastBeacon,
@@ -1272,7 +1273,13 @@ impl Handler<BuildDrt> for ChainManager {
                 .into(),
             ));
         }
-        if let Err(e) = validate_rad_request(&msg.dro.data_request) {
+        let active_wips = ActiveWips {
+            active_wips: self.chain_state.tapi_engine.wip_activation.clone(),
+            // The data request will be included in a block whose epoch is greater than or equal to
+            // the current epoch
+            block_epoch: self.current_epoch.unwrap_or_default(),
+        };
+        if let Err(e) = validate_rad_request(&msg.dro.data_request, &active_wips) {
             return Box::pin(actix::fut::err(e));
         }
         let timestamp = u64::try_from(get_timestamp()).unwrap();
@@ -290,6 +290,12 @@ impl ChainManager {
         let own_pkh = self.own_pkh.unwrap();
         let current_epoch = self.current_epoch.unwrap();
         let data_request_timeout = self.data_request_timeout;
+        let data_request_retrieval_limits = self.data_request_retrieval_limits;
+        let data_request_cache_limits = self.data_request_cache_limits;
+        let data_request_proxy = self.data_request_proxy.clone();
+        let data_request_timestamp = self
+            .epoch_constants
+            .and_then(|epoch_constants| epoch_constants.epoch_timestamp(current_epoch).ok());
         let timestamp = u64::try_from(get_timestamp()).unwrap();
         let consensus_constants = self.consensus_constants();
         let minimum_reppoe_difficulty = consensus_constants.minimum_difficulty;
@@ -363,6 +369,7 @@ impl ChainManager {
             // Grab a reference to `current_retrieval_count`
             let cloned_retrieval_count = Arc::clone(&current_retrieval_count);
             let cloned_retrieval_count2 = Arc::clone(&current_retrieval_count);
+            let data_request_proxy = data_request_proxy.clone();
             let added_retrieval_count =
                 u16::try_from(dr_state.data_request.data_request.retrieve.len())
                     .unwrap_or(core::u16::MAX);
@@ -545,6 +552,11 @@ impl ChainManager {
                         .send(ResolveRA {
                             rad_request,
                             timeout: data_request_timeout,
+                            limits: data_request_retrieval_limits,
+                            cache_limits: data_request_cache_limits,
+                            proxy: data_request_proxy,
+                            epoch: current_epoch,
+                            timestamp: data_request_timestamp,
                             active_wips,
                         })
                         .map(move |res|
@@ -669,6 +681,10 @@ impl ChainManager {
 
                     async move {
                         log::debug!("Building tally for data request {}", dr_pointer);
+                        let active_wips = ActiveWips {
+                            active_wips,
+                            block_epoch,
+                        };
 
                         // Use the serial decoder to decode all the reveals in a lossy way, i.e. will
                         // ignore reveals that cannot be decoded. At this point, reveals that cannot be
@@ -691,6 +707,7 @@ impl ChainManager {
                                     &ReportContext::default(),
                                 ))
                             },
+                            &active_wips,
                         );
 
                         let min_consensus_ratio =
@@ -703,10 +720,6 @@ impl ChainManager {
                         let rad_manager_addr = RadManager::from_registry();
 
                         // The result of `RunTally` will be published as tally
-                        let active_wips = ActiveWips {
-                            active_wips,
-                            block_epoch,
-                        };
                         let tally_result = rad_manager_addr
                             .send(RunTally {
                                 min_consensus_ratio,
@@ -64,7 +64,7 @@ use witnet_data_structures::{
     vrf::VrfCtx,
 };
 
-use witnet_rad::types::RadonTypes;
+use witnet_rad::{proxy::RetrievalProxy, transport::RetrievalLimits, types::RadonTypes};
 use witnet_util::timestamp::seconds_to_human_string;
 use witnet_validations::validations::{
     compare_block_candidates, validate_block, validate_block_transactions,
@@ -83,6 +83,7 @@ use crate::{
             SetLastBeacon, SetSuperBlockTargetBeacon, StoreInventoryItem, SuperBlockNotify,
         },
         peers_manager::PeersManager,
+        rad_manager::retrieval_cache::RetrievalCacheLimits,
         sessions_manager::SessionsManager,
         storage_keys,
     },
@@ -202,6 +203,12 @@ pub struct ChainManager {
     data_request_max_retrievals_per_epoch: u16,
     /// Timeout for data request retrieval and aggregation execution
     data_request_timeout: Option<Duration>,
+    /// Limits applied to the HTTP request of each data request source
+    data_request_retrieval_limits: RetrievalLimits,
+    /// Limits of the cache of data request source responses, and of the requests sent to each host
+    data_request_cache_limits: RetrievalCacheLimits,
+    /// Proxy through which the data request sources are retrieved, if any
+    data_request_proxy: Option<RetrievalProxy>,
     /// Pending transaction timeout
     tx_pending_timeout: u64,
     /// Magic number from ConsensusConstants
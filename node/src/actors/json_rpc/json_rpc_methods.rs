@@ -2,6 +2,7 @@ use std::{
     collections::HashMap,
     convert::TryFrom,
     net::SocketAddr,
+    panic,
     sync::atomic::{AtomicUsize, Ordering},
     sync::Arc,
 };
@@ -16,10 +17,13 @@ use serde::{Deserialize, Serialize};
 
 use witnet_crypto::key::KeyPath;
 use witnet_data_structures::{
-    chain::{Block, Epoch, Hash, Hashable, PublicKeyHash, StateMachine, SyncStatus},
+    chain::{
+        Block, DataRequestInfo, Epoch, Hash, Hashable, PublicKeyHash, StateMachine, SyncStatus,
+    },
     transaction::Transaction,
     vrf::VrfMessage,
 };
+use witnet_rad::types::RadonTypes;
 
 use crate::{
     actors::{
@@ -1088,7 +1092,7 @@ pub async fn data_request_report(params: Result<(Hash,), jsonrpc_core::Error>) -
         .map(|res| {
             res.map_err(internal_error)
                 .and_then(|dr_info| match dr_info {
-                    Ok(x) => match serde_json::to_value(&x) {
+                    Ok(x) => match data_request_report_json(&x) {
                         Ok(x) => Ok(x),
                         Err(e) => {
                             let err = internal_error_s(e);
@@ -1101,6 +1105,40 @@ pub async fn data_request_report(params: Result<(Hash,), jsonrpc_core::Error>) -
         .await
 }
 
+/// Serialize a data request report, adding the reveals and the tally result in the lossless JSON
+/// encoding of RADON values, so that clients do not need to decode their CBOR representation.
+/// Values that cannot be decoded are set to `null`.
+fn data_request_report_json(dr_info: &DataRequestInfo) -> Result<Value, serde_json::Error> {
+    let decode = |bytes: &[u8]| {
+        // Decoding malformed CBOR may panic
+        panic::catch_unwind(|| RadonTypes::try_from(bytes))
+            .ok()
+            .and_then(|value| {
+                value
+                    .and_then(|value| witnet_rad::json::to_json(&value))
+                    .ok()
+            })
+            .unwrap_or(Value::Null)
+    };
+
+    let mut report = serde_json::to_value(dr_info)?;
+    if let Value::Object(report) = &mut report {
+        let reveal_results = dr_info
+            .reveals
+            .iter()
+            .map(|(pkh, reveal)| (pkh.to_string(), decode(&reveal.body.reveal)))
+            .collect();
+        let tally_result = dr_info
+            .tally
+            .as_ref()
+            .map_or(Value::Null, |tally| decode(&tally.tally));
+        report.insert("reveal_results".to_string(), Value::Object(reveal_results));
+        report.insert("tally_result".to_string(), tally_result);
+    }
+
+    Ok(report)
+}
+
 /// Params of getBlockChain method
 #[derive(Debug, Default, Deserialize, Serialize)]
 pub struct GetBalanceParams {
@@ -1819,7 +1857,7 @@ mod tests {
         let block = block_example();
         let inv_elem = InventoryItem::Block(block);
         let s = serde_json::to_string(&inv_elem).unwrap();
-        let expected = r#"{"block":{"block_header":{"signals":0,"beacon":{"checkpoint":0,"hashPrevBlock":"0000000000000000000000000000000000000000000000000000000000000000"},"merkle_roots":{"mint_hash":"0000000000000000000000000000000000000000000000000000000000000000","vt_hash_merkle_root":"0000000000000000000000000000000000000000000000000000000000000000","dr_hash_merkle_root":"0000000000000000000000000000000000000000000000000000000000000000","commit_hash_merkle_root":"0000000000000000000000000000000000000000000000000000000000000000","reveal_hash_merkle_root":"0000000000000000000000000000000000000000000000000000000000000000","tally_hash_merkle_root":"0000000000000000000000000000000000000000000000000000000000000000"},"proof":{"proof":{"proof":[],"public_key":{"compressed":0,"bytes":[0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0]}}},"bn256_public_key":null},"block_sig":{"signature":{"Secp256k1":{"der":[]}},"public_key":{"compressed":0,"bytes":[0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0]}},"txns":{"mint":{"epoch":0,"outputs":[]},"value_transfer_txns":[],"data_request_txns":[{"body":{"inputs":[{"output_pointer":"0000000000000000000000000000000000000000000000000000000000000000:0"}],"outputs":[{"pkh":"wit1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqwrt3a4","value":0,"time_lock":0}],"dr_output":{"data_request":{"time_lock":0,"retrieve":[{"kind":"HTTP-GET","url":"https://openweathermap.org/data/2.5/weather?id=2950159&appid=b6907d289e10d714a6e88b30761fae22","script":[]},{"kind":"HTTP-GET","url":"https://openweathermap.org/data/2.5/weather?id=2950159&appid=b6907d289e10d714a6e88b30761fae22","script":[]}],"aggregate":{"filters":[],"reducer":0},"tally":{"filters":[],"reducer":0}},"witness_reward":0,"witnesses":0,"commit_and_reveal_fee":0,"min_consensus_percentage":0,"collateral":0}},"signatures":[{"signature":{"Secp256k1":{"der":[]}},"public_key":{"compressed":0,"bytes":[0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0]}}]}],"commit_txns":[],"reveal_txns":[],"tally_txns":[]}}}"#;
+        let expected = r#"{"block":{"block_header":{"signals":0,"beacon":{"checkpoint":0,"hashPrevBlock":"0000000000000000000000000000000000000000000000000000000000000000"},"merkle_roots":{"mint_hash":"0000000000000000000000000000000000000000000000000000000000000000","vt_hash_merkle_root":"0000000000000000000000000000000000000000000000000000000000000000","dr_hash_merkle_root":"0000000000000000000000000000000000000000000000000000000000000000","commit_hash_merkle_root":"0000000000000000000000000000000000000000000000000000000000000000","reveal_hash_merkle_root":"0000000000000000000000000000000000000000000000000000000000000000","tally_hash_merkle_root":"0000000000000000000000000000000000000000000000000000000000000000"},"proof":{"proof":{"proof":[],"public_key":{"compressed":0,"bytes":[0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0]}}},"bn256_public_key":null},"block_sig":{"signature":{"Secp256k1":{"der":[]}},"public_key":{"compressed":0,"bytes":[0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0]}},"txns":{"mint":{"epoch":0,"outputs":[]},"value_transfer_txns":[],"data_request_txns":[{"body":{"inputs":[{"output_pointer":"0000000000000000000000000000000000000000000000000000000000000000:0"}],"outputs":[{"pkh":"wit1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqwrt3a4","value":0,"time_lock":0}],"dr_output":{"data_request":{"time_lock":0,"retrieve":[{"kind":"HTTP-GET","url":"https://openweathermap.org/data/2.5/weather?id=2950159&appid=b6907d289e10d714a6e88b30761fae22","script":[],"body":[],"content_type":"","headers":[],"binary":false},{"kind":"HTTP-GET","url":"https://openweathermap.org/data/2.5/weather?id=2950159&appid=b6907d289e10d714a6e88b30761fae22","script":[],"body":[],"content_type":"","headers":[],"binary":false}],"aggregate":{"filters":[],"reducer":0},"tally":{"filters":[],"reducer":0}},"witness_reward":0,"witnesses":0,"commit_and_reveal_fee":0,"min_consensus_percentage":0,"collateral":0}},"signatures":[{"signature":{"Secp256k1":{"der":[]}},"public_key":{"compressed":0,"bytes":[0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0]}}]}],"commit_txns":[],"reveal_txns":[],"tally_txns":[]}}}"#;
         assert_eq!(s, expected, "\n{}\n", s);
     }
 
@@ -1832,12 +1870,20 @@ mod tests {
             kind: RADType::HttpGet,
             url: "https://openweathermap.org/data/2.5/weather?id=2950159&appid=b6907d289e10d714a6e88b30761fae22".to_string(),
             script: vec![0],
+            body: vec![],
+            content_type: String::new(),
+            headers: vec![],
+            binary: false,
         };
 
         let rad_retrieve_2 = RADRetrieve {
             kind: RADType::HttpGet,
             url: "https://openweathermap.org/data/2.5/weather?id=2950159&appid=b6907d289e10d714a6e88b30761fae22".to_string(),
             script: vec![0],
+            body: vec![],
+            content_type: String::new(),
+            headers: vec![],
+            binary: false,
         };
 
         let rad_consensus = RADTally::default();
@@ -1853,7 +1899,7 @@ mod tests {
 
         let inv_elem = InventoryItem::Transaction(transaction);
         let s = serde_json::to_string(&inv_elem).unwrap();
-        let expected = r#"{"transaction":{"DataRequest":{"body":{"inputs":[{"output_pointer":"0909090909090909090909090909090909090909090909090909090909090909:0"}],"outputs":[],"dr_output":{"data_request":{"time_lock":0,"retrieve":[{"kind":"HTTP-GET","url":"https://openweathermap.org/data/2.5/weather?id=2950159&appid=b6907d289e10d714a6e88b30761fae22","script":[0]},{"kind":"HTTP-GET","url":"https://openweathermap.org/data/2.5/weather?id=2950159&appid=b6907d289e10d714a6e88b30761fae22","script":[0]}],"aggregate":{"filters":[],"reducer":0},"tally":{"filters":[],"reducer":0}},"witness_reward":0,"witnesses":0,"commit_and_reveal_fee":0,"min_consensus_percentage":0,"collateral":0}},"signatures":[{"signature":{"Secp256k1":{"der":[]}},"public_key":{"compressed":0,"bytes":[0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0]}}]}}}"#;
+        let expected = r#"{"transaction":{"DataRequest":{"body":{"inputs":[{"output_pointer":"0909090909090909090909090909090909090909090909090909090909090909:0"}],"outputs":[],"dr_output":{"data_request":{"time_lock":0,"retrieve":[{"kind":"HTTP-GET","url":"https://openweathermap.org/data/2.5/weather?id=2950159&appid=b6907d289e10d714a6e88b30761fae22","script":[0],"body":[],"content_type":"","headers":[],"binary":false},{"kind":"HTTP-GET","url":"https://openweathermap.org/data/2.5/weather?id=2950159&appid=b6907d289e10d714a6e88b30761fae22","script":[0],"body":[],"content_type":"","headers":[],"binary":false}],"aggregate":{"filters":[],"reducer":0},"tally":{"filters":[],"reducer":0}},"witness_reward":0,"witnesses":0,"commit_and_reveal_fee":0,"min_consensus_percentage":0,"collateral":0}},"signatures":[{"signature":{"Secp256k1":{"der":[]}},"public_key":{"compressed":0,"bytes":[0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0]}}]}}}"#;
         assert_eq!(s, expected, "\n{}\n", s);
     }
 
@@ -1992,4 +2038,47 @@ mod tests {
             assert_eq!(response.unwrap(), error_msg);
         }
     }
+
+    #[test]
+    fn data_request_report_json_decodes_results() {
+        use witnet_rad::types::integer::RadonInteger;
+
+        let tally = Vec::<u8>::try_from(RadonTypes::from(RadonInteger::from(42))).unwrap();
+        let dr_info = DataRequestInfo {
+            tally: Some(TallyTransaction::new(
+                Hash::default(),
+                tally,
+                vec![],
+                vec![],
+                vec![],
+            )),
+            ..DataRequestInfo::default()
+        };
+
+        let report = data_request_report_json(&dr_info).unwrap();
+        assert_eq!(
+            report["tally_result"],
+            serde_json::json!({"RadonInteger": "42"})
+        );
+        assert_eq!(report["reveal_results"], serde_json::json!({}));
+        // The report can still be deserialized as a `DataRequestInfo`
+        assert_eq!(
+            serde_json::from_value::<DataRequestInfo>(report).unwrap(),
+            dr_info
+        );
+
+        // Undecodable results are null
+        let dr_info = DataRequestInfo {
+            tally: Some(TallyTransaction::new(
+                Hash::default(),
+                vec![0xff],
+                vec![],
+                vec![],
+                vec![],
+            )),
+            ..DataRequestInfo::default()
+        };
+        let report = data_request_report_json(&dr_info).unwrap();
+        assert_eq!(report["tally_result"], Value::Null);
+    }
 }
@@ -3,6 +3,7 @@ mod connection;
 pub mod json_rpc_methods;
 mod newline_codec;
 mod server;
+mod transports;
 
 pub use self::server::JsonRpcServer;
 use std::collections::HashMap;
@@ -6,7 +6,10 @@ use tokio_util::codec::FramedRead;
 use std::{collections::HashMap, collections::HashSet, net::SocketAddr, rc::Rc, sync::Arc};
 
 use super::{
-    connection::JsonRpc, json_rpc_methods::jsonrpc_io_handler, newline_codec::NewLineCodec,
+    connection::JsonRpc,
+    json_rpc_methods::jsonrpc_io_handler,
+    newline_codec::NewLineCodec,
+    transports::{start_http_server, start_ws_server},
     SubscriptionResult, Subscriptions,
 };
 use crate::{
@@ -31,11 +34,21 @@ pub struct JsonRpcServer {
     jsonrpc_io: Option<Rc<PubSubHandler<Arc<Session>>>>,
     /// List of subscriptions
     subscriptions: Subscriptions,
+    /// Server for JSON-RPC over HTTP, if enabled
+    http_server: Option<jsonrpc_http_server::Server>,
+    /// Server for JSON-RPC over WebSocket, if enabled
+    ws_server: Option<jsonrpc_ws_server::Server>,
 }
 
 impl Drop for JsonRpcServer {
     fn drop(&mut self) {
         log::trace!("Dropping JsonRpcServer");
+        if let Some(server) = self.http_server.take() {
+            server.close();
+        }
+        if let Some(server) = self.ws_server.take() {
+            server.close();
+        }
         stop_system_if_panicking("JsonRpcServer");
     }
 }
@@ -69,6 +82,42 @@ impl JsonRpcServer {
                 );
                 act.jsonrpc_io = Some(Rc::new(jsonrpc_io));
 
+                // The HTTP and WebSocket servers forward their requests to this actor
+                if let Some(http_address) = config.jsonrpc.http_address {
+                    match start_http_server(
+                        http_address,
+                        ctx.address(),
+                        &config.jsonrpc.allowed_hosts,
+                        &config.jsonrpc.allowed_origins,
+                    ) {
+                        Ok(server) => {
+                            log::debug!("JSON-RPC over HTTP is now running at {}", http_address);
+                            act.http_server = Some(server);
+                        }
+                        Err(e) => {
+                            log::error!("Could not start JSON-RPC HTTP server: {:?}", e);
+                            panic!("Could not start JSON-RPC HTTP server: {:?}", e);
+                        }
+                    }
+                }
+                if let Some(ws_address) = config.jsonrpc.ws_address {
+                    match start_ws_server(
+                        ws_address,
+                        ctx.address(),
+                        &config.jsonrpc.allowed_hosts,
+                        &config.jsonrpc.allowed_origins,
+                    ) {
+                        Ok(server) => {
+                            log::debug!("JSON-RPC over WebSocket is now running at {}", ws_address);
+                            act.ws_server = Some(server);
+                        }
+                        Err(e) => {
+                            log::error!("Could not start JSON-RPC WebSocket server: {:?}", e);
+                            panic!("Could not start JSON-RPC WebSocket server: {:?}", e);
+                        }
+                    }
+                }
+
                 let fut = async move {
                     // Bind TCP listener to this address
                     // FIXME(#176): running `yes | nc 127.0.0.1 1234` freezes the entire actor system
@@ -199,6 +248,27 @@ impl Handler<Unregister> for JsonRpcServer {
     }
 }
 
+/// Handle a JSON-RPC request received through HTTP or WebSocket
+pub struct HandleRequest {
+    pub request: jsonrpc_core::Request,
+    pub session: Arc<Session>,
+}
+
+impl Message for HandleRequest {
+    type Result = Result<Option<jsonrpc_core::Response>, ()>;
+}
+
+impl Handler<HandleRequest> for JsonRpcServer {
+    type Result = ResponseFuture<Result<Option<jsonrpc_core::Response>, ()>>;
+
+    fn handle(&mut self, msg: HandleRequest, _ctx: &mut Self::Context) -> Self::Result {
+        let jsonrpc_io = self.jsonrpc_io.as_ref().unwrap();
+        let fut01 = jsonrpc_io.handle_rpc_request(msg.request, msg.session);
+
+        Box::pin(Compat01As03::new(fut01))
+    }
+}
+
 impl Handler<BlockNotify> for JsonRpcServer {
     type Result = ();
 
@@ -0,0 +1,308 @@
+//! JSON-RPC over HTTP and WebSocket.
+//!
+//! These transports are served by servers that run in their own threads, outside of the actor
+//! system. The JSON-RPC methods rely on the actors of the node, so every request is forwarded to
+//! the `JsonRpcServer` actor, which handles it just like the requests received through TCP.
+
+use std::{io, net::SocketAddr, sync::Arc};
+
+use actix::Addr;
+use futures_util::{compat::Compat, FutureExt};
+use jsonrpc_core::{
+    futures::{future::Either, sync::mpsc, Future},
+    middleware::{Middleware, NoopCallFuture},
+    Call, Error, ErrorCode, FutureResponse, MetaIoHandler, MethodCall, Notification, Output,
+    Request, Response,
+};
+use jsonrpc_http_server::hyper;
+use jsonrpc_pubsub::Session;
+
+use super::server::{HandleRequest, JsonRpcServer};
+
+/// Name of the method used for creating subscriptions
+const SUBSCRIBE_METHOD: &str = "witnet_subscribe";
+
+/// Transport through which the requests are received
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Transport {
+    Http,
+    WebSocket,
+}
+
+/// Middleware that forwards all the requests to the `JsonRpcServer` actor
+struct ForwardToServer {
+    server: Addr<JsonRpcServer>,
+    transport: Transport,
+}
+
+impl Middleware<Arc<Session>> for ForwardToServer {
+    type Future = FutureResponse;
+    type CallFuture = NoopCallFuture;
+
+    fn on_request<F, X>(
+        &self,
+        request: Request,
+        session: Arc<Session>,
+        _next: F,
+    ) -> Either<Self::Future, X>
+    where
+        F: Fn(Request, Arc<Session>) -> X + Send + Sync,
+        X: Future<Item = Option<Response>, Error = ()> + Send + 'static,
+    {
+        let batch = matches!(request, Request::Batch(_));
+        let (request, rejected) = match self.transport {
+            // There is no way to send the notifications of a subscription over HTTP
+            Transport::Http => split_subscriptions(request),
+            Transport::WebSocket => (Some(request), vec![]),
+        };
+        let server = self.server.clone();
+
+        let fut = async move {
+            let response = match request {
+                Some(request) => server
+                    .send(HandleRequest { request, session })
+                    .await
+                    .map_err(|e| log::error!("Failed to forward JSON-RPC request: {}", e))??,
+                None => None,
+            };
+
+            Ok::<_, ()>(merge_responses(batch, response, rejected))
+        };
+
+        Either::A(Box::new(Compat::new(fut.boxed())))
+    }
+}
+
+/// Whether the call creates a subscription
+fn is_subscription(call: &Call) -> bool {
+    match call {
+        Call::MethodCall(MethodCall { method, .. })
+        | Call::Notification(Notification { method, .. }) => method == SUBSCRIBE_METHOD,
+        Call::Invalid { .. } => false,
+    }
+}
+
+/// Error returned for a subscription made through a transport that does not support them, or
+/// `None` if the call is a notification, which has no response.
+fn subscription_error(call: Call) -> Option<Output> {
+    match call {
+        Call::MethodCall(MethodCall { id, jsonrpc, .. }) => {
+            let error = Error {
+                code: ErrorCode::MethodNotFound,
+                message: "Subscriptions are only available over TCP and WebSocket".to_string(),
+                data: None,
+            };
+
+            Some(Output::from(Err(error), id, jsonrpc))
+        }
+        _ => None,
+    }
+}
+
+/// Take the subscriptions out of a request, returning the rest of the request, if any, and the
+/// errors for the subscriptions.
+fn split_subscriptions(request: Request) -> (Option<Request>, Vec<Output>) {
+    match request {
+        Request::Single(call) if is_subscription(&call) => {
+            (None, subscription_error(call).into_iter().collect())
+        }
+        Request::Single(call) => (Some(Request::Single(call)), vec![]),
+        Request::Batch(calls) => {
+            let (subscriptions, calls): (Vec<_>, Vec<_>) =
+                calls.into_iter().partition(is_subscription);
+            let request = if calls.is_empty() && !subscriptions.is_empty() {
+                None
+            } else {
+                Some(Request::Batch(calls))
+            };
+            let rejected = subscriptions
+                .into_iter()
+                .filter_map(subscription_error)
+                .collect();
+
+            (request, rejected)
+        }
+    }
+}
+
+/// Add the errors of the subscriptions taken out by `split_subscriptions` to the response of the
+/// rest of the request.
+fn merge_responses(
+    batch: bool,
+    response: Option<Response>,
+    mut rejected: Vec<Output>,
+) -> Option<Response> {
+    if rejected.is_empty() {
+        return response;
+    }
+    if !batch {
+        return rejected.pop().map(Response::Single);
+    }
+
+    let mut outputs = match response {
+        Some(Response::Batch(outputs)) => outputs,
+        Some(Response::Single(output)) => vec![output],
+        None => vec![],
+    };
+    outputs.extend(rejected);
+
+    Some(Response::Batch(outputs))
+}
+
+/// Build the list of domains accepted by the servers, where `*` accepts any domain.
+fn domains_validation<T>(domains: &[String]) -> jsonrpc_http_server::DomainsValidation<T>
+where
+    T: for<'a> From<&'a str>,
+{
+    if domains.iter().any(|domain| domain == "*") {
+        jsonrpc_http_server::DomainsValidation::Disabled
+    } else {
+        jsonrpc_http_server::DomainsValidation::AllowOnly(
+            domains
+                .iter()
+                .map(|domain| T::from(domain.as_str()))
+                .collect(),
+        )
+    }
+}
+
+/// Start serving JSON-RPC over HTTP POST requests at the given address.
+///
+/// Only the requests whose `Host` is the address of the server or one of `allowed_hosts` are
+/// accepted, and only the web pages served from `allowed_origins` are allowed by CORS.
+/// Subscriptions are not supported.
+pub fn start_http_server(
+    addr: SocketAddr,
+    server: Addr<JsonRpcServer>,
+    allowed_hosts: &[String],
+    allowed_origins: &[String],
+) -> io::Result<jsonrpc_http_server::Server> {
+    let handler = MetaIoHandler::with_middleware(ForwardToServer {
+        server,
+        transport: Transport::Http,
+    });
+
+    jsonrpc_http_server::ServerBuilder::with_meta_extractor(
+        handler,
+        |_request: &hyper::Request<hyper::Body>| {
+            // Subscriptions are rejected, so nothing is ever sent through this channel
+            let (sender, _receiver) = mpsc::channel(0);
+
+            Arc::new(Session::new(sender))
+        },
+    )
+    .allowed_hosts(domains_validation(allowed_hosts))
+    .cors(domains_validation(allowed_origins))
+    .start_http(&addr)
+}
+
+/// Start serving JSON-RPC over WebSocket at the given address.
+///
+/// Only the connections whose `Host` is the address of the server or one of `allowed_hosts`, and
+/// whose `Origin`, if any, is one of `allowed_origins` are accepted. Each WebSocket connection has
+/// its own session, so subscriptions are supported and they end when the connection is closed.
+pub fn start_ws_server(
+    addr: SocketAddr,
+    server: Addr<JsonRpcServer>,
+    allowed_hosts: &[String],
+    allowed_origins: &[String],
+) -> Result<jsonrpc_ws_server::Server, jsonrpc_ws_server::Error> {
+    let handler = MetaIoHandler::with_middleware(ForwardToServer {
+        server,
+        transport: Transport::WebSocket,
+    });
+
+    jsonrpc_ws_server::ServerBuilder::with_meta_extractor(
+        handler,
+        |context: &jsonrpc_ws_server::RequestContext| Arc::new(Session::new(context.sender())),
+    )
+    .allowed_hosts(domains_validation(allowed_hosts))
+    .allowed_origins(domains_validation(allowed_origins))
+    .start(&addr)
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonrpc_core::{Id, Params, Success, Value, Version};
+
+    use super::*;
+
+    fn method_call(method: &str, id: u64) -> Call {
+        Call::MethodCall(MethodCall {
+            jsonrpc: Some(Version::V2),
+            method: method.to_string(),
+            params: Params::None,
+            id: Id::Num(id),
+        })
+    }
+
+    fn success(id: u64) -> Output {
+        Output::Success(Success {
+            jsonrpc: Some(Version::V2),
+            result: Value::Bool(true),
+            id: Id::Num(id),
+        })
+    }
+
+    #[test]
+    fn split_single_subscription() {
+        let (request, rejected) =
+            split_subscriptions(Request::Single(method_call(SUBSCRIBE_METHOD, 1)));
+
+        assert_eq!(request, None);
+        assert_eq!(rejected.len(), 1);
+        assert!(matches!(
+            &rejected[0],
+            Output::Failure(failure) if failure.id == Id::Num(1)
+        ));
+        assert_eq!(
+            merge_responses(false, None, rejected.clone()),
+            Some(Response::Single(rejected[0].clone()))
+        );
+    }
+
+    #[test]
+    fn split_single_method_call() {
+        let call = method_call("getBlockChain", 1);
+        let (request, rejected) = split_subscriptions(Request::Single(call.clone()));
+
+        assert_eq!(request, Some(Request::Single(call)));
+        assert!(rejected.is_empty());
+    }
+
+    #[test]
+    fn split_batch_with_subscriptions() {
+        let call = method_call("getBlockChain", 1);
+        let (request, rejected) = split_subscriptions(Request::Batch(vec![
+            call.clone(),
+            method_call(SUBSCRIBE_METHOD, 2),
+        ]));
+
+        assert_eq!(request, Some(Request::Batch(vec![call])));
+        assert_eq!(rejected.len(), 1);
+
+        let response = merge_responses(
+            true,
+            Some(Response::Batch(vec![success(1)])),
+            rejected.clone(),
+        );
+        assert_eq!(
+            response,
+            Some(Response::Batch(vec![success(1), rejected[0].clone()]))
+        );
+    }
+
+    #[test]
+    fn split_batch_of_subscriptions() {
+        let (request, rejected) = split_subscriptions(Request::Batch(vec![
+            method_call(SUBSCRIBE_METHOD, 1),
+            method_call(SUBSCRIBE_METHOD, 2),
+        ]));
+
+        assert_eq!(request, None);
+        assert_eq!(
+            merge_responses(true, None, rejected.clone()),
+            Some(Response::Batch(rejected))
+        );
+    }
+}
@@ -32,7 +32,9 @@ use witnet_p2p::{
     error::SessionsError,
     sessions::{GetConsolidatedPeersResult, SessionStatus, SessionType},
 };
-use witnet_rad::{error::RadError, types::RadonTypes};
+use witnet_rad::{
+    error::RadError, proxy::RetrievalProxy, transport::RetrievalLimits, types::RadonTypes,
+};
 
 use super::{
     chain_manager::{ChainManagerError, MAX_BLOCKS_SYNC},
@@ -41,7 +43,7 @@ use super::{
         AllEpochSubscription, EpochManagerError, SendableNotification, SingleEpochSubscription,
     },
     inventory_manager::InventoryManagerError,
-    rad_manager::RadManager,
+    rad_manager::{retrieval_cache::RetrievalCacheLimits, RadManager},
     session::Session,
 };
 
@@ -723,6 +725,18 @@ pub struct ResolveRA {
     pub rad_request: RADRequest,
     /// Timeout: if the execution does not finish before the timeout, it is cancelled.
     pub timeout: Option<Duration>,
+    /// Limits applied to the HTTP request of each of the sources
+    pub limits: RetrievalLimits,
+    /// Limits of the cache of responses shared by the data requests of the same epoch, and of the
+    /// requests sent to each host
+    pub cache_limits: RetrievalCacheLimits,
+    /// Proxy through which the HTTP requests of the sources are sent, if any
+    pub proxy: Option<RetrievalProxy>,
+    /// Epoch in which the data request is being resolved
+    pub epoch: Epoch,
+    /// Timestamp of the start of that epoch, against which the scripts compute the age of the
+    /// retrieved data. If `None`, the current time is used instead.
+    pub timestamp: Option<i64>,
     /// Active Witnet protocol improvements as of the current epoch.
     /// Used to select the correct version of the validation logic.
     pub active_wips: ActiveWips,
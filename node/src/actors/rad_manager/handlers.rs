@@ -1,8 +1,14 @@
 //! Message handlers for `RadManager`
 
 use actix::{Handler, ResponseFuture};
-use witnet_data_structures::radon_report::{RadonReport, ReportContext};
-use witnet_rad::{error::RadError, script::RadonScriptExecutionSettings, types::RadonTypes};
+use witnet_data_structures::{
+    chain::RADType,
+    radon_report::{RadonReport, ReportContext},
+};
+use witnet_rad::{
+    error::RadError, script::RadonScriptExecutionSettings, transport::HttpTransport,
+    types::RadonTypes,
+};
 use witnet_validations::validations::{
     construct_report_from_clause_result, evaluate_tally_postcondition_clause,
     evaluate_tally_precondition_clause, TallyPreconditionClauseResult,
@@ -10,7 +16,7 @@ use witnet_validations::validations::{
 
 use crate::actors::messages::{ResolveRA, RunTally};
 
-use super::RadManager;
+use super::{retrieval_cache::CachedResponses, RadManager};
 use futures::FutureExt;
 
 impl Handler<ResolveRA> for RadManager {
@@ -19,15 +25,38 @@ impl Handler<ResolveRA> for RadManager {
 
     fn handle(&mut self, msg: ResolveRA, _ctx: &mut Self::Context) -> Self::Result {
         let timeout = msg.timeout;
+        let http_transport = HttpTransport::with_limits(msg.limits).with_proxy(msg.proxy.clone());
+        // Sources already retrieved by other data requests in this epoch are served from the cache
+        let mut transport = CachedResponses::default();
+        for retrieve in &msg.rad_request.retrieve {
+            // RNG sources must produce fresh random bytes for every data request
+            if retrieve.kind == RADType::Rng {
+                continue;
+            }
+            let response = self.retrieval_cache.response(
+                retrieve,
+                msg.epoch,
+                http_transport.clone(),
+                &msg.cache_limits,
+            );
+            transport.insert(retrieve, response);
+        }
         // The result of the RAD aggregation is computed asynchronously, because the async block
         // returns a future
-        let fut = async {
+        let fut = async move {
             let sources = msg.rad_request.retrieve;
             let aggregator = msg.rad_request.aggregate;
-
-            let retrieve_responses_fut = sources
-                .iter()
-                .map(|retrieve| witnet_rad::run_retrieval(retrieve));
+            let timestamp = msg.timestamp;
+            let active_wips = &msg.active_wips;
+
+            let retrieve_responses_fut = sources.iter().map(|retrieve| {
+                witnet_rad::run_retrieval_with_transport(
+                    retrieve,
+                    &transport,
+                    timestamp,
+                    active_wips,
+                )
+            });
 
             // Perform retrievals in parallel for the sake of synchronization between sources
             //  (increasing the likeliness of multiple sources returning results that are closer to each
@@ -56,6 +85,7 @@ impl Handler<ResolveRA> for RadManager {
                         values,
                         &aggregator,
                         RadonScriptExecutionSettings::all_but_partial_results(),
+                        &msg.active_wips,
                     )
                 }
                 Ok(TallyPreconditionClauseResult::MajorityOfErrors { errors_mode }) => {
@@ -10,10 +10,14 @@ use crate::utils::stop_system_if_panicking;
 
 mod actor;
 mod handlers;
+pub mod retrieval_cache;
 
 /// RadManager actor
 #[derive(Default)]
-pub struct RadManager;
+pub struct RadManager {
+    /// Responses of the sources retrieved during the current epoch
+    retrieval_cache: retrieval_cache::RetrievalCache,
+}
 
 impl Drop for RadManager {
     fn drop(&mut self) {
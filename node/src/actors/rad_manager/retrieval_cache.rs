@@ -0,0 +1,438 @@
+//! Sharing of the responses of retrieval sources between the data requests resolved in an epoch.
+//!
+//! When several data requests of the same epoch use the same source, its request is performed
+//! only once and the response is shared by all of them. This saves bandwidth and makes the node
+//! report the same data for the same source regardless of the data request. The requests sent
+//! to each host can also be limited, to avoid being rate-limited by data providers.
+
+use std::{
+    cmp,
+    collections::HashMap,
+    sync::{Arc, Mutex, PoisonError},
+    time::Duration,
+};
+
+use futures::future::{BoxFuture, FutureExt, Shared};
+use tokio::{sync::Semaphore, time::Instant};
+use url::Url;
+
+use witnet_data_structures::chain::{Epoch, RADRetrieve};
+use witnet_rad::{
+    error::RadError,
+    transport::{cassette_key, RetrievalResponse, RetrievalTransport},
+};
+
+/// The response of a source, which can be awaited by any number of data requests.
+pub type SharedResponse = Shared<BoxFuture<'static, Result<RetrievalResponse, RadError>>>;
+
+/// Limits of the retrieval cache and of the requests sent to each host.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RetrievalCacheLimits {
+    /// Maximum number of responses kept in the cache during an epoch. Set to 0 to disable the
+    /// cache.
+    pub max_entries: usize,
+    /// Maximum number of requests to the same host that can be in flight at the same time, or
+    /// `None` for no limit.
+    pub max_concurrent_per_host: Option<usize>,
+    /// Minimum time between the start of two requests to the same host, or `None` for no limit.
+    pub min_interval_per_host: Option<Duration>,
+}
+
+impl Default for RetrievalCacheLimits {
+    fn default() -> Self {
+        Self {
+            max_entries: 256,
+            max_concurrent_per_host: Some(4),
+            min_interval_per_host: None,
+        }
+    }
+}
+
+/// Everything in a source that may change its response, as described by its cassette key. The
+/// script is not included, because it is applied to the response afterwards.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct SourceKey(String);
+
+impl From<&RADRetrieve> for SourceKey {
+    fn from(retrieve: &RADRetrieve) -> Self {
+        Self(cassette_key(retrieve))
+    }
+}
+
+/// Limits the requests sent to a single host.
+#[derive(Debug)]
+struct HostThrottle {
+    semaphore: Option<Semaphore>,
+    next_request: Mutex<Instant>,
+}
+
+impl HostThrottle {
+    fn new(limits: &RetrievalCacheLimits) -> Self {
+        Self {
+            semaphore: limits.max_concurrent_per_host.map(Semaphore::new),
+            next_request: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Reserve the earliest time at which a new request can be started.
+    fn reserve_slot(&self, interval: Duration) -> Instant {
+        let mut next_request = self
+            .next_request
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let slot = cmp::max(*next_request, Instant::now());
+        *next_request = slot + interval;
+
+        slot
+    }
+}
+
+/// Host of a URL, or an empty string if the URL cannot be parsed.
+fn url_host(url: &str) -> String {
+    Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// Responses of the sources retrieved during the current epoch, along with the state of the
+/// limits applied to each host.
+#[derive(Default)]
+pub struct RetrievalCache {
+    epoch: Epoch,
+    responses: HashMap<SourceKey, SharedResponse>,
+    hosts: HashMap<String, Arc<HostThrottle>>,
+}
+
+impl RetrievalCache {
+    /// Get the response of a source for a data request resolved in the given epoch, starting its
+    /// request using `transport` unless it is already cached.
+    ///
+    /// Moving to a new epoch empties the cache. Data requests of past epochs are never served from
+    /// the cache. Failed responses are evicted, so that the next data request retries the source.
+    pub fn response<T>(
+        &mut self,
+        retrieve: &RADRetrieve,
+        epoch: Epoch,
+        transport: T,
+        limits: &RetrievalCacheLimits,
+    ) -> SharedResponse
+    where
+        T: RetrievalTransport + 'static,
+    {
+        if epoch > self.epoch {
+            self.epoch = epoch;
+            self.responses.clear();
+            // Forget the hosts that have no requests in flight
+            self.hosts
+                .retain(|_host, throttle| Arc::strong_count(throttle) > 1);
+        }
+
+        let key = SourceKey::from(retrieve);
+        if epoch == self.epoch {
+            match self.responses.get(&key) {
+                Some(response) if matches!(response.peek(), Some(Err(_))) => {
+                    self.responses.remove(&key);
+                }
+                Some(response) => {
+                    log::debug!("Using cached response for source {}", retrieve.url);
+
+                    return response.clone();
+                }
+                None => {}
+            }
+        }
+
+        let response = self.request(retrieve, transport, limits);
+        if epoch == self.epoch && self.responses.len() < limits.max_entries {
+            self.responses.insert(key, response.clone());
+        }
+
+        response
+    }
+
+    /// Number of responses in the cache.
+    pub fn len(&self) -> usize {
+        self.responses.len()
+    }
+
+    /// Whether the cache has no responses.
+    pub fn is_empty(&self) -> bool {
+        self.responses.is_empty()
+    }
+
+    fn request<T>(
+        &mut self,
+        retrieve: &RADRetrieve,
+        transport: T,
+        limits: &RetrievalCacheLimits,
+    ) -> SharedResponse
+    where
+        T: RetrievalTransport + 'static,
+    {
+        let throttle = Arc::clone(
+            self.hosts
+                .entry(url_host(&retrieve.url))
+                .or_insert_with(|| Arc::new(HostThrottle::new(limits))),
+        );
+        let min_interval = limits.min_interval_per_host;
+        let retrieve = retrieve.clone();
+
+        async move {
+            let _permit = match &throttle.semaphore {
+                Some(semaphore) => Some(
+                    semaphore
+                        .acquire()
+                        .await
+                        .expect("host semaphores are never closed"),
+                ),
+                None => None,
+            };
+            if let Some(interval) = min_interval {
+                tokio::time::sleep_until(throttle.reserve_slot(interval)).await;
+            }
+
+            transport.retrieve(&retrieve).await
+        }
+        .boxed()
+        .shared()
+    }
+}
+
+/// Transport that serves the responses previously obtained from a `RetrievalCache`.
+#[derive(Default)]
+pub struct CachedResponses {
+    responses: HashMap<SourceKey, SharedResponse>,
+}
+
+impl CachedResponses {
+    /// Add the response of a source.
+    pub fn insert(&mut self, retrieve: &RADRetrieve, response: SharedResponse) {
+        self.responses.insert(SourceKey::from(retrieve), response);
+    }
+}
+
+impl RetrievalTransport for CachedResponses {
+    fn retrieve<'a>(
+        &'a self,
+        retrieve: &'a RADRetrieve,
+    ) -> BoxFuture<'a, Result<RetrievalResponse, RadError>> {
+        let key = SourceKey::from(retrieve);
+        match self.responses.get(&key) {
+            Some(response) => response.clone().boxed(),
+            None => Box::pin(futures::future::ready(Err(RadError::NotInRetrievalCache {
+                key: key.0,
+            }))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use witnet_data_structures::chain::RADType;
+
+    use super::*;
+    use crate::utils::test_actix_system;
+
+    /// Transport that answers every request with its URL, counting the requests performed.
+    #[derive(Clone, Default)]
+    struct CountingTransport(Arc<AtomicUsize>);
+
+    impl CountingTransport {
+        fn count(&self) -> usize {
+            self.0.load(Ordering::SeqCst)
+        }
+    }
+
+    impl RetrievalTransport for CountingTransport {
+        fn retrieve<'a>(
+            &'a self,
+            retrieve: &'a RADRetrieve,
+        ) -> BoxFuture<'a, Result<RetrievalResponse, RadError>> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            let response = Ok(RetrievalResponse::from(retrieve.url.clone()));
+
+            Box::pin(async move { response })
+        }
+    }
+
+    /// Transport that fails the first request and answers the next ones with their URL.
+    #[derive(Clone, Default)]
+    struct FlakyTransport(CountingTransport);
+
+    impl RetrievalTransport for FlakyTransport {
+        fn retrieve<'a>(
+            &'a self,
+            retrieve: &'a RADRetrieve,
+        ) -> BoxFuture<'a, Result<RetrievalResponse, RadError>> {
+            let response = self.0.retrieve(retrieve);
+            if self.0.count() == 1 {
+                Box::pin(futures::future::ready(Err(RadError::RetrieveTimeout)))
+            } else {
+                response
+            }
+        }
+    }
+
+    fn retrieve_get(url: &str) -> RADRetrieve {
+        RADRetrieve {
+            kind: RADType::HttpGet,
+            url: url.to_string(),
+            ..RADRetrieve::default()
+        }
+    }
+
+    #[test]
+    fn test_url_host() {
+        assert_eq!(url_host("https://Example.com/price?a=1"), "example.com");
+        assert_eq!(url_host("http://user:pw@example.com:8080"), "example.com");
+        assert_eq!(url_host("example.com#fragment"), "");
+    }
+
+    #[test]
+    fn test_retrieval_cache_shares_responses() {
+        test_actix_system(|| async {
+            let transport = CountingTransport::default();
+            let limits = RetrievalCacheLimits::default();
+            let mut cache = RetrievalCache::default();
+
+            let source = retrieve_get("https://example.com/a");
+            let mut other_script = source.clone();
+            other_script.script = vec![0x80];
+            let mut other_headers = source.clone();
+            other_headers.headers = vec![("Accept".to_string(), "text/plain".to_string())];
+
+            let first = cache.response(&source, 1, transport.clone(), &limits);
+            let second = cache.response(&other_script, 1, transport.clone(), &limits);
+            assert_eq!(
+                first.await,
+                Ok(RetrievalResponse::from("https://example.com/a"))
+            );
+            assert_eq!(
+                second.await,
+                Ok(RetrievalResponse::from("https://example.com/a"))
+            );
+            assert_eq!(transport.count(), 1);
+
+            // Different headers may lead to a different response
+            cache
+                .response(&other_headers, 1, transport.clone(), &limits)
+                .await
+                .unwrap();
+            assert_eq!(transport.count(), 2);
+            assert_eq!(cache.len(), 2);
+
+            // A new epoch empties the cache
+            cache
+                .response(&source, 2, transport.clone(), &limits)
+                .await
+                .unwrap();
+            assert_eq!(transport.count(), 3);
+            assert_eq!(cache.len(), 1);
+
+            // Data requests from past epochs are not served from the cache
+            cache
+                .response(&source, 1, transport.clone(), &limits)
+                .await
+                .unwrap();
+            assert_eq!(transport.count(), 4);
+            assert_eq!(cache.len(), 1);
+        });
+    }
+
+    #[test]
+    fn test_retrieval_cache_evicts_errors() {
+        test_actix_system(|| async {
+            let transport = FlakyTransport::default();
+            let limits = RetrievalCacheLimits::default();
+            let mut cache = RetrievalCache::default();
+            let source = retrieve_get("https://example.com/a");
+
+            let first = cache.response(&source, 1, transport.clone(), &limits);
+            assert_eq!(first.await, Err(RadError::RetrieveTimeout));
+
+            // The failed response is not reused
+            let second = cache.response(&source, 1, transport.clone(), &limits);
+            assert_eq!(
+                second.await,
+                Ok(RetrievalResponse::from("https://example.com/a"))
+            );
+            assert_eq!(transport.0.count(), 2);
+            assert_eq!(cache.len(), 1);
+        });
+    }
+
+    #[test]
+    fn test_cached_responses_miss() {
+        test_actix_system(|| async {
+            let source = retrieve_get("https://example.com/a");
+            let responses = CachedResponses::default();
+
+            assert_eq!(
+                responses.retrieve(&source).await,
+                Err(RadError::NotInRetrievalCache {
+                    key: "GET https://example.com/a".to_string()
+                })
+            );
+        });
+    }
+
+    #[test]
+    fn test_retrieval_cache_max_entries() {
+        test_actix_system(|| async {
+            let transport = CountingTransport::default();
+            let limits = RetrievalCacheLimits {
+                max_entries: 1,
+                ..RetrievalCacheLimits::default()
+            };
+            let mut cache = RetrievalCache::default();
+
+            for _ in 0..2 {
+                for url in &["https://example.com/a", "https://example.com/b"] {
+                    cache
+                        .response(&retrieve_get(url), 1, transport.clone(), &limits)
+                        .await
+                        .unwrap();
+                }
+            }
+
+            // Only the first source fits in the cache
+            assert_eq!(cache.len(), 1);
+            assert_eq!(transport.count(), 3);
+        });
+    }
+
+    #[test]
+    fn test_retrieval_cache_min_interval_per_host() {
+        test_actix_system(|| async {
+            let transport = CountingTransport::default();
+            let interval = Duration::from_millis(50);
+            let limits = RetrievalCacheLimits {
+                min_interval_per_host: Some(interval),
+                ..RetrievalCacheLimits::default()
+            };
+            let mut cache = RetrievalCache::default();
+
+            let start = Instant::now();
+            let a = cache.response(
+                &retrieve_get("https://example.com/a"),
+                1,
+                transport.clone(),
+                &limits,
+            );
+            let b = cache.response(
+                &retrieve_get("https://example.com/b"),
+                1,
+                transport.clone(),
+                &limits,
+            );
+            let (a, b) = futures::future::join(a, b).await;
+            assert!(a.is_ok() && b.is_ok());
+
+            assert!(start.elapsed() >= interval);
+            assert_eq!(transport.count(), 2);
+        });
+    }
+}
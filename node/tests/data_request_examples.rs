@@ -6,7 +6,10 @@ use std::{
 
 use serde::{Deserialize, Serialize};
 
-use witnet_data_structures::chain::DataRequestOutput;
+use witnet_data_structures::{
+    chain::{DataRequestOutput, Environment},
+    mainnet_validations::{ActiveWips, TapiEngine},
+};
 use witnet_node::actors::messages::BuildDrt;
 use witnet_rad::{
     script::RadonScriptExecutionSettings,
@@ -40,6 +43,17 @@ fn generate_example_json(build_drt: BuildDrt) -> String {
     .unwrap()
 }
 
+// This should only be used in tests
+fn all_wips_active() -> ActiveWips {
+    let mut tapi_engine = TapiEngine::default();
+    tapi_engine.initialize_wip_information(Environment::Testnet);
+
+    ActiveWips {
+        active_wips: tapi_engine.wip_activation,
+        block_epoch: u32::MAX,
+    }
+}
+
 fn run_dr_locally_with_data(
     dr: &DataRequestOutput,
     data: &[&str],
@@ -56,8 +70,11 @@ fn run_dr_locally_with_data(
     }
 
     log::info!("Running aggregation with values {:?}", retrieval_results);
-    let aggregation_result =
-        witnet_rad::run_aggregation(retrieval_results, &dr.data_request.aggregate)?;
+    let aggregation_result = witnet_rad::run_aggregation(
+        retrieval_results,
+        &dr.data_request.aggregate,
+        &all_wips_active(),
+    )?;
     log::info!("Aggregation result: {:?}", aggregation_result);
 
     // Assume that all the required witnesses will report the same value
@@ -67,7 +84,8 @@ fn run_dr_locally_with_data(
             .map(RadonTypes::try_from)
             .collect();
     log::info!("Running tally with values {:?}", reported_values);
-    let tally_result = witnet_rad::run_tally(reported_values?, &dr.data_request.tally)?;
+    let tally_result =
+        witnet_rad::run_tally(reported_values?, &dr.data_request.tally, &all_wips_active())?;
     log::info!("Tally result: {:?}", tally_result);
 
     Ok(tally_result)
@@ -104,6 +122,18 @@ fn parse_examples() {
                     }
                 };
 
+                // The examples must pass the static type checks
+                let issues = witnet_rad::type_check::check_rad_request(
+                    &file_value.params.dro.data_request,
+                    &all_wips_active(),
+                );
+                assert_eq!(
+                    issues,
+                    vec![],
+                    "Type errors in data request example {}",
+                    path.display()
+                );
+
                 // Run data request locally
                 let local_result = run_dr_locally_with_data(&file_value.params.dro, example_data);
                 assert_eq!(
@@ -213,11 +243,19 @@ mod examples {
                             kind: RADType::HttpGet,
                             url: url_0.to_string(),
                             script: r0_script,
+                            body: vec![],
+                            content_type: String::new(),
+                            headers: vec![],
+                            binary: false,
                         },
                         RADRetrieve {
                             kind: RADType::HttpGet,
                             url: url_1.to_string(),
                             script: r1_script,
+                            body: vec![],
+                            content_type: String::new(),
+                            headers: vec![],
+                            binary: false,
                         },
                     ],
                     aggregate: RADAggregate {
@@ -271,6 +309,10 @@ mod examples {
                         kind: RADType::HttpGet,
                         url: url_0.to_string(),
                         script: r0_script,
+                        body: vec![],
+                        content_type: String::new(),
+                        headers: vec![],
+                        binary: false,
                     }],
                     aggregate: RADAggregate {
                         filters: vec![],
@@ -318,6 +360,10 @@ mod examples {
                         kind: RADType::HttpGet,
                         url: url_0.to_string(),
                         script: r0_script,
+                        body: vec![],
+                        content_type: String::new(),
+                        headers: vec![],
+                        binary: false,
                     }],
                     aggregate: RADAggregate {
                         filters: vec![],
@@ -375,16 +421,28 @@ mod examples {
                             kind: RADType::HttpGet,
                             url: url_0.to_string(),
                             script: r0_script,
+                            body: vec![],
+                            content_type: String::new(),
+                            headers: vec![],
+                            binary: false,
                         },
                         RADRetrieve {
                             kind: RADType::HttpGet,
                             url: url_1.to_string(),
                             script: r1_script,
+                            body: vec![],
+                            content_type: String::new(),
+                            headers: vec![],
+                            binary: false,
                         },
                         RADRetrieve {
                             kind: RADType::HttpGet,
                             url: url_2.to_string(),
                             script: r2_script,
+                            body: vec![],
+                            content_type: String::new(),
+                            headers: vec![],
+                            binary: false,
                         },
                     ],
                     aggregate: RADAggregate {
@@ -6,7 +6,10 @@ use failure::{self, Fail};
 use serde::{Serialize, Serializer};
 use serde_cbor::value::Value as SerdeCborValue;
 
-use witnet_data_structures::radon_error::{ErrorLike, RadonError, RadonErrors};
+use witnet_data_structures::{
+    chain::RADType,
+    radon_error::{ErrorLike, RadonError, RadonErrors},
+};
 
 use crate::types::RadonTypes;
 use crate::{operators::RadonOpCodes, types::array::RadonArray};
@@ -29,6 +32,12 @@ pub enum RadError {
         from: &'static str,
         to: &'static str,
     },
+    /// Failed to decode a RADON value from its JSON encoding
+    #[fail(
+        display = "Failed to decode a RADON value from JSON {}: {}",
+        value, description
+    )]
+    DecodeJson { value: String, description: String },
     /// Failed to calculate the hash of a RADON value or structure
     #[fail(display = "Failed to calculate the hash of a RADON value or structure")]
     Hash,
@@ -38,18 +47,60 @@ pub enum RadError {
         description
     )]
     JsonParse { description: String },
+    /// Failed to parse an object from a XML buffer
+    #[fail(
+        display = "Failed to parse an object from a XML buffer: {:?}",
+        description
+    )]
+    XmlParse { description: String },
+    /// Failed to compile a regular expression
+    #[fail(
+        display = "Failed to compile regular expression `{}`: {}",
+        pattern, description
+    )]
+    RegexCompile {
+        pattern: String,
+        description: String,
+    },
+    /// The regular expression did not match the input string
+    #[fail(display = "Regular expression `{}` did not match", pattern)]
+    RegexNoMatch { pattern: String },
+    /// Replacing the matches of a regular expression produced a string that is too long
+    #[fail(
+        display = "Replacing the matches of regular expression `{}` produced a string longer than {} bytes",
+        pattern, max_size
+    )]
+    RegexReplaceTooLong { pattern: String, max_size: usize },
+    /// Failed to convert between RadonString and RadonBytes using the given encoding
+    #[fail(
+        display = "Failed to convert between RadonString and RadonBytes using `{}` encoding: {}",
+        encoding, description
+    )]
+    InvalidEncoding {
+        encoding: String,
+        description: String,
+    },
     /// The given index is not present in a RadonArray
     #[fail(display = "Failed to get item at index `{}` from RadonArray", index)]
     ArrayIndexOutOfBounds { index: i32 },
     /// The given key is not present in a RadonMap
     #[fail(display = "Failed to get key `{}` from RadonMap", key)]
     MapKeyNotFound { key: String },
+    /// The given path expression is not valid
+    #[fail(display = "Invalid path expression `{}`: {}", path, description)]
+    InvalidPath { path: String, description: String },
     /// The given subscript does not return RadonBoolean in an ArrayFilter
     #[fail(
         display = "ArrayFilter subscript output was not RadonBoolean (was `{}`)",
         value
     )]
     ArrayFilterWrongSubscript { value: String },
+    /// The given subscript does not return RadonBoolean in an ArraySome
+    #[fail(
+        display = "ArraySome subscript output was not RadonBoolean (was `{}`)",
+        value
+    )]
+    ArraySomeWrongSubscript { value: String },
     /// Failed to parse a Value from a buffer
     #[fail(
         display = "Failed to parse a Value from a buffer. Error message: {}",
@@ -71,6 +122,22 @@ pub enum RadError {
         input_type
     )]
     ScriptNotArray { input_type: String },
+    /// The source code of a script written in the RADON script language is not valid
+    #[fail(
+        display = "Syntax error at line {}, column {}: {}",
+        line, column, message
+    )]
+    ScriptSyntax {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// The script contains values that cannot be written in the RADON script language
+    #[fail(
+        display = "The script cannot be written in the RADON script language: {}",
+        description
+    )]
+    ScriptNotRepresentable { description: String },
     /// The given operator code is unknown
     #[fail(display = "Operator code `{}` is unknown", code)]
     UnknownOperator { code: i128 },
@@ -114,6 +181,15 @@ pub enum RadError {
         operator
     )]
     UnsupportedOpNonHomogeneous { operator: String },
+    /// The filter or reducer cannot be applied to arrays containing items of this type
+    #[fail(
+        display = "`{}` cannot be applied to RadonArray items of type `{}`",
+        operator, item_type
+    )]
+    UnsupportedItemType {
+        operator: String,
+        item_type: &'static str,
+    },
     /// This operator cannot be used in tally stage
     #[fail(display = "Operator {} cannot be used in tally stage", operator)]
     UnsupportedOperatorInTally { operator: RadonOpCodes },
@@ -138,6 +214,12 @@ pub enum RadError {
     /// Tried to apply mod reducer on an empty array
     #[fail(display = "Tried to apply mode reducer on an empty array")]
     ModeEmpty,
+    /// Tried to apply a reducer that needs at least one value on an empty array
+    #[fail(display = "Tried to apply {} reducer on an empty array", reducer)]
+    EmptyReduce { reducer: String },
+    /// Weighted reducers only accept non-negative weights
+    #[fail(display = "Invalid weight in weighted reducer: {}", weight)]
+    InvalidWeight { weight: f64 },
     /// The given arguments are not valid for the given operator
     #[fail(
         display = "Wrong `{}::{}()` arguments: `{:?}`",
@@ -149,14 +231,62 @@ pub enum RadError {
         args: Vec<SerdeCborValue>,
     },
     /// The HTTP response was an error code
-    #[fail(display = "HTTP GET response was an HTTP error code: {}", status_code)]
+    #[fail(display = "HTTP response was an HTTP error code: {}", status_code)]
     HttpStatus { status_code: u16 },
     /// Failed to execute HTTP request
     #[fail(
-        display = "Failed to execute HTTP GET request with error message: {}",
+        display = "Failed to execute HTTP request with error message: {}",
         message
     )]
     HttpOther { message: String },
+    /// The HTTP response headers were not received in time
+    #[fail(
+        display = "HTTP request timed out after {} ms without receiving a response",
+        timeout_ms
+    )]
+    HttpConnectTimeout { timeout_ms: u128 },
+    /// The body of the HTTP response was not received in time
+    #[fail(
+        display = "Timed out after {} ms while reading the HTTP response body",
+        timeout_ms
+    )]
+    HttpReadTimeout { timeout_ms: u128 },
+    /// The body of the HTTP response is larger than allowed
+    #[fail(
+        display = "HTTP response body exceeds the maximum size of {} bytes",
+        max_size
+    )]
+    HttpResponseTooLarge { max_size: usize },
+    /// The HTTP request kept failing with transient errors after retrying it
+    #[fail(
+        display = "HTTP request failed after {} attempts, last error: {}",
+        attempts, last_error
+    )]
+    HttpRetriesExhausted {
+        attempts: u32,
+        last_error: Box<RadError>,
+    },
+    /// Failed to read or write a cassette file
+    #[fail(display = "Failed to access cassette file `{}`: {}", path, message)]
+    CassetteIo { path: String, message: String },
+    /// There is no recorded response for a request in the cassette being replayed
+    #[fail(display = "No response recorded in the cassette for `{}`", key)]
+    NotInCassette { key: String },
+    /// There is no response for a request in the responses shared by the data requests of an epoch
+    #[fail(display = "No response cached for `{}`", key)]
+    NotInRetrievalCache { key: String },
+    /// The proxy configured for retrievals is not valid
+    #[fail(display = "Invalid proxy `{}`: {}", url, message)]
+    InvalidProxy { url: String, message: String },
+    /// Tried to perform an HTTP request for a retrieval source that is not an HTTP source
+    #[fail(
+        display = "Retrieval sources of kind {:?} do not perform HTTP requests",
+        kind
+    )]
+    NotHttpRetrieval { kind: RADType },
+    /// The value of an HTTP header is not valid
+    #[fail(display = "Invalid value for HTTP header `{}`: {:?}", name, value)]
+    InvalidHttpHeader { name: String, value: String },
     /// Failed to convert string to float
     #[fail(
         display = "Failed to convert string to float with error message: {}",
@@ -175,6 +305,17 @@ pub enum RadError {
         message
     )]
     ParseBool { message: String },
+    /// Failed to convert string to a date and time. Only the format is kept, because it is
+    /// revealed as the argument of the error.
+    #[fail(display = "Failed to parse a date with format {:?}", format)]
+    ParseDateTime { format: String },
+    /// A timestamp is older than allowed with regard to the timestamp of the data request. The
+    /// age itself is not kept, because it can be different for every witness.
+    #[fail(
+        display = "The timestamp is older than the maximum age of {} seconds",
+        max_age
+    )]
+    StaleTimestamp { max_age: i128 },
     /// Overflow error
     #[fail(display = "Overflow error")]
     Overflow,
@@ -296,6 +437,9 @@ pub enum RadError {
     /// The script contains too many calls.
     #[fail(display = "The script contains too many calls")]
     ScriptTooManyCalls,
+    /// The execution of the script exceeded the cost budget of its stage.
+    #[fail(display = "The execution of the script exceeded the cost budget of its stage")]
+    ScriptTooExpensive,
     /// At least one of the source scripts is not a valid CBOR-encoded value.
     #[fail(display = "At least one of the source scripts is not a valid CBOR-encoded value")]
     SourceScriptNotCBOR,
@@ -399,6 +543,7 @@ impl RadError {
         Ok(RadonError::new(match kind {
             RadonErrors::RequestTooManySources => RadError::RequestTooManySources,
             RadonErrors::ScriptTooManyCalls => RadError::ScriptTooManyCalls,
+            RadonErrors::ScriptTooExpensive => RadError::ScriptTooExpensive,
             RadonErrors::Overflow => RadError::Overflow,
             RadonErrors::InsufficientCommits => RadError::InsufficientCommits,
             RadonErrors::NoReveals => RadError::NoReveals,
@@ -417,6 +562,14 @@ impl RadError {
                 let (key,) = deserialize_args(error_args)?;
                 RadError::MapKeyNotFound { key }
             }
+            RadonErrors::ParseDateTime => {
+                let (format,) = deserialize_args(error_args)?;
+                RadError::ParseDateTime { format }
+            }
+            RadonErrors::StaleTimestamp => {
+                let (max_age,) = deserialize_args(error_args)?;
+                RadError::StaleTimestamp { max_age }
+            }
             RadonErrors::UnsupportedOperator => {
                 let (input_type, operator, args) = deserialize_args(error_args)?;
                 RadError::UnsupportedOperator {
@@ -466,6 +619,11 @@ impl RadError {
             })
         }
 
+        // Retrying a request must not change the error that gets reported
+        if let RadError::HttpRetriesExhausted { last_error, .. } = self {
+            return last_error.try_into_cbor_array();
+        }
+
         let kind = u8::from(self.try_into_error_code()?);
 
         let args = match self {
@@ -491,6 +649,8 @@ impl RadError {
             }
             RadError::ArrayIndexOutOfBounds { index } => Some(serialize_args((index,))?),
             RadError::MapKeyNotFound { key } => Some(serialize_args((key,))?),
+            RadError::ParseDateTime { format } => Some(serialize_args((format,))?),
+            RadError::StaleTimestamp { max_age } => Some(serialize_args((max_age,))?),
             RadError::UnhandledIntercept { inner, message } => {
                 let message = match (inner, message) {
                     // Only serialize the message
@@ -540,6 +700,7 @@ impl RadError {
             RadError::SourceScriptNotRADON => RadonErrors::SourceScriptNotRADON,
             RadError::RequestTooManySources => RadonErrors::RequestTooManySources,
             RadError::ScriptTooManyCalls => RadonErrors::ScriptTooManyCalls,
+            RadError::ScriptTooExpensive => RadonErrors::ScriptTooExpensive,
             RadError::UnsupportedOperator { .. } => RadonErrors::UnsupportedOperator,
             RadError::HttpStatus { .. } => RadonErrors::HTTPError,
             RadError::Underflow => RadonErrors::Underflow,
@@ -547,13 +708,21 @@ impl RadError {
             RadError::DivisionByZero => RadonErrors::DivisionByZero,
             RadError::InsufficientCommits => RadonErrors::InsufficientCommits,
             RadError::NoReveals => RadonErrors::NoReveals,
-            RadError::RetrieveTimeout => RadonErrors::RetrieveTimeout,
+            RadError::RetrieveTimeout
+            | RadError::HttpConnectTimeout { .. }
+            | RadError::HttpReadTimeout { .. } => RadonErrors::RetrieveTimeout,
+            // Retrying a request must not change the error that gets reported
+            RadError::HttpRetriesExhausted { last_error, .. } => {
+                return last_error.try_into_error_code()
+            }
             RadError::InsufficientConsensus { .. } => RadonErrors::InsufficientConsensus,
             RadError::TallyExecution { .. } => RadonErrors::TallyExecution,
             RadError::UnhandledIntercept { .. } => RadonErrors::UnhandledIntercept,
             RadError::MalformedReveal => RadonErrors::MalformedReveal,
             RadError::ArrayIndexOutOfBounds { .. } => RadonErrors::ArrayIndexOutOfBounds,
             RadError::MapKeyNotFound { .. } => RadonErrors::MapKeyNotFound,
+            RadError::ParseDateTime { .. } => RadonErrors::ParseDateTime,
+            RadError::StaleTimestamp { .. } => RadonErrors::StaleTimestamp,
             _ => return Err(RadError::EncodeRadonErrorUnknownCode),
         })
     }
@@ -702,6 +871,10 @@ mod tests {
             RadonErrors::MapKeyNotFound => RadError::MapKeyNotFound {
                 key: String::from("value"),
             },
+            RadonErrors::ParseDateTime => RadError::ParseDateTime {
+                format: String::from("%Y-%m-%d"),
+            },
+            RadonErrors::StaleTimestamp => RadError::StaleTimestamp { max_age: 300 },
             RadonErrors::UnhandledIntercept => RadError::UnhandledIntercept {
                 inner: None,
                 message: Some("Only the message field is serialized".to_string()),
@@ -803,7 +976,7 @@ mod tests {
 
     #[test]
     fn unhandled_intercept_wrong_single_quote_escape() {
-        use crate::RadonString;
+        use crate::types::string::RadonString;
 
         // Try to convert RadonErrors to RadError with no arguments
         let rad_error = RadError::UnhandledIntercept {
@@ -0,0 +1,313 @@
+use std::cmp::Ordering;
+
+use serde_cbor::Value;
+
+use crate::{
+    error::RadError,
+    filters::{keep_items, RadonFilters},
+    types::{array::RadonArray, RadonType, RadonTypes},
+};
+use witnet_data_structures::radon_report::ReportContext;
+
+/// Filter the items of a `RadonArray` by comparing them with the value given as argument.
+///
+/// `GreaterThan` and `LessThan`, as well as their negations `LessOrEqualThan` and
+/// `GreaterOrEqualThan`, support `Integer` and `Float` arrays and take a single `Integer` or
+/// `Float` argument. `Equals` and `NotEquals` additionally support `String` and `Boolean` arrays,
+/// in which case the argument must be a string or a boolean respectively.
+///
+/// `NaN` values are neither greater than, less than nor equal to any other value, so they are
+/// always discarded by `GreaterThan`, `LessThan` and `Equals`. As the negated filters keep exactly
+/// the items that the original filter discards, `NaN` values are always kept by `LessOrEqualThan`,
+/// `GreaterOrEqualThan` and `NotEquals`.
+// FIXME: Allow for now, since there is no safe cast function from an i128 to float yet
+#[allow(clippy::cast_precision_loss)]
+pub fn comparison_filter(
+    input: &RadonArray,
+    filter_code: RadonFilters,
+    extra_args: &[Value],
+    context: &mut ReportContext<RadonTypes>,
+) -> Result<RadonTypes, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonArray::radon_type_name(),
+        operator: filter_code.to_string(),
+        args: extra_args.to_vec(),
+    };
+    let unsupported = || RadError::UnsupportedFilter {
+        array: input.clone(),
+        filter: filter_code.to_string(),
+    };
+
+    if extra_args.len() != 1 {
+        return Err(wrong_args());
+    }
+
+    // The negated filters keep the complementary set of items, so they share the same ordering
+    let expected_ordering = match filter_code {
+        RadonFilters::GreaterThan | RadonFilters::LessOrEqualThan => Ordering::Greater,
+        RadonFilters::LessThan | RadonFilters::GreaterOrEqualThan => Ordering::Less,
+        RadonFilters::Equals | RadonFilters::NotEquals => Ordering::Equal,
+        _ => return Err(unsupported()),
+    };
+    let is_equality = expected_ordering == Ordering::Equal;
+    let matches = |ordering: Option<Ordering>| ordering == Some(expected_ordering);
+
+    let float_arg = match &extra_args[0] {
+        Value::Integer(i) => Some(*i as f64),
+        Value::Float(f) => Some(*f),
+        _ => None,
+    };
+
+    let value = input.value();
+    let keep: Vec<bool> = match (value.first(), &extra_args[0]) {
+        (None, _) => return Ok(RadonTypes::from(input.clone())),
+        (Some(RadonTypes::Integer(_)), Value::Integer(arg)) => value
+            .iter()
+            .map(|item| match item {
+                RadonTypes::Integer(i) => matches(Some(i.value().cmp(arg))),
+                _ => unreachable!(),
+            })
+            .collect(),
+        (Some(RadonTypes::Integer(_)), Value::Float(arg)) => value
+            .iter()
+            .map(|item| match item {
+                RadonTypes::Integer(i) => matches((i.value() as f64).partial_cmp(arg)),
+                _ => unreachable!(),
+            })
+            .collect(),
+        (Some(RadonTypes::Float(_)), Value::Integer(_))
+        | (Some(RadonTypes::Float(_)), Value::Float(_)) => {
+            let arg = float_arg.ok_or_else(wrong_args)?;
+
+            value
+                .iter()
+                .map(|item| match item {
+                    RadonTypes::Float(f) => matches(f.value().partial_cmp(&arg)),
+                    _ => unreachable!(),
+                })
+                .collect()
+        }
+        (Some(RadonTypes::String(_)), Value::Text(arg)) if is_equality => value
+            .iter()
+            .map(|item| match item {
+                RadonTypes::String(s) => matches(Some(s.value().as_str().cmp(arg))),
+                _ => unreachable!(),
+            })
+            .collect(),
+        (Some(RadonTypes::Boolean(_)), Value::Bool(arg)) if is_equality => value
+            .iter()
+            .map(|item| match item {
+                RadonTypes::Boolean(b) => matches(Some(b.value().cmp(arg))),
+                _ => unreachable!(),
+            })
+            .collect(),
+        (Some(RadonTypes::Integer(_)), _) | (Some(RadonTypes::Float(_)), _) => {
+            return Err(wrong_args())
+        }
+        (Some(RadonTypes::String(_)), _) | (Some(RadonTypes::Boolean(_)), _) if is_equality => {
+            return Err(wrong_args())
+        }
+        (Some(_), _) => return Err(unsupported()),
+    };
+
+    Ok(keep_items(input, filter_code, keep, context))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{
+        boolean::RadonBoolean, float::RadonFloat, integer::RadonInteger, string::RadonString,
+    };
+    use witnet_data_structures::radon_report::{Stage, TallyMetaData};
+
+    // Helper function which works with Rust integers, to remove RadonTypes from tests
+    fn icmp(
+        input_i128: &[i128],
+        filter_code: RadonFilters,
+        arg: Value,
+        ctx: &mut ReportContext<RadonTypes>,
+    ) -> Result<Vec<i128>, RadError> {
+        let input_vec: Vec<RadonTypes> = input_i128
+            .iter()
+            .map(|i| RadonTypes::Integer(RadonInteger::from(*i)))
+            .collect();
+        let input = RadonArray::from(input_vec);
+
+        let output = comparison_filter(&input, filter_code, &[arg], ctx)?;
+
+        let output_vec = match output {
+            RadonTypes::Array(x) => x.value(),
+            _ => panic!("Filter method should return a RadonArray"),
+        };
+        let output_i128 = output_vec
+            .into_iter()
+            .map(|r| match r {
+                RadonTypes::Integer(x) => x.value(),
+                _ => panic!("Filter method should return an array of integers"),
+            })
+            .collect();
+
+        Ok(output_i128)
+    }
+
+    #[test]
+    fn test_filter_comparison_integer() {
+        let input = vec![1, 2, 3, 4];
+        let ctx = &mut ReportContext::default();
+
+        let cases = vec![
+            (RadonFilters::GreaterThan, vec![3, 4]),
+            (RadonFilters::LessThan, vec![1]),
+            (RadonFilters::Equals, vec![2]),
+            (RadonFilters::LessOrEqualThan, vec![1, 2]),
+            (RadonFilters::GreaterOrEqualThan, vec![2, 3, 4]),
+            (RadonFilters::NotEquals, vec![1, 3, 4]),
+        ];
+        for (filter_code, expected) in cases {
+            assert_eq!(
+                icmp(&input, filter_code, Value::Integer(2), ctx),
+                Ok(expected)
+            );
+        }
+
+        assert_eq!(
+            icmp(&input, RadonFilters::GreaterThan, Value::Float(2.5), ctx),
+            Ok(vec![3, 4])
+        );
+    }
+
+    #[test]
+    fn test_filter_comparison_float() {
+        let input = RadonArray::from(vec![
+            RadonFloat::from(1.5).into(),
+            RadonFloat::from(std::f64::NAN).into(),
+            RadonFloat::from(3.0).into(),
+        ]);
+
+        let output = comparison_filter(
+            &input,
+            RadonFilters::LessThan,
+            &[Value::Integer(2)],
+            &mut ReportContext::default(),
+        )
+        .unwrap();
+        let expected = RadonTypes::from(RadonArray::from(vec![RadonFloat::from(1.5).into()]));
+        assert_eq!(output, expected);
+
+        // NaN is kept by the negated filter
+        let output = comparison_filter(
+            &input,
+            RadonFilters::GreaterOrEqualThan,
+            &[Value::Float(2.0)],
+            &mut ReportContext::default(),
+        )
+        .unwrap();
+        let expected = RadonTypes::from(RadonArray::from(vec![
+            RadonFloat::from(std::f64::NAN).into(),
+            RadonFloat::from(3.0).into(),
+        ]));
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_filter_comparison_string_and_boolean() {
+        let input = RadonArray::from(vec![
+            RadonString::from("yes").into(),
+            RadonString::from("no").into(),
+            RadonString::from("yes").into(),
+        ]);
+        let output = comparison_filter(
+            &input,
+            RadonFilters::Equals,
+            &[Value::Text("yes".to_string())],
+            &mut ReportContext::default(),
+        )
+        .unwrap();
+        let expected = RadonTypes::from(RadonArray::from(vec![
+            RadonString::from("yes").into(),
+            RadonString::from("yes").into(),
+        ]));
+        assert_eq!(output, expected);
+
+        let input = RadonArray::from(vec![
+            RadonBoolean::from(true).into(),
+            RadonBoolean::from(false).into(),
+        ]);
+        let output = comparison_filter(
+            &input,
+            RadonFilters::NotEquals,
+            &[Value::Bool(true)],
+            &mut ReportContext::default(),
+        )
+        .unwrap();
+        let expected = RadonTypes::from(RadonArray::from(vec![RadonBoolean::from(false).into()]));
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_filter_comparison_wrong_args() {
+        let input = RadonArray::from(vec![RadonInteger::from(1).into()]);
+
+        for extra_args in vec![
+            vec![],
+            vec![Value::Integer(1), Value::Integer(2)],
+            vec![Value::Text("1".to_string())],
+        ] {
+            let expected = RadError::WrongArguments {
+                input_type: RadonArray::radon_type_name(),
+                operator: RadonFilters::GreaterThan.to_string(),
+                args: extra_args.clone(),
+            };
+            let output = comparison_filter(
+                &input,
+                RadonFilters::GreaterThan,
+                &extra_args,
+                &mut ReportContext::default(),
+            );
+            assert_eq!(output.unwrap_err(), expected);
+        }
+    }
+
+    #[test]
+    fn test_filter_comparison_unsupported_type() {
+        let input = RadonArray::from(vec![
+            RadonString::from("a").into(),
+            RadonString::from("b").into(),
+        ]);
+        let expected = RadError::UnsupportedFilter {
+            array: input.clone(),
+            filter: RadonFilters::GreaterThan.to_string(),
+        };
+        let output = comparison_filter(
+            &input,
+            RadonFilters::GreaterThan,
+            &[Value::Text("a".to_string())],
+            &mut ReportContext::default(),
+        );
+        assert_eq!(output.unwrap_err(), expected);
+    }
+
+    #[test]
+    fn test_filter_comparison_liars() {
+        let input = vec![5, 1, 7, 3];
+        let mut ctx = ReportContext {
+            stage: Stage::Tally(TallyMetaData::default()),
+            ..ReportContext::default()
+        };
+
+        let output = icmp(
+            &input,
+            RadonFilters::GreaterThan,
+            Value::Integer(4),
+            &mut ctx,
+        );
+        assert_eq!(output, Ok(vec![5, 7]));
+
+        if let Stage::Tally(metadata) = ctx.stage {
+            assert_eq!(metadata.liars, vec![false, true, false, true]);
+        } else {
+            panic!("Not tally stage");
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     error::RadError,
-    filters::RadonFilters,
+    filters::{keep_items, RadonFilters},
     operators::array::transpose,
     reducers,
     types::{array::RadonArray, boolean::RadonBoolean, float::RadonFloat, RadonType, RadonTypes},
@@ -9,18 +9,41 @@ use serde_cbor::Value;
 use std::convert::TryFrom;
 use witnet_data_structures::radon_report::{ReportContext, Stage};
 
+pub fn standard_filter(
+    input: &RadonArray,
+    extra_args: &[Value],
+    context: &mut ReportContext<RadonTypes>,
+) -> Result<RadonTypes, RadError> {
+    deviation_standard_filter(input, RadonFilters::DeviationStandard, extra_args, context)
+}
+
+pub fn not_standard_filter(
+    input: &RadonArray,
+    extra_args: &[Value],
+    context: &mut ReportContext<RadonTypes>,
+) -> Result<RadonTypes, RadError> {
+    deviation_standard_filter(
+        input,
+        RadonFilters::NotDeviationStandard,
+        extra_args,
+        context,
+    )
+}
+
 // FIXME: Allow for now, wait for https://github.com/rust-lang/rust/issues/67058 to reach stable
 #[allow(clippy::cast_precision_loss)]
-pub fn standard_filter(
+fn deviation_standard_filter(
     input: &RadonArray,
+    filter_code: RadonFilters,
     extra_args: &[Value],
     context: &mut ReportContext<RadonTypes>,
 ) -> Result<RadonTypes, RadError> {
     let wrong_args = || RadError::WrongArguments {
         input_type: RadonArray::radon_type_name(),
-        operator: RadonFilters::DeviationStandard.to_string(),
+        operator: filter_code.to_string(),
         args: extra_args.to_vec(),
     };
+    let negated = filter_code.is_negated();
 
     if extra_args.len() != 1 {
         return Err(wrong_args());
@@ -44,7 +67,7 @@ pub fn standard_filter(
             if arr2.value().is_empty() {
                 return Err(RadError::UnsupportedFilter {
                     array: input.clone(),
-                    filter: RadonFilters::DeviationStandard.to_string(),
+                    filter: filter_code.to_string(),
                 });
             }
 
@@ -52,7 +75,7 @@ pub fn standard_filter(
             if !arr2.is_homogeneous() {
                 return Err(RadError::UnsupportedFilter {
                     array: input.clone(),
-                    filter: RadonFilters::DeviationStandard.to_string(),
+                    filter: filter_code.to_string(),
                 });
             }
 
@@ -61,22 +84,22 @@ pub fn standard_filter(
                 // 3D array
                 return Err(RadError::UnsupportedFilter {
                     array: input.clone(),
-                    filter: RadonFilters::DeviationStandard.to_string(),
+                    filter: filter_code.to_string(),
                 });
             }
-            let bool_matrix = boolean_standard_filter(&input, sigmas_float)?;
+            let bool_matrix = boolean_standard_filter(&input, filter_code, sigmas_float)?;
 
-            Ok(keep_rows(input, &bool_matrix, context))
+            Ok(keep_rows(input, &bool_matrix, negated, context))
         }
         Some(_rad_types) => {
             // 1D array
-            let bool_array = boolean_standard_filter(&input, sigmas_float)?;
+            let bool_array = boolean_standard_filter(&input, filter_code, sigmas_float)?;
 
             let bool_vec: Vec<bool> = bool_array
                 .value()
                 .iter()
                 .map(|b| match b {
-                    RadonTypes::Boolean(rad_bool) => !rad_bool.value(),
+                    RadonTypes::Boolean(rad_bool) => rad_bool.value() == negated,
                     _ => panic!("Expected RadonArray of RadonBoolean"),
                 })
                 .collect();
@@ -97,11 +120,13 @@ pub fn standard_filter(
     }
 }
 
-// Only keep rows from input for which all values in keep are true.
+// Only keep rows from input for which all values in keep are true (or, if negated, for which
+// at least one value in keep is false).
 // input and keep are assumed to have the same dimension
 fn keep_rows(
     input: &RadonArray,
     keep: &RadonArray,
+    negated: bool,
     context: &mut ReportContext<RadonTypes>,
 ) -> RadonTypes {
     let mut result = vec![];
@@ -121,10 +146,11 @@ fn keep_rows(
             }
         });
 
-        if row_true {
+        let keep_row = row_true != negated;
+        if keep_row {
             result.push(item);
         }
-        bool_vec.push(!row_true);
+        bool_vec.push(!keep_row);
     }
 
     if let Stage::Tally(ref mut metadata) = context.stage {
@@ -138,7 +164,11 @@ fn keep_rows(
 // whether to keep a value or not
 // FIXME: Allow for now, since there is no safe cast function from an i128 to float yet
 #[allow(clippy::cast_precision_loss)]
-fn boolean_standard_filter(input: &RadonArray, sigmas_float: f64) -> Result<RadonArray, RadError> {
+fn boolean_standard_filter(
+    input: &RadonArray,
+    filter_code: RadonFilters,
+    sigmas_float: f64,
+) -> Result<RadonArray, RadError> {
     // if input is empty, return the array
     if input.value().is_empty() {
         return Ok(input.clone());
@@ -146,7 +176,7 @@ fn boolean_standard_filter(input: &RadonArray, sigmas_float: f64) -> Result<Rado
 
     if !input.is_homogeneous() {
         return Err(RadError::UnsupportedOpNonHomogeneous {
-            operator: RadonFilters::DeviationStandard.to_string(),
+            operator: filter_code.to_string(),
         });
     }
 
@@ -205,6 +235,7 @@ fn boolean_standard_filter(input: &RadonArray, sigmas_float: f64) -> Result<Rado
                 if let RadonTypes::Array(v2mean) = v2mean {
                     standard_v.push(RadonTypes::from(boolean_standard_filter(
                         &v2mean,
+                        filter_code,
                         sigmas_float,
                     )?));
                 } else {
@@ -219,7 +250,7 @@ fn boolean_standard_filter(input: &RadonArray, sigmas_float: f64) -> Result<Rado
         }
         Some(_rad_types) => Err(RadError::UnsupportedFilter {
             array: input.clone(),
-            filter: RadonFilters::DeviationStandard.to_string(),
+            filter: filter_code.to_string(),
         }),
     }
 }
@@ -233,6 +264,99 @@ fn standard_limits(mean: f64, std_dev: f64, sigmas: f64) -> (f64, f64) {
     (keep_min, keep_max)
 }
 
+/// Keep the values whose distance to the median of all the values is not greater than the
+/// absolute amount given as argument.
+pub fn absolute_filter(
+    input: &RadonArray,
+    filter_code: RadonFilters,
+    extra_args: &[Value],
+    context: &mut ReportContext<RadonTypes>,
+) -> Result<RadonTypes, RadError> {
+    median_deviation_filter(
+        input,
+        filter_code,
+        extra_args,
+        context,
+        |_median, amount| amount,
+    )
+}
+
+/// Keep the values whose distance to the median of all the values is not greater than the
+/// given ratio of that median, i.e. `0.05` keeps the values that are within 5% of the median.
+pub fn relative_filter(
+    input: &RadonArray,
+    filter_code: RadonFilters,
+    extra_args: &[Value],
+    context: &mut ReportContext<RadonTypes>,
+) -> Result<RadonTypes, RadError> {
+    median_deviation_filter(input, filter_code, extra_args, context, |median, ratio| {
+        ratio * median.abs()
+    })
+}
+
+// Common implementation of the absolute and relative deviation filters. The single argument must
+// be a non-negative Integer or Float, and max_distance converts it into the maximum allowed
+// distance to the median.
+// FIXME: Allow for now, since there is no safe cast function from an i128 to float yet
+#[allow(clippy::cast_precision_loss)]
+fn median_deviation_filter<F>(
+    input: &RadonArray,
+    filter_code: RadonFilters,
+    extra_args: &[Value],
+    context: &mut ReportContext<RadonTypes>,
+    max_distance: F,
+) -> Result<RadonTypes, RadError>
+where
+    F: Fn(f64, f64) -> f64,
+{
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonArray::radon_type_name(),
+        operator: filter_code.to_string(),
+        args: extra_args.to_vec(),
+    };
+
+    if extra_args.len() != 1 {
+        return Err(wrong_args());
+    }
+
+    let threshold = match &extra_args[0] {
+        Value::Integer(i) => *i as f64,
+        Value::Float(f) => *f,
+        _ => return Err(wrong_args()),
+    };
+    if threshold.is_nan() || threshold < 0.0 {
+        return Err(wrong_args());
+    }
+
+    let value = input.value();
+    let values: Vec<f64> = match value.first() {
+        None => return Ok(RadonTypes::from(input.clone())),
+        Some(RadonTypes::Integer(_)) | Some(RadonTypes::Float(_)) => value
+            .iter()
+            .map(|item| match item {
+                RadonTypes::Integer(i) => i.value() as f64,
+                RadonTypes::Float(f) => f.value(),
+                _ => unreachable!(),
+            })
+            .collect(),
+        Some(_rad_types) => {
+            return Err(RadError::UnsupportedFilter {
+                array: input.clone(),
+                filter: filter_code.to_string(),
+            })
+        }
+    };
+
+    let median = reducers::deviation::median_of(&values);
+    let max_distance = max_distance(median, threshold);
+    let keep = values
+        .iter()
+        .map(|x| (x - median).abs() <= max_distance)
+        .collect();
+
+    Ok(keep_items(input, filter_code, keep, context))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -718,4 +842,134 @@ mod tests {
         };
         assert_eq!(output, expected_err);
     }
+
+    #[test]
+    fn test_filter_not_deviation_standard() {
+        let input = rfa(&[1.0, 2.0, 3.0]);
+        let extra_args = vec![Value::Float(1.0)];
+        let mut context = ReportContext {
+            stage: Stage::Tally(TallyMetaData::default()),
+            ..ReportContext::default()
+        };
+
+        let output = not_standard_filter(&input, &extra_args, &mut context).unwrap();
+        let expected = RadonTypes::from(rfa(&[1.0, 3.0]));
+        assert_eq!(output, expected);
+
+        if let Stage::Tally(metadata) = context.stage {
+            assert_eq!(metadata.liars, vec![false, true, false]);
+        } else {
+            panic!("Not tally stage");
+        }
+    }
+
+    #[test]
+    fn test_filter_deviation_absolute() {
+        let input = rfa(&[100.0, 101.0, 99.5, 120.0, 102.5]);
+        let extra_args = vec![Value::Integer(2)];
+        let mut context = ReportContext {
+            stage: Stage::Tally(TallyMetaData::default()),
+            ..ReportContext::default()
+        };
+
+        // Median is 101.0
+        let output = absolute_filter(
+            &input,
+            RadonFilters::DeviationAbsolute,
+            &extra_args,
+            &mut context,
+        )
+        .unwrap();
+        let expected = RadonTypes::from(rfa(&[100.0, 101.0, 99.5, 102.5]));
+        assert_eq!(output, expected);
+
+        if let Stage::Tally(metadata) = context.stage {
+            assert_eq!(metadata.liars, vec![false, false, false, true, false]);
+        } else {
+            panic!("Not tally stage");
+        }
+
+        let output = absolute_filter(
+            &input,
+            RadonFilters::NotDeviationAbsolute,
+            &extra_args,
+            &mut ReportContext::default(),
+        )
+        .unwrap();
+        let expected = RadonTypes::from(rfa(&[120.0]));
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_filter_deviation_relative() {
+        let input = RadonArray::from(vec![
+            RadonTypes::Integer(RadonInteger::from(1000)),
+            RadonTypes::Integer(RadonInteger::from(1040)),
+            RadonTypes::Integer(RadonInteger::from(960)),
+            RadonTypes::Integer(RadonInteger::from(1100)),
+            RadonTypes::Integer(RadonInteger::from(1010)),
+        ]);
+        // Keep values within 5% of the median (1010)
+        let extra_args = vec![Value::Float(0.05)];
+
+        let output = relative_filter(
+            &input,
+            RadonFilters::DeviationRelative,
+            &extra_args,
+            &mut ReportContext::default(),
+        )
+        .unwrap();
+        let expected = RadonTypes::from(RadonArray::from(vec![
+            RadonTypes::Integer(RadonInteger::from(1000)),
+            RadonTypes::Integer(RadonInteger::from(1040)),
+            RadonTypes::Integer(RadonInteger::from(960)),
+            RadonTypes::Integer(RadonInteger::from(1010)),
+        ]));
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_filter_deviation_relative_wrong_args() {
+        let input = rfa(&[1.0, 2.0]);
+
+        for extra_args in vec![
+            vec![],
+            vec![Value::Float(-0.1)],
+            vec![Value::Text("0.1".to_string())],
+            vec![Value::Float(0.1), Value::Float(0.1)],
+        ] {
+            let expected = RadError::WrongArguments {
+                input_type: RadonArray::radon_type_name(),
+                operator: RadonFilters::DeviationRelative.to_string(),
+                args: extra_args.clone(),
+            };
+            let output = relative_filter(
+                &input,
+                RadonFilters::DeviationRelative,
+                &extra_args,
+                &mut ReportContext::default(),
+            );
+            assert_eq!(output.unwrap_err(), expected);
+        }
+    }
+
+    #[test]
+    fn test_filter_deviation_absolute_unsupported_type() {
+        let input = RadonArray::from(vec![
+            RadonTypes::String(RadonString::from("foo")),
+            RadonTypes::String(RadonString::from("bar")),
+        ]);
+        let expected = RadError::UnsupportedFilter {
+            array: input.clone(),
+            filter: RadonFilters::DeviationAbsolute.to_string(),
+        };
+
+        let output = absolute_filter(
+            &input,
+            RadonFilters::DeviationAbsolute,
+            &[Value::Float(1.0)],
+            &mut ReportContext::default(),
+        );
+        assert_eq!(output.unwrap_err(), expected);
+    }
 }
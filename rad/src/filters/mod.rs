@@ -5,26 +5,25 @@ use serde_cbor::Value;
 
 use crate::error::RadError;
 use crate::types::{array::RadonArray, RadonType, RadonTypes};
-use witnet_data_structures::radon_report::ReportContext;
+use witnet_data_structures::radon_report::{ReportContext, Stage};
 
+pub mod comparison;
 pub mod deviation;
 pub mod mode;
+pub mod top;
 
-#[derive(Debug, PartialEq, TryFromPrimitive)]
+#[derive(Clone, Copy, Debug, PartialEq, TryFromPrimitive)]
 #[repr(u8)]
 pub enum RadonFilters {
-    // Implemented
-    DeviationStandard = 0x05,
-    Mode = 0x08,
-
-    // Not implemented
     GreaterThan = 0x00,
     LessThan = 0x01,
     Equals = 0x02,
     DeviationAbsolute = 0x03,
     DeviationRelative = 0x04,
+    DeviationStandard = 0x05,
     Top = 0x06,
     Bottom = 0x07,
+    Mode = 0x08,
     LessOrEqualThan = 0x80,
     GreaterOrEqualThan = 0x81,
     NotEquals = 0x82,
@@ -36,6 +35,17 @@ pub enum RadonFilters {
     NotMode = 0x88,
 }
 
+impl RadonFilters {
+    /// Whether this filter is the negation of another filter, i.e. it keeps exactly the items
+    /// that the other filter would discard.
+    ///
+    /// The negated filters are the ones with the highest bit of the code set: `0x80` is the
+    /// negation of `0x00`, `0x81` is the negation of `0x01`, and so on.
+    pub fn is_negated(self) -> bool {
+        (self as u8) & 0x80 != 0
+    }
+}
+
 impl fmt::Display for RadonFilters {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "RadonFilters::{:?}", self)
@@ -48,21 +58,34 @@ pub fn filter(
     extra_args: &[Value],
     context: &mut ReportContext<RadonTypes>,
 ) -> Result<RadonTypes, RadError> {
-    let error = || {
-        Err(RadError::UnsupportedFilter {
-            array: input.clone(),
-            filter: filter_code.to_string(),
-        })
-    };
-
     if input.is_homogeneous() || input.value().is_empty() {
         match filter_code {
+            RadonFilters::GreaterThan
+            | RadonFilters::LessThan
+            | RadonFilters::Equals
+            | RadonFilters::LessOrEqualThan
+            | RadonFilters::GreaterOrEqualThan
+            | RadonFilters::NotEquals => {
+                comparison::comparison_filter(input, filter_code, extra_args, context)
+            }
+            RadonFilters::DeviationAbsolute | RadonFilters::NotDeviationAbsolute => {
+                deviation::absolute_filter(input, filter_code, extra_args, context)
+            }
+            RadonFilters::DeviationRelative | RadonFilters::NotDeviationRelative => {
+                deviation::relative_filter(input, filter_code, extra_args, context)
+            }
             RadonFilters::DeviationStandard => {
                 deviation::standard_filter(input, extra_args, context)
             }
-
+            RadonFilters::NotDeviationStandard => {
+                deviation::not_standard_filter(input, extra_args, context)
+            }
+            RadonFilters::Top
+            | RadonFilters::Bottom
+            | RadonFilters::NotTop
+            | RadonFilters::NotBottom => top::top_filter(input, filter_code, extra_args, context),
             RadonFilters::Mode => mode::mode_filter(input, context),
-            _ => error(),
+            RadonFilters::NotMode => mode::not_mode_filter(input, context),
         }
     } else {
         Err(RadError::UnsupportedOpNonHomogeneous {
@@ -70,3 +93,37 @@ pub fn filter(
         })
     }
 }
+
+/// Keep the items of `input` for which `keep` is true, negating `keep` first if the filter is a
+/// negated one. When in tally stage, the discarded items are marked as liars.
+fn keep_items(
+    input: &RadonArray,
+    filter_code: RadonFilters,
+    keep: Vec<bool>,
+    context: &mut ReportContext<RadonTypes>,
+) -> RadonTypes {
+    let negated = filter_code.is_negated();
+    let mut liars = vec![];
+
+    let result: Vec<RadonTypes> = input
+        .value()
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(item, keep)| {
+            let keep = keep != negated;
+            liars.push(!keep);
+
+            if keep {
+                Some(item)
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    if let Stage::Tally(ref mut metadata) = context.stage {
+        metadata.update_liars(liars);
+    }
+
+    RadonTypes::from(RadonArray::from(result))
+}
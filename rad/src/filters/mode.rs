@@ -8,6 +8,22 @@ use witnet_data_structures::radon_report::{ReportContext, Stage};
 pub fn mode_filter(
     input: &RadonArray,
     context: &mut ReportContext<RadonTypes>,
+) -> Result<RadonTypes, RadError> {
+    filter_by_mode(input, false, context)
+}
+
+/// Keep all the values that are different from the mode.
+pub fn not_mode_filter(
+    input: &RadonArray,
+    context: &mut ReportContext<RadonTypes>,
+) -> Result<RadonTypes, RadError> {
+    filter_by_mode(input, true, context)
+}
+
+fn filter_by_mode(
+    input: &RadonArray,
+    negated: bool,
+    context: &mut ReportContext<RadonTypes>,
 ) -> Result<RadonTypes, RadError> {
     let mode = mode(&input)?;
     let mut liars = vec![];
@@ -16,7 +32,7 @@ pub fn mode_filter(
         .value()
         .into_iter()
         .filter(|rad_types| {
-            let cond = rad_types == &mode;
+            let cond = (rad_types == &mode) != negated;
             liars.push(!cond);
             cond
         })
@@ -227,4 +243,31 @@ mod tests {
             panic!("Not tally stage");
         }
     }
+
+    #[test]
+    fn test_filter_not_mode() {
+        let input = RadonArray::from(vec![
+            RadonInteger::from(1).into(),
+            RadonInteger::from(2).into(),
+            RadonInteger::from(2).into(),
+            RadonInteger::from(3).into(),
+        ]);
+        let expected = RadonTypes::from(RadonArray::from(vec![
+            RadonInteger::from(1).into(),
+            RadonInteger::from(3).into(),
+        ]));
+
+        let mut ctx = ReportContext {
+            stage: Stage::Tally(TallyMetaData::default()),
+            ..ReportContext::default()
+        };
+        let output = not_mode_filter(&input, &mut ctx).unwrap();
+        assert_eq!(output, expected);
+
+        if let Stage::Tally(metadata) = ctx.stage {
+            assert_eq!(metadata.liars, vec![false, true, true, false]);
+        } else {
+            panic!("Not tally stage");
+        }
+    }
 }
@@ -0,0 +1,215 @@
+use std::{cmp::Ordering, convert::TryFrom};
+
+use serde_cbor::Value;
+
+use crate::{
+    error::RadError,
+    filters::{keep_items, RadonFilters},
+    reducers::min_max::compare,
+    types::{array::RadonArray, RadonType, RadonTypes},
+};
+use witnet_data_structures::radon_report::ReportContext;
+
+/// Keep the `n` greatest (`Top`) or smallest (`Bottom`) values, where `n` is the only argument.
+///
+/// Integers and floats are compared numerically, while strings are compared lexicographically.
+/// Ties are broken by position, so when several items are equal, the ones that appear first in
+/// the input are kept. `NotTop` and `NotBottom` keep all the items except for those.
+pub fn top_filter(
+    input: &RadonArray,
+    filter_code: RadonFilters,
+    extra_args: &[Value],
+    context: &mut ReportContext<RadonTypes>,
+) -> Result<RadonTypes, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonArray::radon_type_name(),
+        operator: filter_code.to_string(),
+        args: extra_args.to_vec(),
+    };
+
+    if extra_args.len() != 1 {
+        return Err(wrong_args());
+    }
+
+    let n = match &extra_args[0] {
+        Value::Integer(n) => usize::try_from(*n).map_err(|_| wrong_args())?,
+        _ => return Err(wrong_args()),
+    };
+
+    if !input.is_homogeneous() {
+        return Err(RadError::UnsupportedOpNonHomogeneous {
+            operator: filter_code.to_string(),
+        });
+    }
+
+    let value = input.value();
+    match value.first() {
+        None => return Ok(RadonTypes::from(input.clone())),
+        Some(RadonTypes::Integer(_)) | Some(RadonTypes::Float(_)) | Some(RadonTypes::String(_)) => {
+        }
+        Some(_rad_types) => {
+            return Err(RadError::UnsupportedFilter {
+                array: input.clone(),
+                filter: filter_code.to_string(),
+            })
+        }
+    }
+
+    let top = match filter_code {
+        RadonFilters::Top | RadonFilters::NotTop => true,
+        RadonFilters::Bottom | RadonFilters::NotBottom => false,
+        _ => {
+            return Err(RadError::UnsupportedFilter {
+                array: input.clone(),
+                filter: filter_code.to_string(),
+            })
+        }
+    };
+
+    // Sort the indexes of the items, this is a stable sort so ties are broken by position.
+    // The input is homogeneous, so the items can always be compared
+    let mut indexes: Vec<usize> = (0..value.len()).collect();
+    indexes.sort_by(|&a, &b| {
+        let (a, b) = if top { (b, a) } else { (a, b) };
+        compare(&value[a], &value[b]).unwrap_or(Ordering::Equal)
+    });
+
+    let mut keep = vec![false; value.len()];
+    for &index in indexes.iter().take(n) {
+        keep[index] = true;
+    }
+
+    Ok(keep_items(input, filter_code, keep, context))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{boolean::RadonBoolean, integer::RadonInteger, string::RadonString};
+    use witnet_data_structures::radon_report::{Stage, TallyMetaData};
+
+    // Helper function which works with Rust integers, to remove RadonTypes from tests
+    fn itop(
+        input_i128: &[i128],
+        filter_code: RadonFilters,
+        n: i128,
+        ctx: &mut ReportContext<RadonTypes>,
+    ) -> Result<Vec<i128>, RadError> {
+        let input_vec: Vec<RadonTypes> = input_i128
+            .iter()
+            .map(|i| RadonTypes::Integer(RadonInteger::from(*i)))
+            .collect();
+        let input = RadonArray::from(input_vec);
+
+        let output = top_filter(&input, filter_code, &[Value::Integer(n)], ctx)?;
+
+        let output_vec = match output {
+            RadonTypes::Array(x) => x.value(),
+            _ => panic!("Filter method should return a RadonArray"),
+        };
+        let output_i128 = output_vec
+            .into_iter()
+            .map(|r| match r {
+                RadonTypes::Integer(x) => x.value(),
+                _ => panic!("Filter method should return an array of integers"),
+            })
+            .collect();
+
+        Ok(output_i128)
+    }
+
+    #[test]
+    fn test_filter_top_bottom_integer() {
+        let input = vec![5, 1, 9, 3, 7];
+        let ctx = &mut ReportContext::default();
+
+        assert_eq!(itop(&input, RadonFilters::Top, 2, ctx), Ok(vec![9, 7]));
+        assert_eq!(itop(&input, RadonFilters::Bottom, 2, ctx), Ok(vec![1, 3]));
+        assert_eq!(
+            itop(&input, RadonFilters::NotTop, 2, ctx),
+            Ok(vec![5, 1, 3])
+        );
+        assert_eq!(
+            itop(&input, RadonFilters::NotBottom, 2, ctx),
+            Ok(vec![5, 9, 7])
+        );
+        assert_eq!(itop(&input, RadonFilters::Top, 10, ctx), Ok(input.clone()));
+        assert_eq!(itop(&input, RadonFilters::Top, 0, ctx), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_filter_top_ties() {
+        let mut ctx = ReportContext {
+            stage: Stage::Tally(TallyMetaData::default()),
+            ..ReportContext::default()
+        };
+
+        let output = itop(&[3, 3, 1, 3], RadonFilters::Top, 2, &mut ctx);
+        assert_eq!(output, Ok(vec![3, 3]));
+
+        if let Stage::Tally(metadata) = ctx.stage {
+            assert_eq!(metadata.liars, vec![false, false, true, true]);
+        } else {
+            panic!("Not tally stage");
+        }
+    }
+
+    #[test]
+    fn test_filter_top_string() {
+        let input = RadonArray::from(vec![
+            RadonString::from("b").into(),
+            RadonString::from("c").into(),
+            RadonString::from("a").into(),
+        ]);
+        let output = top_filter(
+            &input,
+            RadonFilters::Bottom,
+            &[Value::Integer(1)],
+            &mut ReportContext::default(),
+        )
+        .unwrap();
+        let expected = RadonTypes::from(RadonArray::from(vec![RadonString::from("a").into()]));
+
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_filter_top_wrong_args() {
+        let input = RadonArray::from(vec![RadonInteger::from(1).into()]);
+
+        for extra_args in vec![vec![], vec![Value::Integer(-1)], vec![Value::Float(1.0)]] {
+            let expected = RadError::WrongArguments {
+                input_type: RadonArray::radon_type_name(),
+                operator: RadonFilters::Top.to_string(),
+                args: extra_args.clone(),
+            };
+            let output = top_filter(
+                &input,
+                RadonFilters::Top,
+                &extra_args,
+                &mut ReportContext::default(),
+            );
+            assert_eq!(output.unwrap_err(), expected);
+        }
+    }
+
+    #[test]
+    fn test_filter_top_unsupported_type() {
+        let input = RadonArray::from(vec![
+            RadonBoolean::from(true).into(),
+            RadonBoolean::from(false).into(),
+        ]);
+        let expected = RadError::UnsupportedFilter {
+            array: input.clone(),
+            filter: RadonFilters::Top.to_string(),
+        };
+        let output = top_filter(
+            &input,
+            RadonFilters::Top,
+            &[Value::Integer(1)],
+            &mut ReportContext::default(),
+        );
+
+        assert_eq!(output.unwrap_err(), expected);
+    }
+}
@@ -0,0 +1,213 @@
+//! The original BLAKE hash functions (SHA-3 finalists), as specified in
+//! "SHA-3 proposal BLAKE", version 1.3, with the final round numbers (14 rounds for BLAKE-256
+//! and 16 rounds for BLAKE-512) and no salt.
+
+/// Message word permutations used in each round.
+const SIGMA: [[usize; 16]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+/// Indexes of the state words mixed by each of the 8 applications of G in a round: first the
+/// 4 columns, then the 4 diagonals.
+const G_INDEXES: [[usize; 4]; 8] = [
+    [0, 4, 8, 12],
+    [1, 5, 9, 13],
+    [2, 6, 10, 14],
+    [3, 7, 11, 15],
+    [0, 5, 10, 15],
+    [1, 6, 11, 12],
+    [2, 7, 8, 13],
+    [3, 4, 9, 14],
+];
+
+const BLAKE256_IV: [u32; 8] = [
+    0x6A09_E667,
+    0xBB67_AE85,
+    0x3C6E_F372,
+    0xA54F_F53A,
+    0x510E_527F,
+    0x9B05_688C,
+    0x1F83_D9AB,
+    0x5BE0_CD19,
+];
+
+const BLAKE256_CONSTANTS: [u32; 16] = [
+    0x243F_6A88,
+    0x85A3_08D3,
+    0x1319_8A2E,
+    0x0370_7344,
+    0xA409_3822,
+    0x299F_31D0,
+    0x082E_FA98,
+    0xEC4E_6C89,
+    0x4528_21E6,
+    0x38D0_1377,
+    0xBE54_66CF,
+    0x34E9_0C6C,
+    0xC0AC_29B7,
+    0xC97C_50DD,
+    0x3F84_D5B5,
+    0xB547_0917,
+];
+
+const BLAKE512_IV: [u64; 8] = [
+    0x6A09_E667_F3BC_C908,
+    0xBB67_AE85_84CA_A73B,
+    0x3C6E_F372_FE94_F82B,
+    0xA54F_F53A_5F1D_36F1,
+    0x510E_527F_ADE6_82D1,
+    0x9B05_688C_2B3E_6C1F,
+    0x1F83_D9AB_FB41_BD6B,
+    0x5BE0_CD19_137E_2179,
+];
+
+const BLAKE512_CONSTANTS: [u64; 16] = [
+    0x243F_6A88_85A3_08D3,
+    0x1319_8A2E_0370_7344,
+    0xA409_3822_299F_31D0,
+    0x082E_FA98_EC4E_6C89,
+    0x4528_21E6_38D0_1377,
+    0xBE54_66CF_34E9_0C6C,
+    0xC0AC_29B7_C97C_50DD,
+    0x3F84_D5B5_B547_0917,
+    0x9216_D5D9_8979_FB1B,
+    0xD131_0BA6_98DF_B5AC,
+    0x2FFD_72DB_D01A_DFB7,
+    0xB8E1_AFED_6A26_7E96,
+    0xBA7C_9045_F12C_7F99,
+    0x24A1_9947_B391_6CF7,
+    0x0801_F2E2_858E_FC16,
+    0x6369_20D8_7157_4E69,
+];
+
+/// Define a BLAKE hash function over a word type.
+///
+/// The message is padded up front, so every block is compressed the same way. The counter of
+/// each block is the number of message bits hashed up to the end of that block, and it is 0 for
+/// a block that only contains padding.
+macro_rules! blake {
+    ($name:ident, $word:ty, $iv:expr, $constants:expr, $rounds:expr, $rotations:expr) => {
+        pub fn $name(input: &[u8]) -> Vec<u8> {
+            const WORD_BYTES: usize = std::mem::size_of::<$word>();
+            const BLOCK_BYTES: usize = 16 * WORD_BYTES;
+            // The message length is appended as a 2-word big-endian integer
+            const LENGTH_BYTES: usize = 2 * WORD_BYTES;
+
+            let message_bits = input.len() as u128 * 8;
+            let padded_len =
+                (input.len() + 1 + LENGTH_BYTES + BLOCK_BYTES - 1) / BLOCK_BYTES * BLOCK_BYTES;
+            let mut padded = vec![0u8; padded_len];
+            padded[..input.len()].copy_from_slice(input);
+            padded[input.len()] |= 0x80;
+            padded[padded_len - LENGTH_BYTES - 1] |= 0x01;
+            padded[padded_len - LENGTH_BYTES..]
+                .copy_from_slice(&message_bits.to_be_bytes()[16 - LENGTH_BYTES..]);
+
+            let mut h = $iv;
+            for (i, block) in padded.chunks_exact(BLOCK_BYTES).enumerate() {
+                let block_start_bits = (i * BLOCK_BYTES) as u128 * 8;
+                let counter = if message_bits > block_start_bits {
+                    std::cmp::min(message_bits, block_start_bits + BLOCK_BYTES as u128 * 8)
+                } else {
+                    0
+                };
+                let t0 = counter as $word;
+                let t1 = (counter >> (8 * WORD_BYTES)) as $word;
+
+                let mut m = [0 as $word; 16];
+                for (word, bytes) in m.iter_mut().zip(block.chunks_exact(WORD_BYTES)) {
+                    let mut be_bytes = [0u8; WORD_BYTES];
+                    be_bytes.copy_from_slice(bytes);
+                    *word = <$word>::from_be_bytes(be_bytes);
+                }
+
+                let c = $constants;
+                let mut v = [0 as $word; 16];
+                v[..8].copy_from_slice(&h);
+                v[8..12].copy_from_slice(&c[..4]);
+                v[12] = t0 ^ c[4];
+                v[13] = t0 ^ c[5];
+                v[14] = t1 ^ c[6];
+                v[15] = t1 ^ c[7];
+
+                let [r0, r1, r2, r3] = $rotations;
+                for round in 0..$rounds {
+                    let s = &SIGMA[round % 10];
+                    for (g, &[a, b, cc, d]) in G_INDEXES.iter().enumerate() {
+                        let (x, y) = (s[2 * g], s[2 * g + 1]);
+                        v[a] = v[a].wrapping_add(v[b]).wrapping_add(m[x] ^ c[y]);
+                        v[d] = (v[d] ^ v[a]).rotate_right(r0);
+                        v[cc] = v[cc].wrapping_add(v[d]);
+                        v[b] = (v[b] ^ v[cc]).rotate_right(r1);
+                        v[a] = v[a].wrapping_add(v[b]).wrapping_add(m[y] ^ c[x]);
+                        v[d] = (v[d] ^ v[a]).rotate_right(r2);
+                        v[cc] = v[cc].wrapping_add(v[d]);
+                        v[b] = (v[b] ^ v[cc]).rotate_right(r3);
+                    }
+                }
+
+                for (j, word) in h.iter_mut().enumerate() {
+                    *word ^= v[j] ^ v[j + 8];
+                }
+            }
+
+            h.iter()
+                .flat_map(|word| word.to_be_bytes().to_vec())
+                .collect()
+        }
+    };
+}
+
+blake!(
+    blake_256,
+    u32,
+    BLAKE256_IV,
+    BLAKE256_CONSTANTS,
+    14,
+    [16, 12, 8, 7]
+);
+blake!(
+    blake_512,
+    u64,
+    BLAKE512_IV,
+    BLAKE512_CONSTANTS,
+    16,
+    [32, 25, 16, 11]
+);
+
+#[test]
+fn test_blake_256() {
+    // Test vectors from the BLAKE specification
+    assert_eq!(
+        hex::encode(blake_256(&[0])),
+        "0ce8d4ef4dd7cd8d62dfded9d4edb0a774ae6a41929a74da23109e8f11139c87"
+    );
+    assert_eq!(
+        hex::encode(blake_256(&[0; 72])),
+        "d419bad32d504fb7d44d460c42c5593fe544fa4c135dec31e21bd9abdcc22d41"
+    );
+}
+
+#[test]
+fn test_blake_512() {
+    // Test vectors from the BLAKE specification
+    assert_eq!(
+        hex::encode(blake_512(&[0])),
+        "97961587f6d970faba6d2478045de6d1fabd09b61ae50932054d52bc29d31be4\
+         ff9102b9f69e2bbdb83be13d4b9c06091e5fa0b48bd081b634058be0ec49beb3"
+    );
+    assert_eq!(
+        hex::encode(blake_512(&[0; 144])),
+        "313717d608e9cf758dcb1eb0f0c3cf9fc150b2d500fb33f51c52afc99d358a2f\
+         1374b8a38bba7974e7f6ef79cab16f22ce1e649d6e01ad9589c213045d545dde"
+    );
+}
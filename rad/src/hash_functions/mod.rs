@@ -1,10 +1,18 @@
 use std::fmt;
 
+use blake2::{Blake2b512, Blake2s256};
+use md5::Md5;
 use num_enum::TryFromPrimitive;
+use ripemd::{Ripemd128, Ripemd160, Ripemd320};
+use sha1::Sha1;
+use sha3::{Digest, Keccak256, Sha3_224, Sha3_256, Sha3_384, Sha3_512};
+use whirlpool::Whirlpool;
 
 use crate::error::RadError;
-use crate::hash_functions::sha2::sha2_256;
+use crate::hash_functions::blake::{blake_256, blake_512};
+use crate::hash_functions::sha2::{sha2_224, sha2_256, sha2_384, sha2_512};
 
+mod blake;
 mod sha2;
 
 #[derive(Debug, PartialEq, TryFromPrimitive)]
@@ -24,11 +32,14 @@ pub enum RadonHashFunctions {
     SHA2_256 = 0x0A,
     SHA2_384 = 0x0B,
     SHA2_512 = 0x0C,
+    // The SHA3_* functions are the NIST FIPS 202 standard, which differs from the original
+    // Keccak submission in the padding. Use `Keccak256` for the hash used by Ethereum.
     SHA3_224 = 0x0D,
     SHA3_256 = 0x0E,
     SHA3_384 = 0x0F,
     SHA3_512 = 0x10,
     Whirlpool512 = 0x11,
+    Keccak256 = 0x12,
 }
 
 impl fmt::Display for RadonHashFunctions {
@@ -37,10 +48,34 @@ impl fmt::Display for RadonHashFunctions {
     }
 }
 
+/// Compute the digest of `input` using any hash function implementing the `Digest` trait.
+fn digest<D: Digest>(input: &[u8]) -> Vec<u8> {
+    D::digest(input).to_vec()
+}
+
+/// Compute the digest of `input` using the hash function identified by `hash_function_code`.
 pub fn hash(input: &[u8], hash_function_code: RadonHashFunctions) -> Result<Vec<u8>, RadError> {
     match hash_function_code {
+        RadonHashFunctions::Blake256 => Ok(blake_256(input)),
+        RadonHashFunctions::Blake512 => Ok(blake_512(input)),
+        RadonHashFunctions::Blake2s256 => Ok(digest::<Blake2s256>(input)),
+        RadonHashFunctions::Blake2b512 => Ok(digest::<Blake2b512>(input)),
+        RadonHashFunctions::MD5_128 => Ok(digest::<Md5>(input)),
+        RadonHashFunctions::Ripemd128 => Ok(digest::<Ripemd128>(input)),
+        RadonHashFunctions::Ripemd160 => Ok(digest::<Ripemd160>(input)),
+        RadonHashFunctions::Ripemd320 => Ok(digest::<Ripemd320>(input)),
+        RadonHashFunctions::SHA1_160 => Ok(digest::<Sha1>(input)),
+        RadonHashFunctions::SHA2_224 => Ok(sha2_224(input)),
         RadonHashFunctions::SHA2_256 => Ok(sha2_256(input)),
-        _ => Err(RadError::UnsupportedHashFunction {
+        RadonHashFunctions::SHA2_384 => Ok(sha2_384(input)),
+        RadonHashFunctions::SHA2_512 => Ok(sha2_512(input)),
+        RadonHashFunctions::SHA3_224 => Ok(digest::<Sha3_224>(input)),
+        RadonHashFunctions::SHA3_256 => Ok(digest::<Sha3_256>(input)),
+        RadonHashFunctions::SHA3_384 => Ok(digest::<Sha3_384>(input)),
+        RadonHashFunctions::SHA3_512 => Ok(digest::<Sha3_512>(input)),
+        RadonHashFunctions::Whirlpool512 => Ok(digest::<Whirlpool>(input)),
+        RadonHashFunctions::Keccak256 => Ok(digest::<Keccak256>(input)),
+        RadonHashFunctions::Fail => Err(RadError::UnsupportedHashFunction {
             function: hash_function_code.to_string(),
         }),
     }
@@ -58,3 +93,135 @@ fn test_hash() {
 
     assert_eq!(output_slice, expected);
 }
+
+#[test]
+fn test_hash_all_functions() {
+    let input = b"Hello, World!";
+    let cases = vec![
+        (
+            RadonHashFunctions::Blake2s256,
+            "ec9db904d636ef61f1421b2ba47112a4fa6b8964fd4a0a514834455c21df7812",
+        ),
+        (
+            RadonHashFunctions::Blake2b512,
+            "7dfdb888af71eae0e6a6b751e8e3413d767ef4fa52a7993daa9ef097f7aa3d94\
+             9199c113caa37c94f80cf3b22f7d9d6e4f5def4ff927830cffe4857c34be3d89",
+        ),
+        (
+            RadonHashFunctions::MD5_128,
+            "65a8e27d8879283831b664bd8b7f0ad4",
+        ),
+        (
+            RadonHashFunctions::Ripemd128,
+            "67f9fe75ca2886dc76ad00f7276bdeba",
+        ),
+        (
+            RadonHashFunctions::Ripemd160,
+            "527a6a4b9a6da75607546842e0e00105350b1aaf",
+        ),
+        (
+            RadonHashFunctions::Ripemd320,
+            "f9832e5bb00576fc56c2221f404eb77addeafe49843c773f0df3fc5a996d5934f3c96e94aeb80e89",
+        ),
+        (
+            RadonHashFunctions::SHA1_160,
+            "0a0a9f2a6772942557ab5355d76af442f8f65e01",
+        ),
+        (
+            RadonHashFunctions::SHA2_224,
+            "72a23dfa411ba6fde01dbfabf3b00a709c93ebf273dc29e2d8b261ff",
+        ),
+        (
+            RadonHashFunctions::SHA2_256,
+            "dffd6021bb2bd5b0af676290809ec3a53191dd81c7f70a4b28688a362182986f",
+        ),
+        (
+            RadonHashFunctions::SHA2_384,
+            "5485cc9b3365b4305dfb4e8337e0a598a574f8242bf17289\
+             e0dd6c20a3cd44a089de16ab4ab308f63e44b1170eb5f515",
+        ),
+        (
+            RadonHashFunctions::SHA2_512,
+            "374d794a95cdcfd8b35993185fef9ba368f160d8daf432d08ba9f1ed1e5abe6c\
+             c69291e0fa2fe0006a52570ef18c19def4e617c33ce52ef0a6e5fbe318cb0387",
+        ),
+        (
+            RadonHashFunctions::SHA3_224,
+            "853048fb8b11462b6100385633c0cc8dcdc6e2b8e376c28102bc84f2",
+        ),
+        (
+            RadonHashFunctions::SHA3_256,
+            "1af17a664e3fa8e419b8ba05c2a173169df76162a5a286e0c405b460d478f7ef",
+        ),
+        (
+            RadonHashFunctions::SHA3_384,
+            "aa9ad8a49f31d2ddcabbb7010a1566417cff803fef50eba2\
+             39558826f872e468c5743e7f026b0a8e5b2d7a1cc465cdbe",
+        ),
+        (
+            RadonHashFunctions::SHA3_512,
+            "38e05c33d7b067127f217d8c856e554fcff09c9320b8a5979ce2ff5d95dd27ba\
+             35d1fba50c562dfd1d6cc48bc9c5baa4390894418cc942d968f97bcb659419ed",
+        ),
+    ];
+
+    for (hash_function, expected) in cases {
+        let output = hash(input, hash_function).unwrap();
+        assert_eq!(hex::encode(output), expected);
+    }
+}
+
+#[test]
+fn test_hash_whirlpool_512() {
+    // Test vectors from the ISO/IEC 10118-3 test suite
+    let cases = vec![
+        (
+            &b""[..],
+            "19fa61d75522a4669b44e39c1d2e1726c530232130d407f89afee0964997f7a7\
+             3e83be698b288febcf88e3e03c4f0757ea8964e59b63d93708b138cc42a66eb3",
+        ),
+        (
+            &b"abc"[..],
+            "4e2448a4c6f486bb16b6562c73b4020bf3043e3a731bce721ae1b303d97e6d4c\
+             7181eebdb6c57e277d0e34957114cbd6c797fc9d95d8b582d225292076d4eef5",
+        ),
+        (
+            &b"The quick brown fox jumps over the lazy dog"[..],
+            "b97de512e91e3828b40d2b0fdce9ceb3c4a71f9bea8d88e75c4fa854df36725f\
+             d2b52eb6544edcacd6f8beddfea403cb55ae31f03ad62a5ef54e42ee82c3fb35",
+        ),
+    ];
+
+    for (input, expected) in cases {
+        let output = hash(input, RadonHashFunctions::Whirlpool512).unwrap();
+        assert_eq!(hex::encode(output), expected);
+    }
+}
+
+#[test]
+fn test_hash_keccak_256() {
+    // Keccak-256 is not the same as the standardized SHA3-256
+    let output = hash(b"", RadonHashFunctions::Keccak256).unwrap();
+    assert_eq!(
+        hex::encode(output),
+        "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+    );
+
+    let output = hash(b"", RadonHashFunctions::SHA3_256).unwrap();
+    assert_eq!(
+        hex::encode(output),
+        "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a"
+    );
+}
+
+#[test]
+fn test_hash_fail() {
+    let output = hash(b"Hello, World!", RadonHashFunctions::Fail);
+
+    assert_eq!(
+        output.unwrap_err(),
+        RadError::UnsupportedHashFunction {
+            function: RadonHashFunctions::Fail.to_string(),
+        }
+    );
+}
@@ -1,9 +1,22 @@
+use sha2::{Digest, Sha224, Sha384, Sha512};
 use witnet_crypto::hash::calculate_sha256;
 
+pub fn sha2_224(input: &[u8]) -> Vec<u8> {
+    Sha224::digest(input).to_vec()
+}
+
 pub fn sha2_256(input: &[u8]) -> Vec<u8> {
     calculate_sha256(input).as_ref().to_vec()
 }
 
+pub fn sha2_384(input: &[u8]) -> Vec<u8> {
+    Sha384::digest(input).to_vec()
+}
+
+pub fn sha2_512(input: &[u8]) -> Vec<u8> {
+    Sha512::digest(input).to_vec()
+}
+
 #[test]
 fn test_sha2_256() {
     let input = [72, 101, 108, 108, 111, 44, 32, 87, 111, 114, 108, 100, 33];
@@ -0,0 +1,102 @@
+//! Support for sending the extra HTTP headers declared in retrieval sources.
+
+use futures::future::BoxFuture;
+use surf::{
+    http::header::{HeaderName, HeaderValue},
+    middleware::{HttpClient, Middleware, Next, Request, Response},
+    Exception,
+};
+
+use crate::error::RadError;
+
+/// A `surf` middleware that inserts a list of HTTP headers into every request.
+///
+/// This is needed because `surf::Request::set_header` only accepts header names that live for
+/// `'static`, while the headers of a retrieval source are only known at runtime.
+#[derive(Clone, Debug, Default)]
+pub struct ExtraHeaders {
+    headers: Vec<(HeaderName, HeaderValue)>,
+}
+
+impl ExtraHeaders {
+    /// Validate and convert a list of (name, value) pairs into `ExtraHeaders`.
+    pub fn try_from_pairs(pairs: &[(String, String)]) -> Result<Self, RadError> {
+        let headers = pairs
+            .iter()
+            .map(|(name, value)| {
+                let invalid_header = || RadError::InvalidHttpHeader {
+                    name: name.clone(),
+                    value: value.clone(),
+                };
+                let header_name =
+                    HeaderName::from_bytes(name.as_bytes()).map_err(|_| invalid_header())?;
+                let header_value = HeaderValue::from_str(value).map_err(|_| invalid_header())?;
+
+                Ok((header_name, header_value))
+            })
+            .collect::<Result<_, RadError>>()?;
+
+        Ok(Self { headers })
+    }
+
+    /// Returns true if there are no headers to insert.
+    pub fn is_empty(&self) -> bool {
+        self.headers.is_empty()
+    }
+}
+
+impl<C: HttpClient> Middleware<C> for ExtraHeaders {
+    fn handle<'a>(
+        &'a self,
+        mut req: Request,
+        client: C,
+        next: Next<'a, C>,
+    ) -> BoxFuture<'a, Result<Response, Exception>> {
+        // Inserting replaces any previous value, so extra headers take precedence over the
+        // default ones (e.g. `User-Agent`)
+        for (name, value) in &self.headers {
+            req.headers_mut().insert(name.clone(), value.clone());
+        }
+
+        next.run(req, client)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extra_headers_valid() {
+        let pairs = vec![
+            ("Accept".to_string(), "application/json".to_string()),
+            ("X-Api-Version".to_string(), "2021-05-01".to_string()),
+        ];
+        let headers = ExtraHeaders::try_from_pairs(&pairs).unwrap();
+
+        assert_eq!(headers.headers.len(), 2);
+        assert_eq!(headers.headers[0].0, "accept");
+        assert_eq!(headers.headers[1].1, "2021-05-01");
+    }
+
+    #[test]
+    fn test_extra_headers_invalid() {
+        let pairs = vec![("Bad Name".to_string(), "value".to_string())];
+        assert_eq!(
+            ExtraHeaders::try_from_pairs(&pairs).unwrap_err(),
+            RadError::InvalidHttpHeader {
+                name: "Bad Name".to_string(),
+                value: "value".to_string(),
+            }
+        );
+
+        let pairs = vec![("Accept".to_string(), "a\r\nb".to_string())];
+        assert_eq!(
+            ExtraHeaders::try_from_pairs(&pairs).unwrap_err(),
+            RadError::InvalidHttpHeader {
+                name: "Accept".to_string(),
+                value: "a\r\nb".to_string(),
+            }
+        );
+    }
+}
@@ -0,0 +1,422 @@
+//! Lossless JSON encoding of RADON values.
+//!
+//! The `Serialize` implementation of `RadonTypes` is meant for displaying values, and it cannot
+//! be decoded back: errors are serialized as their message, and floats that are not finite become
+//! `null`. This encoding can be decoded into the exact same value, so services can consume the
+//! results of data requests without decoding their CBOR representation.
+//!
+//! Every value is encoded as an object with a single key, which is the name of its type:
+//!
+//! | Type           | Encoding                                                        |
+//! |----------------|-----------------------------------------------------------------|
+//! | `RadonArray`   | `{"RadonArray": [{"RadonInteger": "1"}, {"RadonBoolean": true}]}` |
+//! | `RadonBoolean` | `{"RadonBoolean": true}`                                        |
+//! | `RadonBytes`   | `{"RadonBytes": "c0ffee"}`, as a hexadecimal string             |
+//! | `RadonFloat`   | `{"RadonFloat": 1.5}`, or `"NaN"`, `"Infinity"` or `"-Infinity"` |
+//! | `RadonInteger` | `{"RadonInteger": "-42"}`, as a decimal string                  |
+//! | `RadonMap`     | `{"RadonMap": {"key": {"RadonString": "value"}}}`               |
+//! | `RadonString`  | `{"RadonString": "value"}`                                      |
+//!
+//! Integers are encoded as strings because they have 128 bits, which most JSON parsers cannot
+//! handle. Errors are encoded as the same code and arguments that are committed into the
+//! blockchain, along with their name and message for convenience, which are ignored when decoding:
+//!
+//! ```json
+//! {"RadonError": {
+//!     "code": 113,
+//!     "kind": "MapKeyNotFound",
+//!     "args": [{"RadonString": "price"}],
+//!     "message": "Failed to get key `price` from RadonMap"
+//! }}
+//! ```
+//!
+//! The arguments of errors use the encoding of the values, plus `null`.
+
+use std::{collections::BTreeMap, convert::TryFrom};
+
+use serde_cbor::Value as CborValue;
+use serde_json::{Map, Number, Value as JsonValue};
+
+use witnet_data_structures::radon_error::RadonError;
+
+use crate::{
+    error::RadError,
+    types::{
+        array::RadonArray, boolean::RadonBoolean, bytes::RadonBytes, float::RadonFloat,
+        integer::RadonInteger, map::RadonMap, string::RadonString, RadonType, RadonTypes,
+    },
+    RADRequestExecutionReport,
+};
+
+const NAN: &str = "NaN";
+const INFINITY: &str = "Infinity";
+const NEG_INFINITY: &str = "-Infinity";
+
+/// Encode a RADON value into JSON.
+///
+/// This only fails for errors that have no error code, which are never the result of a stage of
+/// a data request, because those errors are intercepted as `RadError::UnhandledIntercept`.
+pub fn to_json(value: &RadonTypes) -> Result<JsonValue, RadError> {
+    let encoded = match value {
+        RadonTypes::Array(array) => JsonValue::Array(
+            array
+                .value()
+                .iter()
+                .map(to_json)
+                .collect::<Result<_, _>>()?,
+        ),
+        RadonTypes::Boolean(boolean) => JsonValue::Bool(boolean.value()),
+        RadonTypes::Bytes(bytes) => JsonValue::String(hex::encode(bytes.value())),
+        RadonTypes::Float(float) => float_to_json(float.value()),
+        RadonTypes::Integer(integer) => JsonValue::String(integer.value().to_string()),
+        RadonTypes::Map(map) => JsonValue::Object(
+            map.value()
+                .iter()
+                .map(|(key, value)| Ok((key.clone(), to_json(value)?)))
+                .collect::<Result<_, RadError>>()?,
+        ),
+        RadonTypes::RadonError(error) => error_to_json(error.inner())?,
+        RadonTypes::String(string) => JsonValue::String(string.value()),
+    };
+
+    Ok(tagged(value.radon_type_name(), encoded))
+}
+
+/// Decode a RADON value from the JSON produced by `to_json`.
+pub fn from_json(value: &JsonValue) -> Result<RadonTypes, RadError> {
+    let (type_name, inner) = single_entry(value).ok_or_else(|| {
+        invalid_json(
+            value,
+            "expected an object with the name of the type as its only key",
+        )
+    })?;
+
+    match (type_name, inner) {
+        ("RadonArray", JsonValue::Array(items)) => items
+            .iter()
+            .map(from_json)
+            .collect::<Result<Vec<_>, _>>()
+            .map(|items| RadonArray::from(items).into()),
+        ("RadonBoolean", JsonValue::Bool(boolean)) => Ok(RadonBoolean::from(*boolean).into()),
+        ("RadonBytes", JsonValue::String(bytes)) => hex::decode(bytes)
+            .map(|bytes| RadonBytes::from(bytes).into())
+            .map_err(|e| invalid_json(value, &e.to_string())),
+        ("RadonError", JsonValue::Object(error)) => error_from_json(error).map(Into::into),
+        ("RadonFloat", float) => float_from_json(float)
+            .map(|float| RadonFloat::from(float).into())
+            .ok_or_else(|| invalid_json(value, "expected a number, NaN, Infinity or -Infinity")),
+        ("RadonInteger", JsonValue::String(integer)) => integer
+            .parse::<i128>()
+            .map(|integer| RadonInteger::from(integer).into())
+            .map_err(|e| invalid_json(value, &e.to_string())),
+        ("RadonMap", JsonValue::Object(entries)) => entries
+            .iter()
+            .map(|(key, value)| Ok((key.clone(), from_json(value)?)))
+            .collect::<Result<BTreeMap<_, _>, RadError>>()
+            .map(|entries| RadonMap::from(entries).into()),
+        ("RadonString", JsonValue::String(string)) => Ok(RadonString::from(string.clone()).into()),
+        _ => Err(invalid_json(value, "unexpected type or value")),
+    }
+}
+
+/// Encode the results of the stages of a data request execution report, as an object with the
+/// `retrieve`, `aggregate` and `tally` keys.
+pub fn execution_report_to_json(report: &RADRequestExecutionReport) -> Result<JsonValue, RadError> {
+    let retrieve = report
+        .retrieve
+        .iter()
+        .map(|retrieve| to_json(&retrieve.result))
+        .collect::<Result<_, _>>()?;
+
+    let mut object = Map::new();
+    object.insert("retrieve".to_string(), JsonValue::Array(retrieve));
+    object.insert("aggregate".to_string(), to_json(&report.aggregate.result)?);
+    object.insert("tally".to_string(), to_json(&report.tally.result)?);
+
+    Ok(JsonValue::Object(object))
+}
+
+fn tagged(type_name: &str, value: JsonValue) -> JsonValue {
+    let mut object = Map::new();
+    object.insert(type_name.to_string(), value);
+
+    JsonValue::Object(object)
+}
+
+/// The key and the value of an object with a single entry.
+fn single_entry(value: &JsonValue) -> Option<(&str, &JsonValue)> {
+    match value {
+        JsonValue::Object(object) if object.len() == 1 => object
+            .iter()
+            .next()
+            .map(|(key, value)| (key.as_str(), value)),
+        _ => None,
+    }
+}
+
+fn invalid_json(value: &JsonValue, description: &str) -> RadError {
+    RadError::DecodeJson {
+        value: value.to_string(),
+        description: description.to_string(),
+    }
+}
+
+fn float_to_json(float: f64) -> JsonValue {
+    match Number::from_f64(float) {
+        Some(number) => JsonValue::Number(number),
+        None if float.is_nan() => JsonValue::from(NAN),
+        None if float > 0.0 => JsonValue::from(INFINITY),
+        None => JsonValue::from(NEG_INFINITY),
+    }
+}
+
+fn float_from_json(value: &JsonValue) -> Option<f64> {
+    match value {
+        JsonValue::Number(number) => number.as_f64(),
+        JsonValue::String(string) => match string.as_str() {
+            NAN => Some(f64::NAN),
+            INFINITY => Some(f64::INFINITY),
+            NEG_INFINITY => Some(f64::NEG_INFINITY),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn error_to_json(error: &RadError) -> Result<JsonValue, RadError> {
+    let code = error.try_into_error_code()?;
+    // The first item of the array is the error code
+    let args = error
+        .try_into_cbor_array()?
+        .into_iter()
+        .skip(1)
+        .map(cbor_to_json)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut object = Map::new();
+    object.insert("code".to_string(), JsonValue::from(u8::from(code)));
+    object.insert("kind".to_string(), JsonValue::from(format!("{:?}", code)));
+    object.insert("args".to_string(), JsonValue::Array(args));
+    object.insert("message".to_string(), JsonValue::from(error.to_string()));
+
+    Ok(JsonValue::Object(object))
+}
+
+fn error_from_json(error: &Map<String, JsonValue>) -> Result<RadonError<RadError>, RadError> {
+    let invalid = |description| invalid_json(&JsonValue::Object(error.clone()), description);
+
+    let code = error
+        .get("code")
+        .and_then(JsonValue::as_u64)
+        .ok_or_else(|| invalid("expected an error code"))?;
+    let mut array = vec![CborValue::Integer(i128::from(code))];
+    match error.get("args") {
+        None => {}
+        Some(JsonValue::Array(args)) => {
+            for arg in args {
+                array.push(cbor_from_json(arg)?);
+            }
+        }
+        Some(_) => return Err(invalid("expected the error arguments to be an array")),
+    }
+
+    RadError::try_from_cbor_array(array)
+}
+
+/// Encode an argument of an error, which is like encoding a value, but allowing `null`.
+fn cbor_to_json(value: CborValue) -> Result<JsonValue, RadError> {
+    match value {
+        CborValue::Null => Ok(JsonValue::Null),
+        CborValue::Array(items) => items
+            .into_iter()
+            .map(cbor_to_json)
+            .collect::<Result<_, _>>()
+            .map(|items| tagged(RadonArray::radon_type_name(), JsonValue::Array(items))),
+        CborValue::Map(entries) => entries
+            .into_iter()
+            .map(|(key, value)| match key {
+                CborValue::Text(key) => Ok((key, cbor_to_json(value)?)),
+                key => Err(RadError::EncodeRadonErrorArguments {
+                    error_args: format!("{:?}", key),
+                }),
+            })
+            .collect::<Result<_, _>>()
+            .map(|entries| tagged(RadonMap::radon_type_name(), JsonValue::Object(entries))),
+        value => to_json(&RadonTypes::try_from(value)?),
+    }
+}
+
+/// Decode an argument of an error encoded by `cbor_to_json`.
+fn cbor_from_json(value: &JsonValue) -> Result<CborValue, RadError> {
+    match single_entry(value) {
+        _ if value.is_null() => Ok(CborValue::Null),
+        Some(("RadonArray", JsonValue::Array(items))) => items
+            .iter()
+            .map(cbor_from_json)
+            .collect::<Result<_, _>>()
+            .map(CborValue::Array),
+        Some(("RadonMap", JsonValue::Object(entries))) => entries
+            .iter()
+            .map(|(key, value)| Ok((CborValue::Text(key.clone()), cbor_from_json(value)?)))
+            .collect::<Result<_, RadError>>()
+            .map(CborValue::Map),
+        Some(("RadonError", _)) => Err(invalid_json(value, "errors cannot be error arguments")),
+        _ => CborValue::try_from(from_json(value)?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::operators::RadonOpCodes;
+
+    fn assert_roundtrip(value: RadonTypes, expected: JsonValue) {
+        let encoded = to_json(&value).unwrap();
+        assert_eq!(encoded, expected);
+        // Decode from the serialized JSON, as a client would do
+        let serialized = serde_json::to_string(&encoded).unwrap();
+        let decoded = from_json(&serde_json::from_str(&serialized).unwrap()).unwrap();
+        assert_eq!(decoded, value);
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let mut map = BTreeMap::new();
+        map.insert(
+            "bytes".to_string(),
+            RadonBytes::from(vec![0xc0, 0xff, 0xee]).into(),
+        );
+        map.insert("string".to_string(), RadonString::from("0xc0ffee").into());
+        let value = RadonTypes::from(RadonArray::from(vec![
+            RadonBoolean::from(true).into(),
+            RadonFloat::from(1.0).into(),
+            RadonInteger::from(1).into(),
+            RadonMap::from(map).into(),
+        ]));
+
+        assert_roundtrip(
+            value,
+            json!({"RadonArray": [
+                {"RadonBoolean": true},
+                {"RadonFloat": 1.0},
+                {"RadonInteger": "1"},
+                {"RadonMap": {
+                    "bytes": {"RadonBytes": "c0ffee"},
+                    "string": {"RadonString": "0xc0ffee"},
+                }},
+            ]}),
+        );
+    }
+
+    #[test]
+    fn test_json_numbers_are_lossless() {
+        assert_roundtrip(
+            RadonInteger::from(i128::MIN).into(),
+            json!({"RadonInteger": "-170141183460469231731687303715884105728"}),
+        );
+        assert_roundtrip(
+            RadonFloat::from(0.1 + 0.2).into(),
+            json!({"RadonFloat": 0.30000000000000004}),
+        );
+        assert_roundtrip(
+            RadonFloat::from(f64::INFINITY).into(),
+            json!({"RadonFloat": "Infinity"}),
+        );
+        assert_roundtrip(
+            RadonFloat::from(f64::NEG_INFINITY).into(),
+            json!({"RadonFloat": "-Infinity"}),
+        );
+
+        let nan = from_json(&to_json(&RadonFloat::from(f64::NAN).into()).unwrap()).unwrap();
+        match nan {
+            RadonTypes::Float(float) => assert!(float.value().is_nan()),
+            other => panic!("Unexpected value: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_json_errors() {
+        let error = RadError::MapKeyNotFound {
+            key: "price".to_string(),
+        };
+        assert_roundtrip(
+            RadonError::new(error).into(),
+            json!({"RadonError": {
+                "code": 0x71,
+                "kind": "MapKeyNotFound",
+                "args": [{"RadonString": "price"}],
+                "message": "Failed to get key `price` from RadonMap",
+            }}),
+        );
+
+        // Errors can have `null` arguments
+        let error = RadError::UnsupportedOperator {
+            input_type: "RadonString".to_string(),
+            operator: RadonOpCodes::StringLength.to_string(),
+            args: None,
+        };
+        let encoded = to_json(&RadonError::new(error.clone()).into()).unwrap();
+        assert_eq!(encoded["RadonError"]["args"][2], JsonValue::Null);
+        assert_eq!(from_json(&encoded), Ok(RadonError::new(error).into()));
+
+        // Errors inside arrays, as in the input of the tally. Arrays that contain errors cannot be
+        // encoded into CBOR, so their items are compared one by one.
+        let items: Vec<RadonTypes> = vec![
+            RadonError::new(RadError::HttpStatus { status_code: 404 }).into(),
+            RadonFloat::from(1.5).into(),
+        ];
+        let encoded = to_json(&RadonArray::from(items.clone()).into()).unwrap();
+        assert_eq!(
+            encoded,
+            json!({"RadonArray": [
+                {"RadonError": {
+                    "code": 0x30,
+                    "kind": "HTTPError",
+                    "args": [{"RadonInteger": "404"}],
+                    "message": "HTTP response was an HTTP error code: 404",
+                }},
+                {"RadonFloat": 1.5},
+            ]})
+        );
+        match from_json(&encoded) {
+            Ok(RadonTypes::Array(decoded)) => assert_eq!(decoded.value(), items),
+            decoded => panic!("Unexpected decoded value: {:?}", decoded),
+        }
+
+        // Errors without an error code cannot be encoded
+        let error = RadonError::new(RadError::ParseBool {
+            message: "invalid".to_string(),
+        });
+        assert_eq!(
+            to_json(&error.into()),
+            Err(RadError::EncodeRadonErrorUnknownCode)
+        );
+    }
+
+    #[test]
+    fn test_json_invalid() {
+        for invalid in &[
+            json!("foo"),
+            json!({"RadonString": "foo", "RadonInteger": "1"}),
+            json!({"RadonInteger": 1}),
+            json!({"RadonInteger": "1.5"}),
+            json!({"RadonBytes": "xyz"}),
+            json!({"RadonFloat": "1.5"}),
+            json!({"RadonUnknown": true}),
+            json!({"RadonError": {"args": []}}),
+        ] {
+            assert!(
+                matches!(from_json(invalid), Err(RadError::DecodeJson { .. })),
+                "{} should be invalid",
+                invalid
+            );
+        }
+
+        assert_eq!(
+            from_json(&json!({"RadonError": {"code": 0xAA}})),
+            Err(RadError::DecodeRadonErrorUnknownCode { error_code: 0xAA })
+        );
+    }
+}
@@ -0,0 +1,953 @@
+//! The RADON script language, a human-readable syntax for RADON scripts.
+//!
+//! A script is written as a chain of operator calls separated by dots, where each call is applied
+//! to the result of the previous one:
+//!
+//! ```text
+//! parseJSONMap().getFloat("price").multiply(100).round()
+//! ```
+//!
+//! Operators can be called by their full name in lower camel case (`mapGetFloat`), or by their
+//! name without the type of their input (`getFloat`). The short names are resolved using the type
+//! of the value returned by the previous call, so they can only be used when that type is known.
+//!
+//! The arguments of a call can be:
+//! - Integers (`100`, `-3`, `0xFF`) and floats (`1.5`, `2e-3`).
+//! - Strings in double quotes, with the escape sequences `\"`, `\\`, `\n`, `\r`, `\t` and
+//!   `\u{...}`.
+//! - `true`, `false` and `null`.
+//! - Arrays (`[1, 2]`) and maps (`{"key": "value"}`).
+//! - The codes of filters, reducers, hash functions, encodings and date formats, written as
+//!   `RadonFilters::DeviationStandard`, `RadonReducers::AverageMean`,
+//!   `RadonHashFunctions::SHA2_256`, `RadonBytesEncoding::Hex` or
+//!   `RadonDateTimeFormat::Rfc3339`.
+//! - Subscripts, for operators like `map` or `filter`, written as another chain of calls.
+//!
+//! Line comments start with `//`.
+
+use std::{collections::BTreeMap, convert::TryFrom, iter::Peekable, str::Chars};
+
+use serde_cbor::{self as cbor, Value};
+
+use crate::{
+    error::RadError,
+    filters::RadonFilters,
+    hash_functions::RadonHashFunctions,
+    operators::{
+        bytes::RadonBytesEncoding, string::RadonDateTimeFormat, RadonOpCodes, RadonTypeKind,
+    },
+    reducers::RadonReducers,
+    script::{unpack_radon_script, unpack_subscript, RadonCall},
+};
+
+/// The type of the input of the scripts of the retrieval sources.
+const SCRIPT_INPUT_TYPE: RadonTypeKind = RadonTypeKind::String;
+
+/// Compile the source code of a script for a retrieval source into its CBOR serialization.
+pub fn compile(source: &str) -> Result<Vec<u8>, RadError> {
+    let mut parser = Parser::new(tokenize(source)?);
+    let calls = parser.parse_program()?;
+    let script = compile_calls(&calls, Some(SCRIPT_INPUT_TYPE))?;
+
+    cbor::to_vec(&Value::Array(script)).map_err(|_| RadError::Encode {
+        from: "RADON script",
+        to: "CBOR",
+    })
+}
+
+/// Decompile the CBOR serialization of a script for a retrieval source into source code, with
+/// one call per line.
+///
+/// Compiling the result gives back an equivalent script, but not always the same bytes: calls
+/// with an empty list of arguments and values not encoded in their shortest form are normalized.
+pub fn decompile(script: &[u8]) -> Result<String, RadError> {
+    let calls = unpack_radon_script(script)?;
+
+    Ok(render_calls(&calls, Some(SCRIPT_INPUT_TYPE))?.join("\n    ."))
+}
+
+/// Line and column of a character in the source code, both starting at 1.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Position {
+    line: usize,
+    column: usize,
+}
+
+fn syntax_error(position: Position, message: String) -> RadError {
+    RadError::ScriptSyntax {
+        line: position.line,
+        column: position.column,
+        message,
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Identifier(String),
+    Integer(i128),
+    Float(f64),
+    Text(String),
+    Punctuation(char),
+    PathSeparator,
+    End,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Identifier(name) => format!("`{}`", name),
+            Token::Integer(_) | Token::Float(_) => "a number".to_string(),
+            Token::Text(_) => "a string".to_string(),
+            Token::Punctuation(c) => format!("`{}`", c),
+            Token::PathSeparator => "`::`".to_string(),
+            Token::End => "the end of the script".to_string(),
+        }
+    }
+}
+
+struct Lexer<'a> {
+    chars: Peekable<Chars<'a>>,
+    position: Position,
+}
+
+impl<'a> Lexer<'a> {
+    fn next_char(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.position.line += 1;
+            self.position.column = 1;
+        } else {
+            self.position.column += 1;
+        }
+
+        Some(c)
+    }
+
+    fn next_token(&mut self) -> Result<(Token, Position), RadError> {
+        // Skip whitespace and comments
+        loop {
+            match self.chars.peek() {
+                Some(c) if c.is_whitespace() => {
+                    self.next_char();
+                }
+                Some('/') => {
+                    let position = self.position;
+                    self.next_char();
+                    if self.next_char() != Some('/') {
+                        return Err(syntax_error(
+                            position,
+                            "Unexpected character `/`, comments start with `//`".to_string(),
+                        ));
+                    }
+                    while !matches!(self.chars.peek(), None | Some('\n')) {
+                        self.next_char();
+                    }
+                }
+                _ => break,
+            }
+        }
+
+        let position = self.position;
+        let c = match self.chars.peek() {
+            Some(c) => *c,
+            None => return Ok((Token::End, position)),
+        };
+
+        let token = match c {
+            '(' | ')' | '[' | ']' | '{' | '}' | ',' | '.' => {
+                self.next_char();
+                Token::Punctuation(c)
+            }
+            ':' => {
+                self.next_char();
+                if self.chars.peek() == Some(&':') {
+                    self.next_char();
+                    Token::PathSeparator
+                } else {
+                    Token::Punctuation(':')
+                }
+            }
+            '"' => {
+                self.next_char();
+                Token::Text(self.read_text(position)?)
+            }
+            c if c == '-' || c.is_ascii_digit() => self.read_number(position)?,
+            c if c == '_' || c.is_ascii_alphabetic() => {
+                let mut identifier = String::new();
+                while let Some(&c) = self.chars.peek() {
+                    if c != '_' && !c.is_ascii_alphanumeric() {
+                        break;
+                    }
+                    identifier.push(c);
+                    self.next_char();
+                }
+
+                Token::Identifier(identifier)
+            }
+            c => {
+                return Err(syntax_error(
+                    position,
+                    format!("Unexpected character `{}`", c),
+                ))
+            }
+        };
+
+        Ok((token, position))
+    }
+
+    /// Read a string literal, after the opening quote and up to the closing one.
+    fn read_text(&mut self, start: Position) -> Result<String, RadError> {
+        let mut text = String::new();
+        loop {
+            let position = self.position;
+            match self.next_char() {
+                None => {
+                    return Err(syntax_error(start, "Unterminated string".to_string()));
+                }
+                Some('"') => return Ok(text),
+                Some('\\') => {
+                    let c = match self.next_char() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('u') => self.read_unicode_escape(position)?,
+                        _ => {
+                            return Err(syntax_error(
+                                position,
+                                "Invalid escape sequence".to_string(),
+                            ))
+                        }
+                    };
+                    text.push(c);
+                }
+                Some(c) => text.push(c),
+            }
+        }
+    }
+
+    /// Read the `{...}` part of a `\u{...}` escape sequence.
+    fn read_unicode_escape(&mut self, start: Position) -> Result<char, RadError> {
+        let invalid = || syntax_error(start, "Invalid unicode escape sequence".to_string());
+
+        if self.next_char() != Some('{') {
+            return Err(invalid());
+        }
+        let mut hex = String::new();
+        loop {
+            match self.next_char() {
+                Some('}') => break,
+                Some(c) if c.is_ascii_hexdigit() && hex.len() < 6 => hex.push(c),
+                _ => return Err(invalid()),
+            }
+        }
+
+        u32::from_str_radix(&hex, 16)
+            .ok()
+            .and_then(std::char::from_u32)
+            .ok_or_else(invalid)
+    }
+
+    fn read_number(&mut self, position: Position) -> Result<Token, RadError> {
+        let mut literal = String::new();
+        while let Some(&c) = self.chars.peek() {
+            let exponent_sign = (c == '+' || c == '-')
+                && matches!(literal.chars().last(), Some('e') | Some('E'))
+                && !literal.contains('x');
+            let leading_minus = c == '-' && literal.is_empty();
+            if !(c.is_ascii_alphanumeric()
+                || c == '_'
+                || c == '.'
+                || exponent_sign
+                || leading_minus)
+            {
+                break;
+            }
+            literal.push(c);
+            self.next_char();
+        }
+
+        let invalid = || syntax_error(position, format!("Invalid number `{}`", literal));
+        let digits = literal.replace('_', "");
+        let (negative, unsigned) = match digits.strip_prefix('-') {
+            Some(unsigned) => (true, unsigned),
+            None => (false, digits.as_str()),
+        };
+
+        let integer = if let Some(hex) = unsigned.strip_prefix("0x") {
+            i128::from_str_radix(hex, 16).map_err(|_| invalid())?
+        } else if unsigned.contains(&['.', 'e', 'E'][..]) {
+            return digits
+                .parse::<f64>()
+                .ok()
+                .filter(|float| float.is_finite())
+                .map(Token::Float)
+                .ok_or_else(invalid);
+        } else {
+            unsigned.parse::<i128>().map_err(|_| invalid())?
+        };
+        let integer = if negative { -integer } else { integer };
+
+        // CBOR can only encode integers from -2^64 to 2^64 - 1
+        if !(-(1 << 64)..=(1 << 64) - 1).contains(&integer) {
+            return Err(syntax_error(
+                position,
+                format!("Integer `{}` is out of range", literal),
+            ));
+        }
+
+        Ok(Token::Integer(integer))
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<(Token, Position)>, RadError> {
+    let mut lexer = Lexer {
+        chars: source.chars().peekable(),
+        position: Position { line: 1, column: 1 },
+    };
+
+    let mut tokens = vec![];
+    loop {
+        let (token, position) = lexer.next_token()?;
+        let end = token == Token::End;
+        tokens.push((token, position));
+        if end {
+            return Ok(tokens);
+        }
+    }
+}
+
+/// A call to an operator, as written in the source code.
+#[derive(Debug, PartialEq)]
+struct Call {
+    name: String,
+    position: Position,
+    arguments: Vec<Expression>,
+}
+
+/// An argument of a call, as written in the source code.
+#[derive(Debug, PartialEq)]
+enum Expression {
+    Value(Value),
+    Array(Vec<Expression>),
+    Map(Vec<(Expression, Expression)>),
+    Script(Vec<Call>),
+}
+
+struct Parser {
+    tokens: Vec<(Token, Position)>,
+    index: usize,
+}
+
+impl Parser {
+    fn new(tokens: Vec<(Token, Position)>) -> Self {
+        Self { tokens, index: 0 }
+    }
+
+    fn peek(&self) -> &Token {
+        &self.tokens[self.index].0
+    }
+
+    fn peek_second(&self) -> &Token {
+        self.tokens
+            .get(self.index + 1)
+            .map_or(&Token::End, |(token, _)| token)
+    }
+
+    fn next(&mut self) -> (Token, Position) {
+        let (token, position) = self.tokens[self.index].clone();
+        // The last token is always `End`, which is never consumed
+        if token != Token::End {
+            self.index += 1;
+        }
+
+        (token, position)
+    }
+
+    fn unexpected(&self, expected: &str) -> RadError {
+        let (token, position) = &self.tokens[self.index];
+
+        syntax_error(
+            *position,
+            format!("Expected {}, found {}", expected, token.describe()),
+        )
+    }
+
+    fn expect_punctuation(&mut self, c: char) -> Result<(), RadError> {
+        if self.peek() == &Token::Punctuation(c) {
+            self.next();
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("`{}`", c)))
+        }
+    }
+
+    /// Parse a whole script, which may be empty.
+    fn parse_program(&mut self) -> Result<Vec<Call>, RadError> {
+        if self.peek() == &Token::End {
+            return Ok(vec![]);
+        }
+        let calls = self.parse_chain()?;
+        if self.peek() != &Token::End {
+            return Err(self.unexpected("`.` or the end of the script"));
+        }
+
+        Ok(calls)
+    }
+
+    /// Parse one or more calls separated by dots.
+    fn parse_chain(&mut self) -> Result<Vec<Call>, RadError> {
+        let mut calls = vec![self.parse_call()?];
+        while self.peek() == &Token::Punctuation('.') {
+            self.next();
+            calls.push(self.parse_call()?);
+        }
+
+        Ok(calls)
+    }
+
+    fn parse_call(&mut self) -> Result<Call, RadError> {
+        let name = match self.peek() {
+            Token::Identifier(name) => name.clone(),
+            _ => return Err(self.unexpected("the name of an operator")),
+        };
+        let (_, position) = self.next();
+        self.expect_punctuation('(')?;
+        let arguments = self.parse_list(')')?;
+
+        Ok(Call {
+            name,
+            position,
+            arguments,
+        })
+    }
+
+    /// Parse expressions separated by commas, up to the closing character.
+    fn parse_list(&mut self, close: char) -> Result<Vec<Expression>, RadError> {
+        let mut expressions = vec![];
+        while self.peek() != &Token::Punctuation(close) {
+            if !expressions.is_empty() {
+                self.expect_punctuation(',')?;
+            }
+            expressions.push(self.parse_expression()?);
+        }
+        self.next();
+
+        Ok(expressions)
+    }
+
+    fn parse_expression(&mut self) -> Result<Expression, RadError> {
+        if let Token::Punctuation(c) = self.peek() {
+            if *c != '[' && *c != '{' {
+                return Err(self.unexpected("a value"));
+            }
+        }
+        let second = self.peek_second().clone();
+        let (token, position) = self.next();
+
+        Ok(match token {
+            Token::Integer(integer) => Expression::Value(Value::Integer(integer)),
+            Token::Float(float) => Expression::Value(Value::Float(float)),
+            Token::Text(text) => Expression::Value(Value::Text(text)),
+            Token::Punctuation('[') => Expression::Array(self.parse_list(']')?),
+            Token::Punctuation('{') => {
+                let mut entries = vec![];
+                while self.peek() != &Token::Punctuation('}') {
+                    if !entries.is_empty() {
+                        self.expect_punctuation(',')?;
+                    }
+                    let key = self.parse_expression()?;
+                    self.expect_punctuation(':')?;
+                    entries.push((key, self.parse_expression()?));
+                }
+                self.next();
+
+                Expression::Map(entries)
+            }
+            Token::Identifier(_) if second == Token::Punctuation('(') => {
+                self.index -= 1;
+                Expression::Script(self.parse_chain()?)
+            }
+            Token::Identifier(name) if second == Token::PathSeparator => {
+                self.next();
+                let variant = match self.peek() {
+                    Token::Identifier(variant) => variant.clone(),
+                    _ => return Err(self.unexpected("the name of a constant")),
+                };
+                self.next();
+                let code = constant_code(&name, &variant).ok_or_else(|| {
+                    syntax_error(
+                        position,
+                        format!("Unknown constant `{}::{}`", name, variant),
+                    )
+                })?;
+
+                Expression::Value(Value::Integer(i128::from(code)))
+            }
+            Token::Identifier(name) => match name.as_str() {
+                "true" => Expression::Value(Value::Bool(true)),
+                "false" => Expression::Value(Value::Bool(false)),
+                "null" => Expression::Value(Value::Null),
+                _ => {
+                    return Err(syntax_error(
+                        position,
+                        format!(
+                            "Unexpected identifier `{}`, calls must be followed by `(`",
+                            name
+                        ),
+                    ))
+                }
+            },
+            // Punctuation other than `[` and `{` was already rejected
+            _ => return Err(self.unexpected("a value")),
+        })
+    }
+}
+
+/// The name of a constant with the given code, as written in the source code.
+fn constant_name(enum_name: &str, code: u8) -> Option<String> {
+    match enum_name {
+        "RadonFilters" => RadonFilters::try_from(code)
+            .ok()
+            .map(|x| format!("{:?}", x)),
+        "RadonReducers" => RadonReducers::try_from(code)
+            .ok()
+            .map(|x| format!("{:?}", x)),
+        "RadonHashFunctions" => RadonHashFunctions::try_from(code)
+            .ok()
+            .map(|x| format!("{:?}", x)),
+        "RadonBytesEncoding" => RadonBytesEncoding::try_from(code)
+            .ok()
+            .map(|x| format!("{:?}", x)),
+        "RadonDateTimeFormat" => RadonDateTimeFormat::try_from(code)
+            .ok()
+            .map(|x| format!("{:?}", x)),
+        _ => None,
+    }
+}
+
+fn constant_code(enum_name: &str, variant: &str) -> Option<u8> {
+    (0..=u8::MAX).find(|code| constant_name(enum_name, *code).as_deref() == Some(variant))
+}
+
+/// The enum whose constants are accepted as the argument at `index` of an operator, if any.
+fn constant_enum(op_code: RadonOpCodes, index: usize) -> Option<&'static str> {
+    match (op_code, index) {
+        (RadonOpCodes::ArrayFilter, 0) => Some("RadonFilters"),
+        (RadonOpCodes::ArrayReduce, 0) => Some("RadonReducers"),
+        (RadonOpCodes::BytesHash, 0) => Some("RadonHashFunctions"),
+        (RadonOpCodes::BytesAsString, 0) | (RadonOpCodes::StringAsBytes, 0) => {
+            Some("RadonBytesEncoding")
+        }
+        (RadonOpCodes::StringParseDateTime, 0) => Some("RadonDateTimeFormat"),
+        _ => None,
+    }
+}
+
+/// Whether the argument at `index` of an operator can be a subscript.
+fn takes_subscript(op_code: RadonOpCodes, index: usize) -> bool {
+    match op_code {
+        RadonOpCodes::ArrayFilter
+        | RadonOpCodes::ArrayMap
+        | RadonOpCodes::ArraySome
+        | RadonOpCodes::ArraySort => index == 0,
+        _ => false,
+    }
+}
+
+fn lower_camel_case(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+/// Find the operator called `name` when applied to a value of type `input`, if known.
+fn resolve_operator(name: &str, input: Option<RadonTypeKind>) -> Result<RadonOpCodes, String> {
+    // Full names always refer to the same operator, whatever the input
+    if let Some(op_code) = RadonOpCodes::all().find(|op| lower_camel_case(&op.to_string()) == name)
+    {
+        return Ok(op_code);
+    }
+
+    let candidates: Vec<RadonOpCodes> = RadonOpCodes::all()
+        .filter(|op| lower_camel_case(&op.method_name()) == name)
+        .collect();
+    if candidates.is_empty() {
+        return Err(format!("Unknown operator `{}`", name));
+    }
+
+    match input {
+        Some(kind) => candidates
+            .into_iter()
+            .find(|op| op.input_type().is_none() || op.input_type() == Some(kind))
+            .ok_or_else(|| format!("`{}` is not an operator of {}", name, kind)),
+        None if candidates.len() == 1 => Ok(candidates[0]),
+        None => Err(format!(
+            "The operator `{}` is ambiguous because the type of its input is unknown, use one of {}",
+            name,
+            candidates
+                .iter()
+                .map(|op| format!("`{}`", lower_camel_case(&op.to_string())))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+fn compile_calls(calls: &[Call], mut input: Option<RadonTypeKind>) -> Result<Vec<Value>, RadError> {
+    let mut script = vec![];
+    for call in calls {
+        let op_code = resolve_operator(&call.name, input)
+            .map_err(|message| syntax_error(call.position, message))?;
+        input = op_code.output_type(input);
+
+        let code = Value::Integer(i128::from(op_code as u8));
+        script.push(if call.arguments.is_empty() {
+            code
+        } else {
+            let mut compound_call = vec![code];
+            for argument in &call.arguments {
+                compound_call.push(compile_expression(argument)?);
+            }
+
+            Value::Array(compound_call)
+        });
+    }
+
+    Ok(script)
+}
+
+fn compile_expression(expression: &Expression) -> Result<Value, RadError> {
+    Ok(match expression {
+        Expression::Value(value) => value.clone(),
+        Expression::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(compile_expression)
+                .collect::<Result<_, _>>()?,
+        ),
+        Expression::Map(entries) => {
+            let mut map = BTreeMap::new();
+            for (key, value) in entries {
+                map.insert(compile_expression(key)?, compile_expression(value)?);
+            }
+
+            Value::Map(map)
+        }
+        // The type of the input of subscripts is not known
+        Expression::Script(calls) => Value::Array(compile_calls(calls, None)?),
+    })
+}
+
+fn not_representable(description: String) -> RadError {
+    RadError::ScriptNotRepresentable { description }
+}
+
+fn render_calls(
+    calls: &[RadonCall],
+    mut input: Option<RadonTypeKind>,
+) -> Result<Vec<String>, RadError> {
+    let mut rendered = vec![];
+    for (op_code, arguments) in calls {
+        // Prefer the short name, as long as it is resolved to the same operator
+        let short_name = lower_camel_case(&op_code.method_name());
+        let name = if resolve_operator(&short_name, input) == Ok(*op_code) {
+            short_name
+        } else {
+            lower_camel_case(&op_code.to_string())
+        };
+        input = op_code.output_type(input);
+
+        let arguments = arguments
+            .iter()
+            .flatten()
+            .enumerate()
+            .map(|(index, argument)| render_argument(*op_code, index, argument))
+            .collect::<Result<Vec<_>, _>>()?;
+        rendered.push(format!("{}({})", name, arguments.join(", ")));
+    }
+
+    Ok(rendered)
+}
+
+fn render_argument(
+    op_code: RadonOpCodes,
+    index: usize,
+    argument: &Value,
+) -> Result<String, RadError> {
+    if let (Some(enum_name), Value::Integer(code)) = (constant_enum(op_code, index), argument) {
+        let name = u8::try_from(*code)
+            .ok()
+            .and_then(|code| constant_name(enum_name, code));
+        if let Some(name) = name {
+            return Ok(format!("{}::{}", enum_name, name));
+        }
+    }
+
+    if takes_subscript(op_code, index) {
+        if let Value::Array(items) = argument {
+            // An empty subscript is the same as an empty array
+            if !items.is_empty() {
+                let calls = unpack_subscript(argument)?;
+                return Ok(render_calls(&calls, None)?.join("."));
+            }
+        }
+    }
+
+    render_value(argument)
+}
+
+fn render_value(value: &Value) -> Result<String, RadError> {
+    Ok(match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(boolean) => boolean.to_string(),
+        Value::Integer(integer) => integer.to_string(),
+        // The debug representation always has a decimal point or an exponent
+        Value::Float(float) if float.is_finite() => format!("{:?}", float),
+        Value::Float(float) => {
+            return Err(not_representable(format!(
+                "the float `{}` is not finite",
+                float
+            )))
+        }
+        Value::Text(text) => render_text(text),
+        Value::Array(items) => format!(
+            "[{}]",
+            items
+                .iter()
+                .map(render_value)
+                .collect::<Result<Vec<_>, _>>()?
+                .join(", ")
+        ),
+        Value::Map(entries) => format!(
+            "{{{}}}",
+            entries
+                .iter()
+                .map(|(key, value)| Ok(format!("{}: {}", render_value(key)?, render_value(value)?)))
+                .collect::<Result<Vec<_>, RadError>>()?
+                .join(", ")
+        ),
+        value => {
+            return Err(not_representable(format!(
+                "unsupported value `{:?}`",
+                value
+            )))
+        }
+    })
+}
+
+fn render_text(text: &str) -> String {
+    let mut rendered = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => rendered.push_str("\\\""),
+            '\\' => rendered.push_str("\\\\"),
+            '\n' => rendered.push_str("\\n"),
+            '\r' => rendered.push_str("\\r"),
+            '\t' => rendered.push_str("\\t"),
+            c if c.is_control() => rendered.push_str(&format!("\\u{{{:x}}}", u32::from(c))),
+            c => rendered.push(c),
+        }
+    }
+    rendered.push('"');
+
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn op(op_code: RadonOpCodes) -> Value {
+        Value::Integer(i128::from(op_code as u8))
+    }
+
+    fn compiled_value(source: &str) -> Result<Value, RadError> {
+        compile(source).map(|bytes| cbor::from_slice(&bytes).unwrap())
+    }
+
+    fn error_at(line: usize, column: usize, message: &str) -> RadError {
+        RadError::ScriptSyntax {
+            line,
+            column,
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_compile() {
+        let expected = Value::Array(vec![
+            op(RadonOpCodes::StringParseJSONMap),
+            Value::Array(vec![
+                op(RadonOpCodes::MapGetFloat),
+                Value::Text("price".to_string()),
+            ]),
+            Value::Array(vec![op(RadonOpCodes::FloatMultiply), Value::Integer(100)]),
+            op(RadonOpCodes::FloatRound),
+        ]);
+
+        assert_eq!(
+            compiled_value(r#"parseJSONMap().getFloat("price").multiply(100).round()"#),
+            Ok(expected.clone())
+        );
+        // Full names, whitespace and comments
+        assert_eq!(
+            compiled_value(
+                r#"
+                // Get the price in cents
+                stringParseJSONMap()
+                    .mapGetFloat( "price" )
+                    .floatMultiply(100) // Integer argument
+                    .floatRound()
+                "#
+            ),
+            Ok(expected)
+        );
+        assert_eq!(compiled_value(""), Ok(Value::Array(vec![])));
+    }
+
+    #[test]
+    fn test_compile_arguments() {
+        let compiled = compiled_value(
+            r#"parseJSONArray()
+                .map(mapGetFloat("price"))
+                .filter(RadonFilters::DeviationStandard, 1.5)
+                .reduce(RadonReducers::AverageMean)
+                .identity([1, -0x10, "a\"\u{e9}"], {"key": [true, false, null]})"#,
+        )
+        .unwrap();
+
+        let mut map = BTreeMap::new();
+        map.insert(
+            Value::Text("key".to_string()),
+            Value::Array(vec![Value::Bool(true), Value::Bool(false), Value::Null]),
+        );
+        let expected = Value::Array(vec![
+            op(RadonOpCodes::StringParseJSONArray),
+            Value::Array(vec![
+                op(RadonOpCodes::ArrayMap),
+                Value::Array(vec![Value::Array(vec![
+                    op(RadonOpCodes::MapGetFloat),
+                    Value::Text("price".to_string()),
+                ])]),
+            ]),
+            Value::Array(vec![
+                op(RadonOpCodes::ArrayFilter),
+                Value::Integer(RadonFilters::DeviationStandard as i128),
+                Value::Float(1.5),
+            ]),
+            Value::Array(vec![
+                op(RadonOpCodes::ArrayReduce),
+                Value::Integer(RadonReducers::AverageMean as i128),
+            ]),
+            Value::Array(vec![
+                op(RadonOpCodes::Identity),
+                Value::Array(vec![
+                    Value::Integer(1),
+                    Value::Integer(-16),
+                    Value::Text("a\"\u{e9}".to_string()),
+                ]),
+                Value::Map(map),
+            ]),
+        ]);
+
+        assert_eq!(compiled, expected);
+    }
+
+    #[test]
+    fn test_compile_errors() {
+        assert_eq!(
+            compile("parseJSONMap()\n  .getFloat(\"price\"\n"),
+            Err(error_at(3, 1, "Expected `,`, found the end of the script"))
+        );
+        assert_eq!(
+            compile("parseJSONMap().getFlot(\"price\")"),
+            Err(error_at(1, 16, "Unknown operator `getFlot`"))
+        );
+        assert_eq!(
+            compile("parseJSONMap().round()"),
+            Err(error_at(1, 16, "`round` is not an operator of RadonMap"))
+        );
+        assert_eq!(
+            compile("parseJSONArray().map(getFloat(\"price\"))"),
+            Err(error_at(
+                1,
+                22,
+                "The operator `getFloat` is ambiguous because the type of its input is unknown, \
+                 use one of `arrayGetFloat`, `mapGetFloat`"
+            ))
+        );
+        assert_eq!(
+            compile("parseJSONMap().getFloat(price)"),
+            Err(error_at(
+                1,
+                25,
+                "Unexpected identifier `price`, calls must be followed by `(`"
+            ))
+        );
+        assert_eq!(
+            compile("parseJSONArray().reduce(RadonReducers::Average)"),
+            Err(error_at(1, 25, "Unknown constant `RadonReducers::Average`"))
+        );
+        assert_eq!(
+            compile("asFloat().multiply(1.2.3)"),
+            Err(error_at(1, 20, "Invalid number `1.2.3`"))
+        );
+        assert_eq!(
+            compile("length() length()"),
+            Err(error_at(
+                1,
+                10,
+                "Expected `.` or the end of the script, found `length`"
+            ))
+        );
+        assert_eq!(
+            compile("match(\"a)"),
+            Err(error_at(1, 7, "Unterminated string"))
+        );
+    }
+
+    #[test]
+    fn test_decompile() {
+        let source = "parseJSONArray()\n    \
+                      .map(mapGetFloat(\"price\").multiply(100))\n    \
+                      .filter(RadonFilters::DeviationStandard, 1.5)\n    \
+                      .reduce(RadonReducers::AverageMean)\n    \
+                      .round()";
+
+        let script = compile(source).unwrap();
+        assert_eq!(decompile(&script), Ok(source.to_string()));
+
+        let source = "asBytes(RadonBytesEncoding::Base64)\n    \
+                      .hash(RadonHashFunctions::SHA2_256)\n    \
+                      .asString()\n    \
+                      .match({\"a\": true}, false)\n    \
+                      .identity([1.0, -2, \"\\\"\\u{1}\"], null)";
+        let script = compile(source).unwrap();
+        assert_eq!(decompile(&script), Ok(source.to_string()));
+
+        let source = "parseDateTime(RadonDateTimeFormat::Rfc2822)\n    \
+                      .maxAge(300)";
+        let script = compile(source).unwrap();
+        assert_eq!(decompile(&script), Ok(source.to_string()));
+    }
+
+    #[test]
+    fn test_decompile_not_representable() {
+        let script = cbor::to_vec(&Value::Array(vec![Value::Array(vec![
+            op(RadonOpCodes::StringMatch),
+            Value::Bytes(vec![1, 2]),
+        ])]))
+        .unwrap();
+
+        assert_eq!(
+            decompile(&script),
+            Err(RadError::ScriptNotRepresentable {
+                description: "unsupported value `Bytes([1, 2])`".to_string()
+            })
+        );
+    }
+}
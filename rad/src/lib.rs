@@ -1,31 +1,55 @@
 //! # RAD Engine
 
-use futures::{executor::block_on, future::join_all};
+use std::{
+    future::Future,
+    pin::Pin,
+    time::{Duration, Instant},
+};
+
+use futures::{
+    executor::block_on,
+    future::{join_all, poll_fn, select, Either},
+};
+use futures_io::AsyncRead;
+use futures_timer::Delay;
+use rand::{rngs::OsRng, RngCore};
 use serde::Serialize;
 pub use serde_cbor::to_vec as cbor_to_vec;
 pub use serde_cbor::Value as CborValue;
+use surf::middleware::HttpClient;
 
 use witnet_data_structures::{
     chain::{RADAggregate, RADRequest, RADRetrieve, RADTally, RADType},
+    mainnet_validations::{current_active_wips, ActiveWips},
     radon_report::{RadonReport, ReportContext, RetrievalMetadata, Stage, TallyMetaData},
 };
 
 use crate::{
     error::RadError,
+    headers::ExtraHeaders,
+    proxy::RetrievalProxy,
     script::{
-        create_radon_script_from_filters_and_reducer, execute_radon_script, unpack_radon_script,
-        RadonScriptExecutionSettings,
+        check_radon_script_operators, create_radon_script_from_filters_and_reducer,
+        execute_radon_script, unpack_radon_script, RadonScriptExecutionSettings,
     },
-    types::{array::RadonArray, string::RadonString, RadonTypes},
+    transport::{HttpTransport, RetrievalLimits, RetrievalResponse, RetrievalTransport},
+    types::{array::RadonArray, RadonTypes},
     user_agents::UserAgent,
 };
 
 pub mod error;
 pub mod filters;
 pub mod hash_functions;
+pub mod headers;
+pub mod json;
+pub mod language;
 pub mod operators;
+pub mod proxy;
 pub mod reducers;
 pub mod script;
+pub mod trace;
+pub mod transport;
+pub mod type_check;
 pub mod types;
 pub mod user_agents;
 
@@ -50,9 +74,30 @@ pub fn try_data_request(
     request: &RADRequest,
     settings: RadonScriptExecutionSettings,
     inputs_injection: Option<&[&str]>,
+    active_wips: &ActiveWips,
+) -> RADRequestExecutionReport {
+    try_data_request_with_transport(
+        request,
+        settings,
+        inputs_injection,
+        &HttpTransport::default(),
+        active_wips,
+    )
+}
+
+/// Executes a data request locally, performing the retrievals using the given transport.
+/// See `try_data_request` for the meaning of `inputs_injection`, which takes precedence over the
+/// transport.
+pub fn try_data_request_with_transport(
+    request: &RADRequest,
+    settings: RadonScriptExecutionSettings,
+    inputs_injection: Option<&[&str]>,
+    transport: &dyn RetrievalTransport,
+    active_wips: &ActiveWips,
 ) -> RADRequestExecutionReport {
     let mut retrieval_context =
         ReportContext::from_stage(Stage::Retrieval(RetrievalMetadata::default()));
+    retrieval_context.active_wips = Some(active_wips.clone());
     let retrieve_responses = if let Some(inputs) = inputs_injection {
         assert_eq!(inputs.len(), request.retrieve.len(), "Tried to locally run a data request with a number of injected sources different than the number of retrieval paths ({} != {})", inputs.len(), request.retrieve.len());
 
@@ -69,7 +114,15 @@ pub fn try_data_request(
             request
                 .retrieve
                 .iter()
-                .map(|retrieve| run_retrieval_report(retrieve, settings))
+                .map(|retrieve| {
+                    run_retrieval_with_transport_report(
+                        retrieve,
+                        settings,
+                        transport,
+                        None,
+                        active_wips,
+                    )
+                })
                 .collect::<Vec<_>>(),
         ))
     };
@@ -92,6 +145,7 @@ pub fn try_data_request(
         &request.aggregate,
         &mut aggregation_context,
         settings,
+        active_wips,
     )
     .unwrap_or_else(|error| RadonReport::from_result(Err(error), &aggregation_context));
     let aggregation_value = aggregation_report.result.clone();
@@ -102,6 +156,7 @@ pub fn try_data_request(
         &request.tally,
         &mut tally_context,
         settings,
+        active_wips,
     )
     .unwrap_or_else(|error| RadonReport::from_result(Err(error), &tally_context));
 
@@ -113,20 +168,40 @@ pub fn try_data_request(
 }
 
 /// Run retrieval without performing any external network requests, return `RadonReport`.
+/// The response is passed to the script of binary sources as the bytes of the string.
 pub fn run_retrieval_with_data_report(
     retrieve: &RADRetrieve,
     response: &str,
     context: &mut ReportContext<RadonTypes>,
     settings: RadonScriptExecutionSettings,
 ) -> Result<RadonReport<RadonTypes>> {
-    match retrieve.kind {
-        RADType::HttpGet => {
-            let input = RadonTypes::from(RadonString::from(response));
-            let radon_script = unpack_radon_script(&retrieve.script)?;
+    run_retrieval_with_response_report(
+        retrieve,
+        RetrievalResponse::from(response),
+        context,
+        settings,
+    )
+}
 
-            execute_radon_script(input, &radon_script, context, settings)
-        }
+/// Run retrieval on an already obtained response, return `RadonReport`.
+pub fn run_retrieval_with_response_report(
+    retrieve: &RADRetrieve,
+    response: RetrievalResponse,
+    context: &mut ReportContext<RadonTypes>,
+    settings: RadonScriptExecutionSettings,
+) -> Result<RadonReport<RadonTypes>> {
+    // The random bytes of RNG sources are always passed to the script as they are
+    let binary = match retrieve.kind {
+        RADType::HttpGet | RADType::HttpPost => retrieve.binary,
+        RADType::Rng => true,
+    };
+    let input = response.into_radon_types(binary)?;
+    let radon_script = unpack_radon_script(&retrieve.script)?;
+    if let Some(active_wips) = &context.active_wips {
+        check_radon_script_operators(&radon_script, active_wips)?;
     }
+
+    execute_radon_script(input, &radon_script, context, settings)
 }
 
 /// Run retrieval without performing any external network requests, return `RadonTypes`.
@@ -145,57 +220,265 @@ pub async fn run_retrieval_report(
     retrieve: &RADRetrieve,
     settings: RadonScriptExecutionSettings,
 ) -> Result<RadonReport<RadonTypes>> {
-    let context = &mut ReportContext::from_stage(Stage::Retrieval(RetrievalMetadata::default()));
+    run_retrieval_with_transport_report(
+        retrieve,
+        settings,
+        &HttpTransport::default(),
+        None,
+        &current_active_wips(),
+    )
+    .await
+}
+
+/// Run retrieval stage of a data request using the given transport, return `RadonReport`.
+///
+/// `timestamp` is the epoch timestamp of the data request, against which the age of timestamps
+/// is computed in the script. If `None`, the current time is used.
+pub async fn run_retrieval_with_transport_report(
+    retrieve: &RADRetrieve,
+    settings: RadonScriptExecutionSettings,
+    transport: &dyn RetrievalTransport,
+    timestamp: Option<i64>,
+    active_wips: &ActiveWips,
+) -> Result<RadonReport<RadonTypes>> {
+    let context = &mut ReportContext::from_stage(Stage::Retrieval(RetrievalMetadata {
+        timestamp,
+        ..RetrievalMetadata::default()
+    }));
+    context.active_wips = Some(active_wips.clone());
+
+    let response = retrieval_response(retrieve, transport).await?;
+
+    let result = run_retrieval_with_response_report(retrieve, response, context, settings);
+
+    match &result {
+        Ok(report) => {
+            log::debug!(
+                "Successful result for source {}: {:?}",
+                retrieve.url,
+                report.result
+            );
+        }
+        Err(e) => log::debug!("Failed result for source {}: {:?}", retrieve.url, e),
+    }
 
+    result
+}
+
+/// Obtain the response of a retrieval source. HTTP sources are requested using the given
+/// transport, while RNG sources get random bytes generated locally.
+pub async fn retrieval_response(
+    retrieve: &RADRetrieve,
+    transport: &dyn RetrievalTransport,
+) -> Result<RetrievalResponse> {
     match retrieve.kind {
-        RADType::HttpGet => {
-            // Validate URL because surf::get panics on invalid URL
-            // It could still panic if surf gets updated and changes their URL parsing library
-            let _valid_url =
-                url::Url::parse(&retrieve.url).map_err(|err| RadError::UrlParseError {
-                    inner: err,
-                    url: retrieve.url.clone(),
-                })?;
-
-            // Set a random user-agent from the list
-            let mut response = surf::get(&retrieve.url)
-                .set_header("User-Agent", UserAgent::random())
-                .await
-                .map_err(|x| RadError::HttpOther {
-                    message: x.to_string(),
-                })?;
-
-            if !response.status().is_success() {
-                return Err(RadError::HttpStatus {
-                    status_code: response.status().into(),
-                });
+        RADType::HttpGet | RADType::HttpPost => transport.retrieve(retrieve).await,
+        RADType::Rng => Ok(rng_response()),
+    }
+}
+
+/// Number of random bytes returned by RNG sources.
+pub const RNG_RESPONSE_SIZE: usize = 32;
+
+/// Generate the response of an RNG source using the random number generator of the operating
+/// system.
+fn rng_response() -> RetrievalResponse {
+    let mut body = vec![0; RNG_RESPONSE_SIZE];
+    OsRng.fill_bytes(&mut body);
+
+    RetrievalResponse {
+        body,
+        content_type: None,
+    }
+}
+
+/// Perform the HTTP request described by a retrieval source, and return the body of the response.
+/// Transient errors of HTTP-GET requests are retried as many times and for as long as allowed by
+/// the limits.
+pub(crate) async fn http_response(
+    retrieve: &RADRetrieve,
+    limits: &RetrievalLimits,
+    proxy: Option<&RetrievalProxy>,
+) -> Result<RetrievalResponse> {
+    let start = Instant::now();
+    let mut backoff = limits.retry_backoff;
+    let mut attempts = 0;
+    let can_retry = |attempts: u32, backoff: Duration| {
+        retrieve.kind == RADType::HttpGet
+            && attempts <= limits.max_retries
+            && limits
+                .max_retry_duration
+                .map_or(true, |max| start.elapsed() + backoff < max)
+    };
+
+    loop {
+        attempts += 1;
+        match http_response_attempt(retrieve, limits, proxy).await {
+            Err(e) if is_transient_error(&e) && can_retry(attempts, backoff) => {
+                log::debug!(
+                    "Retrying request to {} in {:?} after error: {}",
+                    retrieve.url,
+                    backoff,
+                    e
+                );
+                Delay::new(backoff).await;
+                backoff = backoff.checked_mul(2).unwrap_or(backoff);
             }
+            Err(e) if is_transient_error(&e) && attempts > 1 => {
+                return Err(RadError::HttpRetriesExhausted {
+                    attempts,
+                    last_error: Box::new(e),
+                })
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Whether a request that failed with this error could succeed if it is retried.
+fn is_transient_error(error: &RadError) -> bool {
+    match error {
+        RadError::HttpStatus { status_code } => *status_code == 429 || *status_code >= 500,
+        RadError::HttpOther { .. }
+        | RadError::HttpConnectTimeout { .. }
+        | RadError::HttpReadTimeout { .. } => true,
+        _ => false,
+    }
+}
+
+/// Await a future, failing with the given error if it does not complete before the timeout.
+async fn with_timeout<T>(
+    future: impl Future<Output = T>,
+    timeout: Option<Duration>,
+    timeout_error: impl FnOnce(u128) -> RadError,
+) -> Result<T> {
+    match timeout {
+        None => Ok(future.await),
+        Some(timeout) => match select(Box::pin(future), Delay::new(timeout)).await {
+            Either::Left((output, _)) => Ok(output),
+            Either::Right(_) => Err(timeout_error(timeout.as_millis())),
+        },
+    }
+}
 
-            let response_string = response
-                // TODO: replace with .body_bytes() and let RADON handle the encoding?
-                .body_string()
-                .await
-                .map_err(|x| RadError::HttpOther {
-                    message: x.to_string(),
-                })?;
-
-            let result =
-                run_retrieval_with_data_report(retrieve, &response_string, context, settings);
-
-            match &result {
-                Ok(report) => {
-                    log::debug!(
-                        "Successful result for source {}: {:?}",
-                        retrieve.url,
-                        report.result
-                    );
-                }
-                Err(e) => log::debug!("Failed result for source {}: {:?}", retrieve.url, e),
+/// Read the body of a response, failing as soon as it exceeds the maximum size.
+async fn read_body(response: &mut surf::Response, max_size: Option<usize>) -> Result<Vec<u8>> {
+    let mut body = Vec::new();
+    let mut chunk = [0; 8192];
+
+    loop {
+        // surf responses implement the `AsyncRead` trait from `futures-preview`
+        let read = poll_fn(|cx| Pin::new(&mut *response).poll_read(cx, &mut chunk))
+            .await
+            .map_err(|x| RadError::HttpOther {
+                message: x.to_string(),
+            })?;
+        if read == 0 {
+            return Ok(body);
+        }
+        if let Some(max_size) = max_size {
+            if body.len() + read > max_size {
+                return Err(RadError::HttpResponseTooLarge { max_size });
             }
+        }
+        body.extend_from_slice(&chunk[..read]);
+    }
+}
+
+/// Perform the HTTP request described by a retrieval source once.
+async fn http_response_attempt(
+    retrieve: &RADRetrieve,
+    limits: &RetrievalLimits,
+    proxy: Option<&RetrievalProxy>,
+) -> Result<RetrievalResponse> {
+    let method = match retrieve.kind {
+        RADType::HttpGet => surf::http::Method::GET,
+        RADType::HttpPost => surf::http::Method::POST,
+        RADType::Rng => {
+            return Err(RadError::NotHttpRetrieval {
+                kind: retrieve.kind.clone(),
+            })
+        }
+    };
+
+    // Validate URL because surf::get panics on invalid URL
+    // It could still panic if surf gets updated and changes their URL parsing library
+    let url = url::Url::parse(&retrieve.url).map_err(|err| RadError::UrlParseError {
+        inner: err,
+        url: retrieve.url.clone(),
+    })?;
+
+    match proxy.filter(|proxy| !proxy.bypasses(&url)) {
+        Some(proxy) => {
+            let request = surf::Request::with_client(method, url, proxy.client());
+            send_http_request(request, retrieve, limits).await
+        }
+        None => send_http_request(surf::Request::new(method, url), retrieve, limits).await,
+    }
+}
+
+/// Send a request built for a retrieval source, using any `surf` client.
+async fn send_http_request<C: HttpClient>(
+    request: surf::Request<C>,
+    retrieve: &RADRetrieve,
+    limits: &RetrievalLimits,
+) -> Result<RetrievalResponse> {
+    // Validate the extra headers because surf panics on invalid header names and values
+    let extra_headers = ExtraHeaders::try_from_pairs(&retrieve.headers)?;
+
+    let request = match retrieve.kind {
+        RADType::HttpGet | RADType::Rng => request,
+        RADType::HttpPost => {
+            let request = request.body_bytes(&retrieve.body);
+            if retrieve.content_type.is_empty() {
+                // Keep the default `application/octet-stream` content type
+                request
+            } else {
+                // Validate the content type because surf panics on invalid header values
+                surf::http::header::HeaderValue::from_str(&retrieve.content_type).map_err(
+                    |_| RadError::InvalidHttpHeader {
+                        name: "Content-Type".to_string(),
+                        value: retrieve.content_type.clone(),
+                    },
+                )?;
 
-            result
+                request.set_header("Content-Type", &retrieve.content_type)
+            }
         }
+    };
+
+    // Set a random user-agent from the list
+    let request = request.set_header("User-Agent", UserAgent::random());
+    let request = if extra_headers.is_empty() {
+        request
+    } else {
+        request.middleware(extra_headers)
+    };
+
+    let mut response = with_timeout(request, limits.connect_timeout, |timeout_ms| {
+        RadError::HttpConnectTimeout { timeout_ms }
+    })
+    .await?
+    .map_err(|x| RadError::HttpOther {
+        message: x.to_string(),
+    })?;
+
+    if !response.status().is_success() {
+        return Err(RadError::HttpStatus {
+            status_code: response.status().into(),
+        });
     }
+
+    let content_type = response.header("Content-Type").map(str::to_string);
+    let body = with_timeout(
+        read_body(&mut response, limits.max_response_size),
+        limits.read_timeout,
+        |timeout_ms| RadError::HttpReadTimeout { timeout_ms },
+    )
+    .await??;
+
+    // The body is decoded later on, depending on whether the source is binary
+    Ok(RetrievalResponse { body, content_type })
 }
 
 /// Run retrieval stage of a data request, return `RadonTypes`.
@@ -206,15 +489,36 @@ pub async fn run_retrieval(retrieve: &RADRetrieve) -> Result<RadonTypes> {
         .map(RadonReport::into_inner)
 }
 
+/// Run retrieval stage of a data request using the given transport, return `RadonTypes`.
+/// See `run_retrieval_with_transport_report` for the meaning of `timestamp`.
+pub async fn run_retrieval_with_transport(
+    retrieve: &RADRetrieve,
+    transport: &dyn RetrievalTransport,
+    timestamp: Option<i64>,
+    active_wips: &ActiveWips,
+) -> Result<RadonTypes> {
+    // Disable all execution tracing features, as this is the best-effort version of this method
+    run_retrieval_with_transport_report(
+        retrieve,
+        RadonScriptExecutionSettings::disable_all(),
+        transport,
+        timestamp,
+        active_wips,
+    )
+    .await
+    .map(RadonReport::into_inner)
+}
+
 /// Run aggregate stage of a data request, return `RadonReport`.
 pub fn run_aggregation_report(
     radon_types_vec: Vec<RadonTypes>,
     aggregate: &RADAggregate,
     settings: RadonScriptExecutionSettings,
+    active_wips: &ActiveWips,
 ) -> Result<RadonReport<RadonTypes>> {
     let context = &mut ReportContext::from_stage(Stage::Aggregation);
 
-    run_aggregation_with_context_report(radon_types_vec, aggregate, context, settings)
+    run_aggregation_with_context_report(radon_types_vec, aggregate, context, settings, active_wips)
 }
 
 /// Run aggregate stage of a data request on a custom context, return `RadonReport`.
@@ -223,10 +527,12 @@ pub fn run_aggregation_with_context_report(
     aggregate: &RADAggregate,
     context: &mut ReportContext<RadonTypes>,
     settings: RadonScriptExecutionSettings,
+    active_wips: &ActiveWips,
 ) -> Result<RadonReport<RadonTypes>> {
     let filters = aggregate.filters.as_slice();
     let reducer = aggregate.reducer;
-    let radon_script = create_radon_script_from_filters_and_reducer(filters, reducer)?;
+    let radon_script = create_radon_script_from_filters_and_reducer(filters, reducer, active_wips)?;
+    context.active_wips = Some(active_wips.clone());
 
     let items_to_aggregate = RadonTypes::from(RadonArray::from(radon_types_vec));
 
@@ -237,12 +543,14 @@ pub fn run_aggregation_with_context_report(
 pub fn run_aggregation(
     radon_types_vec: Vec<RadonTypes>,
     aggregate: &RADAggregate,
+    active_wips: &ActiveWips,
 ) -> Result<RadonTypes> {
     // Disable all execution tracing features, as this is the best-effort version of this method
     run_aggregation_report(
         radon_types_vec,
         aggregate,
         RadonScriptExecutionSettings::disable_all(),
+        active_wips,
     )
     .map(RadonReport::into_inner)
 }
@@ -254,6 +562,7 @@ pub fn run_tally_report(
     liars: Option<Vec<bool>>,
     errors: Option<Vec<bool>>,
     settings: RadonScriptExecutionSettings,
+    active_wips: &ActiveWips,
 ) -> Result<RadonReport<RadonTypes>> {
     let mut metadata = TallyMetaData::default();
     if let Some(liars) = liars {
@@ -271,7 +580,13 @@ pub fn run_tally_report(
         ..Default::default()
     };
 
-    run_tally_with_context_report(radon_types_vec, consensus, &mut context, settings)
+    run_tally_with_context_report(
+        radon_types_vec,
+        consensus,
+        &mut context,
+        settings,
+        active_wips,
+    )
 }
 
 /// Run tally stage of a data request on a custom context, return `RadonReport`.
@@ -280,10 +595,12 @@ pub fn run_tally_with_context_report(
     consensus: &RADTally,
     context: &mut ReportContext<RadonTypes>,
     settings: RadonScriptExecutionSettings,
+    active_wips: &ActiveWips,
 ) -> Result<RadonReport<RadonTypes>> {
     let filters = consensus.filters.as_slice();
     let reducer = consensus.reducer;
-    let radon_script = create_radon_script_from_filters_and_reducer(filters, reducer)?;
+    let radon_script = create_radon_script_from_filters_and_reducer(filters, reducer, active_wips)?;
+    context.active_wips = Some(active_wips.clone());
 
     if radon_types_vec.is_empty() {
         return Ok(RadonReport::from_result(Err(RadError::NoReveals), context));
@@ -295,10 +612,22 @@ pub fn run_tally_with_context_report(
 }
 
 /// Run tally stage of a data request, return `RadonTypes`.
-pub fn run_tally(radon_types_vec: Vec<RadonTypes>, consensus: &RADTally) -> Result<RadonTypes> {
+pub fn run_tally(
+    radon_types_vec: Vec<RadonTypes>,
+    consensus: &RADTally,
+    active_wips: &ActiveWips,
+) -> Result<RadonTypes> {
     // Disable all execution tracing features, as this is the best-effort version of this method
     let settings = RadonScriptExecutionSettings::disable_all();
-    run_tally_report(radon_types_vec, consensus, None, None, settings).map(RadonReport::into_inner)
+    run_tally_report(
+        radon_types_vec,
+        consensus,
+        None,
+        None,
+        settings,
+        active_wips,
+    )
+    .map(RadonReport::into_inner)
 }
 
 #[cfg(test)]
@@ -308,19 +637,32 @@ mod tests {
     use serde_cbor::Value;
 
     use witnet_data_structures::{
-        chain::RADFilter,
+        chain::{Environment, RADFilter},
+        mainnet_validations::TapiEngine,
         radon_error::{RadonError, RadonErrors},
     };
 
     use crate::{
         filters::RadonFilters,
+        hash_functions::RadonHashFunctions,
         operators::RadonOpCodes,
         reducers::RadonReducers,
-        types::{float::RadonFloat, integer::RadonInteger},
+        types::{bytes::RadonBytes, float::RadonFloat, integer::RadonInteger},
     };
 
     use super::*;
 
+    // This should only be used in tests
+    pub fn all_wips_active() -> ActiveWips {
+        let mut tapi_engine = TapiEngine::default();
+        tapi_engine.initialize_wip_information(Environment::Testnet);
+
+        ActiveWips {
+            active_wips: tapi_engine.wip_activation,
+            block_epoch: u32::MAX,
+        }
+    }
+
     #[test]
     fn test_run_retrieval() {
         let script_r = Value::Array(vec![
@@ -340,6 +682,10 @@ mod tests {
             kind: RADType::HttpGet,
             url: "https://openweathermap.org/data/2.5/weather?id=2950159&appid=b6907d289e10d714a6e88b30761fae22".to_string(),
             script: packed_script_r,
+            body: vec![],
+            content_type: String::new(),
+            headers: vec![],
+            binary: false,
         };
         let response = r#"{"coord":{"lon":13.41,"lat":52.52},"weather":[{"id":500,"main":"Rain","description":"light rain","icon":"10d"}],"base":"stations","main":{"temp":17.59,"pressure":1022,"humidity":67,"temp_min":15,"temp_max":20},"visibility":10000,"wind":{"speed":3.6,"deg":260},"rain":{"1h":0.51},"clouds":{"all":20},"dt":1567501321,"sys":{"type":1,"id":1275,"message":0.0089,"country":"DE","sunrise":1567484402,"sunset":1567533129},"timezone":7200,"id":2950159,"name":"Berlin","cod":200}"#;
 
@@ -356,6 +702,145 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_run_retrieval_http_post() {
+        let script_r = Value::Array(vec![
+            Value::Integer(RadonOpCodes::StringParseJSONMap as i128),
+            Value::Array(vec![
+                Value::Integer(RadonOpCodes::MapGetInteger as i128),
+                Value::Text("result".to_string()),
+            ]),
+        ]);
+        let packed_script_r = serde_cbor::to_vec(&script_r).unwrap();
+
+        let retrieve = RADRetrieve {
+            kind: RADType::HttpPost,
+            url: "https://blockchain.example/rpc".to_string(),
+            script: packed_script_r,
+            body: br#"{"jsonrpc":"2.0","method":"getblockcount","params":[],"id":1}"#.to_vec(),
+            content_type: "application/json".to_string(),
+            headers: vec![],
+            binary: false,
+        };
+        let response = r#"{"jsonrpc":"2.0","result":692000,"id":1}"#;
+
+        let result = run_retrieval_with_data(
+            &retrieve,
+            response,
+            RadonScriptExecutionSettings::disable_all(),
+        )
+        .unwrap();
+
+        assert_eq!(result, RadonTypes::Integer(RadonInteger::from(692_000)));
+    }
+
+    #[test]
+    fn test_run_retrieval_binary() {
+        use sha2::{Digest, Sha256};
+
+        let script = Value::Array(vec![Value::Array(vec![
+            Value::Integer(RadonOpCodes::BytesHash as i128),
+            Value::Integer(RadonHashFunctions::SHA2_256 as i128),
+        ])]);
+        let retrieve = RADRetrieve {
+            kind: RADType::HttpGet,
+            url: "https://images.example/logo.png".to_string(),
+            script: serde_cbor::to_vec(&script).unwrap(),
+            binary: true,
+            ..RADRetrieve::default()
+        };
+        // Not valid UTF-8, so this could not be retrieved as a string
+        let body = vec![0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0xff];
+        let response = RetrievalResponse {
+            body: body.clone(),
+            content_type: Some("image/png".to_string()),
+        };
+
+        let report = run_retrieval_with_response_report(
+            &retrieve,
+            response.clone(),
+            &mut ReportContext::default(),
+            RadonScriptExecutionSettings::disable_all(),
+        )
+        .unwrap();
+        assert_eq!(
+            report.result,
+            RadonTypes::from(RadonBytes::from(Sha256::digest(&body).to_vec()))
+        );
+
+        let retrieve = RADRetrieve {
+            binary: false,
+            ..retrieve
+        };
+        assert!(run_retrieval_with_response_report(
+            &retrieve,
+            response,
+            &mut ReportContext::default(),
+            RadonScriptExecutionSettings::disable_all(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_run_retrieval_rng() {
+        use crate::types::RadonType;
+
+        let retrieve = RADRetrieve {
+            kind: RADType::Rng,
+            script: vec![128],
+            ..RADRetrieve::default()
+        };
+        let run = || {
+            block_on(run_retrieval_with_transport(
+                &retrieve,
+                &HttpTransport::default(),
+                None,
+                &all_wips_active(),
+            ))
+        };
+
+        let first = run().unwrap();
+        match &first {
+            RadonTypes::Bytes(bytes) => assert_eq!(bytes.value().len(), RNG_RESPONSE_SIZE),
+            other => panic!("Unexpected RNG result: {:?}", other),
+        }
+        // Every retrieval generates new random bytes
+        assert_ne!(run().unwrap(), first);
+
+        // RNG sources cannot be requested over HTTP
+        assert_eq!(
+            block_on(HttpTransport::default().retrieve(&retrieve)),
+            Err(RadError::NotHttpRetrieval { kind: RADType::Rng })
+        );
+    }
+
+    #[test]
+    fn test_run_tally_rng() {
+        let reveals = vec![
+            RadonTypes::from(RadonBytes::from(vec![0x01; RNG_RESPONSE_SIZE])),
+            RadonTypes::from(RadonBytes::from(vec![0x02; RNG_RESPONSE_SIZE])),
+        ];
+        let tally = RADTally {
+            filters: vec![],
+            reducer: RadonReducers::HashConcatenate as u32,
+        };
+
+        let output = run_tally(reveals.clone(), &tally, &all_wips_active()).unwrap();
+        let mut concatenated = vec![0x01; RNG_RESPONSE_SIZE];
+        concatenated.extend(vec![0x02; RNG_RESPONSE_SIZE]);
+        let expected = RadonTypes::from(RadonBytes::from(
+            witnet_crypto::hash::calculate_sha256(&concatenated)
+                .as_ref()
+                .to_vec(),
+        ));
+        assert_eq!(output, expected);
+        // The result is the same for every node running the tally
+        assert_eq!(
+            run_tally(reveals, &tally, &all_wips_active()).unwrap(),
+            expected
+        );
+    }
+
     #[test]
     fn test_run_consensus_and_aggregation() {
         let f_1 = RadonTypes::Float(RadonFloat::from(1f64));
@@ -371,6 +856,7 @@ mod tests {
                 filters: vec![],
                 reducer: RadonReducers::AverageMean as u32,
             },
+            &all_wips_active(),
         )
         .unwrap();
         let output_tally = run_tally(
@@ -379,6 +865,7 @@ mod tests {
                 filters: vec![],
                 reducer: RadonReducers::AverageMean as u32,
             },
+            &all_wips_active(),
         )
         .unwrap();
 
@@ -394,6 +881,10 @@ mod tests {
             kind: RADType::HttpGet,
             url: "https://wrapapi.com/use/aesedepece/ffzz/prima/0.0.3?wrapAPIKey=ql4DVWylABdXCpt1NUTLNEDwPH57aHGm".to_string(),
             script: packed_script_r,
+            body: vec![],
+            content_type: String::new(),
+            headers: vec![],
+            binary: false,
         };
         let response = "84";
         let expected = RadonTypes::Float(RadonFloat::from(84));
@@ -414,8 +905,8 @@ mod tests {
             RadonScriptExecutionSettings::disable_all(),
         )
         .unwrap();
-        let aggregated = run_aggregation(vec![retrieved], &aggregate).unwrap();
-        let tallied = run_tally(vec![aggregated], &tally).unwrap();
+        let aggregated = run_aggregation(vec![retrieved], &aggregate, &all_wips_active()).unwrap();
+        let tallied = run_tally(vec![aggregated], &tally, &all_wips_active()).unwrap();
 
         assert_eq!(tallied, expected);
     }
@@ -428,6 +919,10 @@ mod tests {
             kind: RADType::HttpGet,
             url: "https://wrapapi.com/use/aesedepece/ffzz/murders/0.0.2?wrapAPIKey=ql4DVWylABdXCpt1NUTLNEDwPH57aHGm".to_string(),
             script: packed_script_r,
+            body: vec![],
+            content_type: String::new(),
+            headers: vec![],
+            binary: false,
         };
         let response = "307";
         let expected = RadonTypes::Float(RadonFloat::from(307));
@@ -447,8 +942,8 @@ mod tests {
             RadonScriptExecutionSettings::disable_all(),
         )
         .unwrap();
-        let aggregated = run_aggregation(vec![retrieved], &aggregate).unwrap();
-        let tallied = run_tally(vec![aggregated], &tally).unwrap();
+        let aggregated = run_aggregation(vec![retrieved], &aggregate, &all_wips_active()).unwrap();
+        let tallied = run_tally(vec![aggregated], &tally, &all_wips_active()).unwrap();
 
         assert_eq!(tallied, expected);
     }
@@ -476,6 +971,10 @@ mod tests {
             kind: RADType::HttpGet,
             url: "http://airemadrid.herokuapp.com/api/estacion".to_string(),
             script: packed_script_r,
+            body: vec![],
+            content_type: String::new(),
+            headers: vec![],
+            binary: false,
         };
         // This response was modified because the original was about 100KB.
         let response = r#"[{"estacion_nombre":"Pza. de España","estacion_numero":4,"fecha":"03092019","hora0":{"estado":"Pasado","valor":"00008"}}]"#;
@@ -496,8 +995,8 @@ mod tests {
             RadonScriptExecutionSettings::disable_all(),
         )
         .unwrap();
-        let aggregated = run_aggregation(vec![retrieved], &aggregate).unwrap();
-        let tallied = run_tally(vec![aggregated], &tally).unwrap();
+        let aggregated = run_aggregation(vec![retrieved], &aggregate, &all_wips_active()).unwrap();
+        let tallied = run_tally(vec![aggregated], &tally, &all_wips_active()).unwrap();
 
         assert_eq!(tallied, expected);
     }
@@ -517,6 +1016,10 @@ mod tests {
             kind: RADType::HttpGet,
             url: "https://wrapapi.com/use/aesedepece/ffzz/generales/0.0.3?wrapAPIKey=ql4DVWylABdXCpt1NUTLNEDwPH57aHGm".to_string(),
             script: packed_script_r,
+            body: vec![],
+            content_type: String::new(),
+            headers: vec![],
+            binary: false,
         };
         let response = r#"{"PSOE":123,"PP":66,"Cs":57,"UP":42,"VOX":24,"ERC-SOBIRANISTES":15,"JxCAT-JUNTS":7,"PNV":6,"EH Bildu":4,"CCa-PNC":2,"NA+":2,"COMPROMÍS 2019":1,"PRC":1,"PACMA":0,"FRONT REPUBLICÀ":0,"BNG":0,"RECORTES CERO-GV":0,"NCa":0,"PACT":0,"ARA-MES-ESQUERRA":0,"GBAI":0,"PUM+J":0,"EN MAREA":0,"PCTE":0,"EL PI":0,"AxSI":0,"PCOE":0,"PCPE":0,"AVANT ADELANTE LOS VERDES":0,"EB":0,"CpM":0,"SOMOS REGIÓN":0,"PCPA":0,"PH":0,"UIG-SOM-CUIDES":0,"ERPV":0,"IZQP":0,"PCPC":0,"AHORA CANARIAS":0,"CxG":0,"PPSO":0,"CNV":0,"PREPAL":0,"C.Ex-C.R.Ex-P.R.Ex":0,"PR+":0,"P-LIB":0,"CILU-LINARES":0,"ANDECHA ASTUR":0,"JF":0,"PYLN":0,"FIA":0,"FE de las JONS":0,"SOLIDARIA":0,"F8":0,"DPL":0,"UNIÓN REGIONALISTA":0,"centrados":0,"DP":0,"VOU":0,"PDSJE-UDEC":0,"IZAR":0,"RISA":0,"C 21":0,"+MAS+":0,"UDT":0}"#;
         let expected = RadonTypes::Float(RadonFloat::from(123));
@@ -536,8 +1039,8 @@ mod tests {
             RadonScriptExecutionSettings::disable_all(),
         )
         .unwrap();
-        let aggregated = run_aggregation(vec![retrieved], &aggregate).unwrap();
-        let tallied = run_tally(vec![aggregated], &tally).unwrap();
+        let aggregated = run_aggregation(vec![retrieved], &aggregate, &all_wips_active()).unwrap();
+        let tallied = run_tally(vec![aggregated], &tally, &all_wips_active()).unwrap();
 
         assert_eq!(tallied, expected);
     }
@@ -568,6 +1071,10 @@ mod tests {
             kind: RADType::HttpGet,
             url: "https://www.sofascore.com/event/8397714/json".to_string(),
             script: packed_script_r,
+            body: vec![],
+            content_type: String::new(),
+            headers: vec![],
+            binary: false,
         };
         let response = r#"{"event":{"homeTeam":{"name":"Ryazan-VDV","slug":"ryazan-vdv","gender":"F","national":false,"id":171120,"shortName":"Ryazan-VDV","subTeams":[]},"awayTeam":{"name":"Olympique Lyonnais","slug":"olympique-lyonnais","gender":"F","national":false,"id":26245,"shortName":"Lyon","subTeams":[]},"homeScore":{"current":0,"display":0,"period1":0,"normaltime":0},"awayScore":{"current":9,"display":9,"period1":5,"normaltime":9}}}"#;
         let retrieved = run_retrieval_with_data(
@@ -595,6 +1102,7 @@ mod tests {
             None,
             None,
             RadonScriptExecutionSettings::disable_all(),
+            &all_wips_active(),
         )
         .unwrap();
 
@@ -627,6 +1135,7 @@ mod tests {
             None,
             None,
             RadonScriptExecutionSettings::disable_all(),
+            &all_wips_active(),
         )
         .unwrap();
 
@@ -660,6 +1169,7 @@ mod tests {
             None,
             None,
             RadonScriptExecutionSettings::disable_all(),
+            &all_wips_active(),
         )
         .unwrap();
 
@@ -694,6 +1204,7 @@ mod tests {
             None,
             None,
             RadonScriptExecutionSettings::disable_all(),
+            &all_wips_active(),
         )
         .unwrap();
 
@@ -740,6 +1251,7 @@ mod tests {
             None,
             None,
             RadonScriptExecutionSettings::disable_all(),
+            &all_wips_active(),
         )
         .unwrap();
 
@@ -775,6 +1287,7 @@ mod tests {
             None,
             None,
             RadonScriptExecutionSettings::disable_all(),
+            &all_wips_active(),
         )
         .unwrap();
 
@@ -816,6 +1329,7 @@ mod tests {
             None,
             None,
             RadonScriptExecutionSettings::disable_all(),
+            &all_wips_active(),
         )
         .unwrap_err();
 
@@ -858,6 +1372,7 @@ mod tests {
             None,
             None,
             RadonScriptExecutionSettings::disable_all(),
+            &all_wips_active(),
         )
         .unwrap_err();
 
@@ -900,6 +1415,7 @@ mod tests {
             None,
             None,
             RadonScriptExecutionSettings::disable_all(),
+            &all_wips_active(),
         )
         .unwrap_err();
 
@@ -925,6 +1441,7 @@ mod tests {
             None,
             None,
             RadonScriptExecutionSettings::disable_all(),
+            &all_wips_active(),
         )
         .unwrap()
         .into_inner();
@@ -955,4 +1472,143 @@ mod tests {
         let req = surf::get("https://httpbin.org/get?page=2").set_header("User-Agent", test_header);
         assert_eq!(req.header("User-Agent"), Some(test_header));
     }
+
+    #[test]
+    fn test_is_transient_error() {
+        assert!(is_transient_error(&RadError::HttpStatus {
+            status_code: 503
+        }));
+        assert!(is_transient_error(&RadError::HttpStatus {
+            status_code: 429
+        }));
+        assert!(is_transient_error(&RadError::HttpConnectTimeout {
+            timeout_ms: 1000
+        }));
+        assert!(!is_transient_error(&RadError::HttpStatus {
+            status_code: 404
+        }));
+        assert!(!is_transient_error(&RadError::HttpResponseTooLarge {
+            max_size: 1000
+        }));
+    }
+
+    #[test]
+    fn test_with_timeout() {
+        let timeout_error = |timeout_ms| RadError::HttpReadTimeout { timeout_ms };
+
+        assert_eq!(
+            block_on(with_timeout(
+                futures::future::pending::<()>(),
+                Some(Duration::from_millis(10)),
+                timeout_error
+            )),
+            Err(RadError::HttpReadTimeout { timeout_ms: 10 })
+        );
+        assert_eq!(
+            block_on(with_timeout(
+                futures::future::ready(1),
+                Some(Duration::from_millis(10)),
+                timeout_error
+            )),
+            Ok(1)
+        );
+        assert_eq!(
+            block_on(with_timeout(futures::future::ready(1), None, timeout_error)),
+            Ok(1)
+        );
+    }
+
+    #[test]
+    fn test_http_retries_exhausted() {
+        // Nothing listens on port 1, so every attempt fails with a connection error
+        let retrieve = RADRetrieve {
+            kind: RADType::HttpGet,
+            url: "http://127.0.0.1:1/".to_string(),
+            ..RADRetrieve::default()
+        };
+        let limits = RetrievalLimits {
+            max_retries: 2,
+            retry_backoff: Duration::from_millis(1),
+            ..RetrievalLimits::default()
+        };
+
+        match block_on(http_response(&retrieve, &limits, None)) {
+            Err(RadError::HttpRetriesExhausted {
+                attempts,
+                last_error,
+            }) => {
+                assert_eq!(attempts, 3);
+                assert!(is_transient_error(&last_error));
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+
+        let limits = RetrievalLimits {
+            max_retries: 0,
+            ..RetrievalLimits::default()
+        };
+        match block_on(http_response(&retrieve, &limits, None)) {
+            Err(RadError::HttpOther { .. }) => {}
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_http_retries_only_get() {
+        // Nothing listens on port 1, so every attempt fails with a connection error
+        let retrieve = RADRetrieve {
+            kind: RADType::HttpPost,
+            url: "http://127.0.0.1:1/".to_string(),
+            ..RADRetrieve::default()
+        };
+        let limits = RetrievalLimits {
+            max_retries: 2,
+            retry_backoff: Duration::from_millis(1),
+            ..RetrievalLimits::default()
+        };
+
+        match block_on(http_response(&retrieve, &limits, None)) {
+            Err(RadError::HttpOther { .. }) => {}
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_http_retries_max_duration() {
+        // Nothing listens on port 1, so every attempt fails with a connection error
+        let retrieve = RADRetrieve {
+            kind: RADType::HttpGet,
+            url: "http://127.0.0.1:1/".to_string(),
+            ..RADRetrieve::default()
+        };
+        // Only the first retry starts before the maximum duration
+        let limits = RetrievalLimits {
+            max_retries: 5,
+            retry_backoff: Duration::from_millis(100),
+            max_retry_duration: Some(Duration::from_millis(250)),
+            ..RetrievalLimits::default()
+        };
+
+        match block_on(http_response(&retrieve, &limits, None)) {
+            Err(RadError::HttpRetriesExhausted { attempts, .. }) => assert_eq!(attempts, 2),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_http_retries_exhausted_keeps_error_code() {
+        let error = RadError::HttpRetriesExhausted {
+            attempts: 3,
+            last_error: Box::new(RadError::HttpStatus { status_code: 503 }),
+        };
+
+        assert_eq!(
+            error.try_into_cbor_array(),
+            RadError::HttpStatus { status_code: 503 }.try_into_cbor_array()
+        );
+        assert_eq!(
+            RadError::HttpConnectTimeout { timeout_ms: 1000 }.try_into_error_code(),
+            Ok(RadonErrors::RetrieveTimeout)
+        );
+    }
 }
@@ -13,7 +13,9 @@ use crate::{
     filters::{self, RadonFilters},
     operators::RadonOpCodes,
     reducers::{self, RadonReducers},
-    script::{execute_radon_script, unpack_subscript, RadonCall, RadonScriptExecutionSettings},
+    script::{
+        execute_radon_script, unpack_subscript_in_context, RadonCall, RadonScriptExecutionSettings,
+    },
     types::{
         array::RadonArray, boolean::RadonBoolean, bytes::RadonBytes, float::RadonFloat,
         integer::RadonInteger, map::RadonMap, string::RadonString, RadonType, RadonTypes,
@@ -97,6 +99,60 @@ pub fn get_string(input: &RadonArray, args: &[Value]) -> Result<RadonString, Rad
     item.try_into()
 }
 
+/// Flatten an array of arrays into a single array, by recursively replacing every inner array by its
+/// items up to the given depth. Items that are not arrays are kept as they are.
+///
+/// The only argument is the depth, which is optional and defaults to 1.
+pub fn flatten(input: &RadonArray, args: &[Value]) -> Result<RadonArray, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonArray::radon_type_name(),
+        operator: "Flatten".to_string(),
+        args: args.to_vec(),
+    };
+
+    let depth = match args {
+        [] => 1,
+        [arg] => from_value::<u32>(arg.to_owned()).map_err(|_| wrong_args())?,
+        _ => return Err(wrong_args()),
+    };
+
+    let mut output = vec![];
+    flatten_into(input.value(), depth, &mut output);
+
+    Ok(RadonArray::from(output))
+}
+
+fn flatten_into(items: Vec<RadonTypes>, depth: u32, output: &mut Vec<RadonTypes>) {
+    for item in items {
+        match item {
+            RadonTypes::Array(array) if depth > 0 => flatten_into(array.value(), depth - 1, output),
+            item => output.push(item),
+        }
+    }
+}
+
+/// Take the first `n` items of an array, where `n` is the only argument. If the array has less than
+/// `n` items, all of them are returned.
+pub fn take(input: &RadonArray, args: &[Value]) -> Result<RadonArray, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonArray::radon_type_name(),
+        operator: "Take".to_string(),
+        args: args.to_vec(),
+    };
+
+    if args.len() != 1 {
+        return Err(wrong_args());
+    }
+
+    let arg = args[0].to_owned();
+    let n = from_value::<u32>(arg).map_err(|_| wrong_args())?;
+    let n = usize::try_from(n).map_err(|_| wrong_args())?;
+
+    Ok(RadonArray::from(
+        input.value().into_iter().take(n).collect::<Vec<_>>(),
+    ))
+}
+
 pub fn map(
     input: &RadonArray,
     args: &[Value],
@@ -117,7 +173,7 @@ pub fn map(
         operator: "Map".to_string(),
         inner: Box::new(e),
     };
-    let subscript = unpack_subscript(&args[0]).map_err(subscript_err)?;
+    let subscript = unpack_subscript_in_context(&args[0], context).map_err(subscript_err)?;
 
     let mut reports = vec![];
     let mut results = vec![];
@@ -163,7 +219,8 @@ pub fn filter(
                 operator: "Filter".to_string(),
                 inner: Box::new(e),
             };
-            let subscript = unpack_subscript(first_arg).map_err(subscript_err)?;
+            let subscript =
+                unpack_subscript_in_context(first_arg, context).map_err(subscript_err)?;
 
             let mut reports = vec![];
             let mut results = vec![];
@@ -209,6 +266,65 @@ pub fn filter(
     }
 }
 
+/// Check whether any of the items of an array satisfies the predicate given as a subscript, which
+/// must return a `RadonBoolean`. The subscript is applied in order, stopping at the first item for
+/// which it returns `true`, and the result is always `false` for empty arrays.
+pub fn some(
+    input: &RadonArray,
+    args: &[Value],
+    context: &mut ReportContext<RadonTypes>,
+) -> Result<RadonTypes, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonArray::radon_type_name(),
+        operator: "Some".to_string(),
+        args: args.to_vec(),
+    };
+
+    if args.len() != 1 {
+        return Err(wrong_args());
+    }
+
+    let subscript_err = |e| RadError::Subscript {
+        input_type: "RadonArray".to_string(),
+        operator: "Some".to_string(),
+        inner: Box::new(e),
+    };
+    let subscript = unpack_subscript_in_context(&args[0], context).map_err(subscript_err)?;
+
+    let mut reports = vec![];
+    let mut result = false;
+
+    let settings = RadonScriptExecutionSettings::tailored_to_stage(&context.stage);
+    for item in input.value() {
+        let report = execute_radon_script(item, subscript.as_slice(), context, settings)?;
+
+        // If there is an error while applying the subscript, short-circuit and bubble up the error
+        // as it comes from the radon script execution
+        if let RadonTypes::RadonError(error) = &report.result {
+            return Err(error.clone().into_inner());
+        }
+
+        if let RadonTypes::Boolean(boolean) = &report.result {
+            result = boolean.value();
+        } else {
+            return Err(RadError::ArraySomeWrongSubscript {
+                value: report.result.to_string(),
+            });
+        }
+
+        reports.push(report);
+
+        if result {
+            break;
+        }
+    }
+
+    // Extract the partial results from the reports and put them in the execution context if needed
+    partial_results_extract(&subscript, &reports, context);
+
+    Ok(RadonBoolean::from(result).into())
+}
+
 pub fn sort(
     input: &RadonArray,
     args: &[Value],
@@ -834,6 +950,152 @@ mod tests {
         assert_eq!(output, expected)
     }
 
+    #[test]
+    fn test_flatten() {
+        let inner = RadonTypes::from(RadonArray::from(vec![
+            RadonInteger::from(2).into(),
+            RadonArray::from(vec![RadonInteger::from(3).into()]).into(),
+        ]));
+        let input = RadonArray::from(vec![RadonInteger::from(1).into(), inner]);
+
+        let output = flatten(&input, &[]).unwrap();
+        let expected = RadonArray::from(vec![
+            RadonInteger::from(1).into(),
+            RadonInteger::from(2).into(),
+            RadonArray::from(vec![RadonInteger::from(3).into()]).into(),
+        ]);
+        assert_eq!(output, expected);
+
+        let output = flatten(&input, &[Value::Integer(2)]).unwrap();
+        let expected = RadonArray::from(vec![
+            RadonInteger::from(1).into(),
+            RadonInteger::from(2).into(),
+            RadonInteger::from(3).into(),
+        ]);
+        assert_eq!(output, expected);
+
+        let output = flatten(&input, &[Value::Integer(0)]).unwrap();
+        assert_eq!(output, input);
+    }
+
+    #[test]
+    fn test_flatten_wrong_args() {
+        let input = RadonArray::from(vec![]);
+
+        assert_eq!(
+            &flatten(&input, &[Value::Integer(-1)])
+                .unwrap_err()
+                .to_string(),
+            "Wrong `RadonArray::Flatten()` arguments: `[Integer(-1)]`"
+        );
+        assert!(flatten(&input, &[Value::Integer(1), Value::Integer(1)]).is_err());
+    }
+
+    #[test]
+    fn test_take() {
+        let input = RadonArray::from(vec![
+            RadonInteger::from(1).into(),
+            RadonInteger::from(2).into(),
+            RadonInteger::from(3).into(),
+        ]);
+
+        let output = take(&input, &[Value::Integer(2)]).unwrap();
+        let expected = RadonArray::from(vec![
+            RadonInteger::from(1).into(),
+            RadonInteger::from(2).into(),
+        ]);
+        assert_eq!(output, expected);
+
+        let output = take(&input, &[Value::Integer(5)]).unwrap();
+        assert_eq!(output, input);
+
+        let output = take(&input, &[Value::Integer(0)]).unwrap();
+        assert_eq!(output, RadonArray::from(vec![]));
+    }
+
+    #[test]
+    fn test_take_wrong_args() {
+        let input = RadonArray::from(vec![RadonInteger::from(1).into()]);
+
+        assert_eq!(
+            &take(&input, &[Value::Integer(-1)]).unwrap_err().to_string(),
+            "Wrong `RadonArray::Take()` arguments: `[Integer(-1)]`"
+        );
+        assert!(take(&input, &[]).is_err());
+    }
+
+    #[test]
+    fn test_some_integer_greater_than() {
+        let input = RadonArray::from(vec![
+            RadonInteger::from(2).into(),
+            RadonInteger::from(6).into(),
+        ]);
+        let script = |n| {
+            vec![Value::Array(vec![Value::Array(vec![
+                Value::Integer(IntegerGreaterThan as i128),
+                Value::Integer(n),
+            ])])]
+        };
+
+        let output = some(&input, &script(4), &mut ReportContext::default()).unwrap();
+        assert_eq!(output, RadonTypes::from(RadonBoolean::from(true)));
+
+        let output = some(&input, &script(6), &mut ReportContext::default()).unwrap();
+        assert_eq!(output, RadonTypes::from(RadonBoolean::from(false)));
+
+        let empty = RadonArray::from(vec![]);
+        let output = some(&empty, &script(4), &mut ReportContext::default()).unwrap();
+        assert_eq!(output, RadonTypes::from(RadonBoolean::from(false)));
+    }
+
+    #[test]
+    fn test_some_with_partial_results() {
+        let input = RadonArray::from(vec![
+            RadonInteger::from(2).into(),
+            RadonInteger::from(3).into(),
+            RadonInteger::from(4).into(),
+        ]);
+        let script = vec![Value::Array(vec![Value::Array(vec![
+            Value::Integer(IntegerGreaterThan as i128),
+            Value::Integer(2),
+        ])])];
+        let mut context = ReportContext::from_stage(Stage::Retrieval(RetrievalMetadata::default()));
+        some(&input, &script, &mut context).unwrap();
+
+        // The subscript is not applied after the first item that satisfies the predicate
+        match context.stage {
+            Stage::Retrieval(metadata) => {
+                let expected_partial_results = vec![vec![
+                    vec![RadonInteger::from(2).into(), RadonInteger::from(3).into()],
+                    vec![
+                        RadonBoolean::from(false).into(),
+                        RadonBoolean::from(true).into(),
+                    ],
+                ]];
+                assert_eq!(metadata.subscript_partial_results, expected_partial_results);
+            }
+            stage => panic!("Expected the retrieval stage, got {:?}", stage),
+        }
+    }
+
+    #[test]
+    fn test_some_negative() {
+        let input = RadonArray::from(vec![
+            RadonInteger::from(2).into(),
+            RadonInteger::from(6).into(),
+        ]);
+        let script = vec![Value::Array(vec![Value::Array(vec![
+            Value::Integer(IntegerMultiply as i128),
+            Value::Integer(4),
+        ])])];
+        let result = some(&input, &script, &mut ReportContext::default());
+
+        assert_eq!(
+            &result.unwrap_err().to_string(),
+            "ArraySome subscript output was not RadonBoolean (was `RadonTypes::RadonInteger(8)`)"
+        );
+    }
+
     #[test]
     fn test_sort_map_string_values() {
         let mut map1 = BTreeMap::new();
@@ -4,7 +4,8 @@ use serde_cbor::Value;
 
 use crate::{
     error::RadError,
-    types::{boolean::RadonBoolean, string::RadonString, RadonType},
+    operators::string::match_key,
+    types::{boolean::RadonBoolean, string::RadonString, RadonType, RadonTypes},
 };
 
 pub fn negate(input: &RadonBoolean) -> RadonBoolean {
@@ -15,6 +16,15 @@ pub fn to_string(input: RadonBoolean) -> Result<RadonString, RadError> {
     RadonString::try_from(Value::Text(input.value().to_string()))
 }
 
+pub fn boolean_match(input: &RadonBoolean, args: &[Value]) -> Result<RadonTypes, RadError> {
+    match_key(
+        &input.value().to_string(),
+        RadonBoolean::radon_type_name(),
+        "Match",
+        args,
+    )
+}
+
 #[test]
 fn test_boolean_negate() {
     let true_bool = RadonBoolean::from(true);
@@ -31,3 +41,21 @@ fn test_boolean_to_string() {
 
     assert_eq!(to_string(rad_int).unwrap(), rad_string);
 }
+
+#[test]
+fn test_boolean_match() {
+    use std::collections::BTreeMap;
+
+    let mut map = BTreeMap::new();
+    map.insert(Value::Text("true".to_string()), Value::Integer(1));
+    let args = [Value::Map(map), Value::Integer(0)];
+
+    assert_eq!(
+        boolean_match(&RadonBoolean::from(true), &args).unwrap(),
+        RadonTypes::from(crate::types::integer::RadonInteger::from(1))
+    );
+    assert_eq!(
+        boolean_match(&RadonBoolean::from(false), &args).unwrap(),
+        RadonTypes::from(crate::types::integer::RadonInteger::from(0))
+    );
+}
@@ -1,5 +1,6 @@
+use num_enum::TryFromPrimitive;
 use serde_cbor::value::{from_value, Value};
-use std::convert::TryFrom;
+use std::{convert::TryFrom, fmt};
 
 use crate::{
     error::RadError,
@@ -7,8 +8,64 @@ use crate::{
     types::{bytes::RadonBytes, string::RadonString, RadonType},
 };
 
-pub fn to_string(input: &RadonBytes) -> Result<RadonString, RadError> {
-    RadonString::try_from(Value::Text(hex::encode(input.value())))
+/// List of encodings that can be used for converting between `RadonBytes` and `RadonString`.
+/// **WARNING: these codes are consensus-critical.** They can be renamed but they cannot be
+/// re-assigned without causing a non-backwards-compatible protocol upgrade.
+#[derive(Clone, Copy, Debug, PartialEq, TryFromPrimitive)]
+#[repr(u8)]
+pub enum RadonBytesEncoding {
+    /// Lowercase hexadecimal, this is the default encoding
+    Hex = 0x00,
+    /// Standard base64 alphabet, with padding
+    Base64 = 0x01,
+    /// UTF-8, the string is the text contained in the bytes
+    Utf8 = 0x02,
+}
+
+impl fmt::Display for RadonBytesEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RadonBytesEncoding::{:?}", self)
+    }
+}
+
+/// Read the encoding from the optional single argument of `BytesAsString` and `StringAsBytes`,
+/// defaulting to `Hex` if there is no argument.
+pub fn encoding_from_args(
+    args: &[Value],
+    wrong_args: impl Fn() -> RadError,
+) -> Result<RadonBytesEncoding, RadError> {
+    match args {
+        [] => Ok(RadonBytesEncoding::Hex),
+        [arg] => {
+            let encoding_integer = from_value::<u8>(arg.to_owned()).map_err(|_| wrong_args())?;
+            RadonBytesEncoding::try_from(encoding_integer).map_err(|_| wrong_args())
+        }
+        _ => Err(wrong_args()),
+    }
+}
+
+pub fn to_string(input: &RadonBytes, args: &[Value]) -> Result<RadonString, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonBytes::radon_type_name(),
+        operator: "AsString".to_string(),
+        args: args.to_vec(),
+    };
+
+    let encoding = encoding_from_args(args, wrong_args)?;
+    let bytes = input.value();
+
+    let string = match encoding {
+        RadonBytesEncoding::Hex => hex::encode(bytes),
+        RadonBytesEncoding::Base64 => base64::encode(bytes),
+        RadonBytesEncoding::Utf8 => {
+            String::from_utf8(bytes).map_err(|e| RadError::InvalidEncoding {
+                encoding: encoding.to_string(),
+                description: e.to_string(),
+            })?
+        }
+    };
+
+    Ok(RadonString::from(string))
 }
 
 pub fn hash(input: &RadonBytes, args: &[Value]) -> Result<RadonBytes, RadError> {
@@ -34,13 +91,52 @@ mod tests {
     #[test]
     fn test_bytes_to_string() {
         let input = RadonBytes::from(vec![0x01, 0x02, 0x03]);
-        let output = to_string(&input).unwrap().value();
+        let output = to_string(&input, &[]).unwrap().value();
 
         let valid_expected = "010203".to_string();
 
         assert_eq!(output, valid_expected);
     }
 
+    #[test]
+    fn test_bytes_to_string_encodings() {
+        let input = RadonBytes::from(b"Hello, World!".to_vec());
+
+        let output = to_string(&input, &[Value::from(RadonBytesEncoding::Hex as u8)]).unwrap();
+        assert_eq!(output.value(), "48656c6c6f2c20576f726c6421");
+
+        let output = to_string(&input, &[Value::from(RadonBytesEncoding::Base64 as u8)]).unwrap();
+        assert_eq!(output.value(), "SGVsbG8sIFdvcmxkIQ==");
+
+        let output = to_string(&input, &[Value::from(RadonBytesEncoding::Utf8 as u8)]).unwrap();
+        assert_eq!(output.value(), "Hello, World!");
+    }
+
+    #[test]
+    fn test_bytes_to_string_invalid_utf8() {
+        let input = RadonBytes::from(vec![0xC3, 0x28]);
+        let output = to_string(&input, &[Value::from(RadonBytesEncoding::Utf8 as u8)]);
+
+        assert_eq!(
+            output.unwrap_err(),
+            RadError::InvalidEncoding {
+                encoding: RadonBytesEncoding::Utf8.to_string(),
+                description: "invalid utf-8 sequence of 1 bytes from index 0".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_bytes_to_string_wrong_args() {
+        let input = RadonBytes::from(vec![0x01]);
+        let output = to_string(&input, &[Value::from(0x03)]);
+
+        assert_eq!(
+            &output.unwrap_err().to_string(),
+            "Wrong `RadonBytes::AsString()` arguments: `[Integer(3)]`"
+        );
+    }
+
     #[test]
     fn test_bytes_hash() {
         let input = RadonBytes::from(vec![0x01, 0x02, 0x03]);
@@ -89,6 +89,37 @@ pub fn power(input: &RadonFloat, args: &[Value]) -> Result<RadonFloat, RadError>
     Ok(RadonFloat::from(input.value().powf(exp)))
 }
 
+/// Compute `1 / input`.
+pub fn reciprocal(input: &RadonFloat) -> Result<RadonFloat, RadError> {
+    let value = input.value();
+    if value == 0.0 {
+        return Err(RadError::DivisionByZero);
+    }
+
+    Ok(RadonFloat::from(1.0 / value))
+}
+
+/// Add a number to the input. Integer arguments are accepted as well, so that integer constants
+/// can be added to floats.
+pub fn sum(input: &RadonFloat, args: &[Value]) -> Result<RadonFloat, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonFloat::radon_type_name(),
+        operator: "Sum".to_string(),
+        args: args.to_vec(),
+    };
+
+    let arg = args.first().ok_or_else(wrong_args)?.to_owned();
+    let addend = from_value::<f64>(arg).map_err(|_| wrong_args())?;
+    let result = input.value() + addend;
+
+    // Adding two finite numbers can only result in an infinite number on overflow
+    if result.is_infinite() && input.value().is_finite() && addend.is_finite() {
+        Err(RadError::Overflow)
+    } else {
+        Ok(RadonFloat::from(result))
+    }
+}
+
 // FIXME: Allow for now, wait for https://github.com/rust-lang/rust/issues/67058 to reach stable
 #[allow(clippy::cast_possible_truncation)]
 pub fn floor(input: &RadonFloat) -> RadonInteger {
@@ -257,3 +288,42 @@ fn test_float_trunc() {
     assert_eq!(truncate(&float2), RadonInteger::from(10));
     assert_eq!(truncate(&float3), RadonInteger::from(-10));
 }
+
+#[test]
+fn test_float_sum() {
+    let rad_float = RadonFloat::from(1.5);
+
+    assert_eq!(
+        sum(&rad_float, &[Value::Float(0.25)]).unwrap(),
+        RadonFloat::from(1.75)
+    );
+    // Integers can be added to floats
+    assert_eq!(
+        sum(&rad_float, &[Value::Integer(-2)]).unwrap(),
+        RadonFloat::from(-0.5)
+    );
+    assert_eq!(
+        sum(&RadonFloat::from(f64::MAX), &[Value::Float(f64::MAX)]).unwrap_err(),
+        RadError::Overflow,
+    );
+    assert!(matches!(
+        sum(&rad_float, &[Value::Text("1".to_string())]),
+        Err(RadError::WrongArguments { .. })
+    ));
+}
+
+#[test]
+fn test_float_reciprocal() {
+    assert_eq!(
+        reciprocal(&RadonFloat::from(0.5)).unwrap(),
+        RadonFloat::from(2.0)
+    );
+    assert_eq!(
+        reciprocal(&RadonFloat::from(-4.0)).unwrap(),
+        RadonFloat::from(-0.25)
+    );
+    assert_eq!(
+        reciprocal(&RadonFloat::from(0.0)).unwrap_err(),
+        RadError::DivisionByZero,
+    );
+}
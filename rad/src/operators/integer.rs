@@ -2,11 +2,15 @@ use std::{borrow::ToOwned, convert::TryFrom, i128};
 
 use serde_cbor::value::{from_value, Value};
 
+use witnet_data_structures::radon_report::{ReportContext, Stage};
+use witnet_util::timestamp::get_timestamp;
+
 use crate::{
     error::RadError,
+    operators::{float as float_operators, string::match_key},
     types::{
         boolean::RadonBoolean, float::RadonFloat, integer::RadonInteger, string::RadonString,
-        RadonType,
+        RadonType, RadonTypes,
     },
 };
 
@@ -114,6 +118,96 @@ pub fn power(input: &RadonInteger, args: &[Value]) -> Result<RadonInteger, RadEr
     }
 }
 
+/// Add the argument to the input. Adding a float results in a float, as in `FloatSum`.
+pub fn sum(input: &RadonInteger, args: &[Value]) -> Result<RadonTypes, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonInteger::radon_type_name(),
+        operator: "Sum".to_string(),
+        args: args.to_vec(),
+    };
+
+    let arg = args.first().ok_or_else(wrong_args)?.to_owned();
+    if let Value::Float(_) = arg {
+        return float_operators::sum(&to_float(input.clone())?, args).map(Into::into);
+    }
+    let addend = from_value::<i128>(arg).map_err(|_| wrong_args())?;
+    let result = input.value().checked_add(addend);
+
+    if let Some(result) = result {
+        Ok(RadonInteger::from(result).into())
+    } else {
+        Err(RadError::Overflow)
+    }
+}
+
+/// Compute `1 / input`. The result is a float, as it can only be an integer for `1` and `-1`.
+pub fn reciprocal(input: &RadonInteger) -> Result<RadonFloat, RadError> {
+    let value = input.value();
+    if value == 0 {
+        return Err(RadError::DivisionByZero);
+    }
+
+    to_float(input.clone()).map(|float| RadonFloat::from(1.0 / float.value()))
+}
+
+/// The timestamp that dates are compared against: the timestamp of the epoch in which the data
+/// request is resolved, or the current time if it is not known.
+fn reference_timestamp(context: &ReportContext<RadonTypes>) -> i128 {
+    let timestamp = match &context.stage {
+        Stage::Retrieval(metadata) => metadata.timestamp,
+        _ => None,
+    };
+
+    i128::from(timestamp.unwrap_or_else(get_timestamp))
+}
+
+/// Compute how many seconds old a UNIX timestamp is with regard to the timestamp of the data
+/// request. Timestamps in the future result in negative ages.
+pub fn age(
+    input: &RadonInteger,
+    context: &ReportContext<RadonTypes>,
+) -> Result<RadonInteger, RadError> {
+    reference_timestamp(context)
+        .checked_sub(input.value())
+        .map(RadonInteger::from)
+        .ok_or(RadError::Overflow)
+}
+
+/// Fail with `RadError::StaleTimestamp` if a UNIX timestamp is older than the given number of
+/// seconds with regard to the timestamp of the data request. Otherwise, return the timestamp.
+pub fn max_age(
+    input: &RadonInteger,
+    args: &[Value],
+    context: &ReportContext<RadonTypes>,
+) -> Result<RadonInteger, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonInteger::radon_type_name(),
+        operator: "MaxAge".to_string(),
+        args: args.to_vec(),
+    };
+
+    let arg = args.first().ok_or_else(wrong_args)?.to_owned();
+    let max_age = from_value::<i128>(arg).map_err(|_| wrong_args())?;
+    let age = age(input, context)?.value();
+
+    if age > max_age {
+        log::debug!("The timestamp is {} seconds old", age);
+
+        Err(RadError::StaleTimestamp { max_age })
+    } else {
+        Ok(input.clone())
+    }
+}
+
+pub fn integer_match(input: &RadonInteger, args: &[Value]) -> Result<RadonTypes, RadError> {
+    match_key(
+        &input.value().to_string(),
+        RadonInteger::radon_type_name(),
+        "Match",
+        args,
+    )
+}
+
 #[test]
 fn test_integer_absolute() {
     let positive_integer = RadonInteger::from(10);
@@ -266,3 +360,121 @@ fn test_integer_power() {
         "Overflow error".to_string(),
     );
 }
+
+#[test]
+fn test_integer_sum() {
+    let rad_int = RadonInteger::from(10);
+
+    assert_eq!(
+        sum(&rad_int, &[Value::Integer(-3)]).unwrap(),
+        RadonTypes::from(RadonInteger::from(7))
+    );
+    assert_eq!(
+        sum(&RadonInteger::from(i128::max_value()), &[Value::Integer(1)]).unwrap_err(),
+        RadError::Overflow,
+    );
+    // Adding a float results in a float
+    assert_eq!(
+        sum(&rad_int, &[Value::Float(1.5)]).unwrap(),
+        RadonTypes::from(RadonFloat::from(11.5))
+    );
+    assert!(matches!(
+        sum(&rad_int, &[Value::Text("1".to_string())]),
+        Err(RadError::WrongArguments { .. })
+    ));
+}
+
+#[test]
+fn test_integer_reciprocal() {
+    assert_eq!(
+        reciprocal(&RadonInteger::from(4)).unwrap(),
+        RadonFloat::from(0.25)
+    );
+    assert_eq!(
+        reciprocal(&RadonInteger::from(0)).unwrap_err(),
+        RadError::DivisionByZero,
+    );
+}
+
+#[test]
+fn test_integer_match() {
+    use std::collections::BTreeMap;
+
+    let mut map = BTreeMap::new();
+    map.insert(Value::Text("1".to_string()), Value::Text("one".to_string()));
+    map.insert(
+        Value::Text("-1".to_string()),
+        Value::Text("minus one".to_string()),
+    );
+    let args = [Value::Map(map), Value::Text("other".to_string())];
+
+    assert_eq!(
+        integer_match(&RadonInteger::from(-1), &args).unwrap(),
+        RadonTypes::from(RadonString::from("minus one"))
+    );
+    assert_eq!(
+        integer_match(&RadonInteger::from(2), &args).unwrap(),
+        RadonTypes::from(RadonString::from("other"))
+    );
+    assert!(matches!(
+        integer_match(&RadonInteger::from(1), &args[..1]),
+        Err(RadError::WrongArguments { .. })
+    ));
+}
+
+#[test]
+fn test_integer_age() {
+    use witnet_data_structures::radon_report::RetrievalMetadata;
+
+    let mut context = ReportContext::from_stage(Stage::Retrieval(RetrievalMetadata {
+        timestamp: Some(1_591_012_800),
+        ..RetrievalMetadata::default()
+    }));
+
+    assert_eq!(
+        age(&RadonInteger::from(1_591_012_500), &context).unwrap(),
+        RadonInteger::from(300)
+    );
+    // Timestamps in the future have a negative age
+    assert_eq!(
+        age(&RadonInteger::from(1_591_012_810), &context).unwrap(),
+        RadonInteger::from(-10)
+    );
+    assert_eq!(
+        age(&RadonInteger::from(i128::MIN), &context).unwrap_err(),
+        RadError::Overflow
+    );
+
+    // Without the timestamp of the data request, the current time is used
+    context.stage = Stage::Retrieval(RetrievalMetadata::default());
+    let now = get_timestamp();
+    let output = age(&RadonInteger::from(i128::from(now) - 60), &context).unwrap();
+    assert!(output.value() >= 60);
+}
+
+#[test]
+fn test_integer_max_age() {
+    use witnet_data_structures::radon_report::RetrievalMetadata;
+
+    let context = ReportContext::from_stage(Stage::Retrieval(RetrievalMetadata {
+        timestamp: Some(1_591_012_800),
+        ..RetrievalMetadata::default()
+    }));
+    let five_minutes = [Value::Integer(300)];
+
+    let input = RadonInteger::from(1_591_012_500);
+    assert_eq!(max_age(&input, &five_minutes, &context).unwrap(), input);
+
+    let input = RadonInteger::from(1_591_012_499);
+    assert_eq!(
+        max_age(&input, &five_minutes, &context).unwrap_err(),
+        RadError::StaleTimestamp { max_age: 300 }
+    );
+
+    assert_eq!(
+        &max_age(&input, &[Value::Text("300".to_string())], &context)
+            .unwrap_err()
+            .to_string(),
+        "Wrong `RadonInteger::MaxAge()` arguments: `[Text(\"300\")]`"
+    );
+}
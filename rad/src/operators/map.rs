@@ -55,6 +55,21 @@ pub fn get_string(input: &RadonMap, args: &[Value]) -> Result<RadonString, RadEr
     item.try_into()
 }
 
+/// Returns an array with one `[key, value]` pair for each entry of the map, sorted by key.
+pub fn entries(input: &RadonMap) -> RadonArray {
+    let v: Vec<RadonTypes> = input
+        .value()
+        .into_iter()
+        .map(|(key, value)| {
+            RadonTypes::from(RadonArray::from(vec![
+                RadonTypes::from(RadonString::from(key)),
+                value,
+            ]))
+        })
+        .collect();
+    RadonArray::from(v)
+}
+
 pub fn keys(input: &RadonMap) -> RadonArray {
     let v: Vec<RadonTypes> = input
         .value()
@@ -108,6 +123,40 @@ mod tests {
         assert!(not_found_object.is_err());
     }
 
+    #[test]
+    fn test_map_entries() {
+        let mut map = BTreeMap::new();
+        map.insert(
+            "Zero".to_string(),
+            RadonTypes::Integer(RadonInteger::from(0)),
+        );
+        map.insert(
+            "One".to_string(),
+            RadonTypes::String(RadonString::from("1")),
+        );
+        let input = RadonMap::from(map);
+
+        let output = entries(&input);
+        let expected = RadonArray::from(vec![
+            RadonArray::from(vec![
+                RadonString::from("One").into(),
+                RadonString::from("1").into(),
+            ])
+            .into(),
+            RadonArray::from(vec![
+                RadonString::from("Zero").into(),
+                RadonInteger::from(0).into(),
+            ])
+            .into(),
+        ]);
+
+        assert_eq!(output, expected);
+        assert_eq!(
+            entries(&RadonMap::from(BTreeMap::new())),
+            RadonArray::from(vec![])
+        );
+    }
+
     #[test]
     fn test_map_keys() {
         let key0 = "Zero";
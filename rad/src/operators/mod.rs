@@ -1,11 +1,18 @@
-use std::fmt;
+use std::{convert::TryFrom, fmt};
 
 use num_enum::TryFromPrimitive;
 use serde::Serialize;
 
-use witnet_data_structures::radon_report::ReportContext;
+use witnet_data_structures::{mainnet_validations::ActiveWips, radon_report::ReportContext};
 
-use crate::{error::RadError, script::RadonCall, types::RadonTypes};
+use crate::{
+    error::RadError,
+    script::RadonCall,
+    types::{
+        array::RadonArray, boolean::RadonBoolean, bytes::RadonBytes, float::RadonFloat,
+        integer::RadonInteger, map::RadonMap, string::RadonString, RadonType, RadonTypes,
+    },
+};
 
 pub mod array;
 pub mod boolean;
@@ -13,6 +20,7 @@ pub mod bytes;
 pub mod float;
 pub mod integer;
 pub mod map;
+pub mod path;
 pub mod string;
 
 /// List of RADON operators.
@@ -26,11 +34,12 @@ pub enum RadonOpCodes {
     ///////////////////////////////////////////////////////////////////////
     // Multi-type operator codes start at 0x00
     Identity = 0x00,
+    GetPath = 0x01,
     ///////////////////////////////////////////////////////////////////////
     // Array operator codes (start at 0x10)
     ArrayCount = 0x10,
     ArrayFilter = 0x11,
-    //    ArrayFlatten = 0x12,
+    ArrayFlatten = 0x12,
     ArrayGetArray = 0x13,
     ArrayGetBoolean = 0x14,
     ArrayGetBytes = 0x15,
@@ -40,13 +49,13 @@ pub enum RadonOpCodes {
     ArrayGetString = 0x19,
     ArrayMap = 0x1A,
     ArrayReduce = 0x1B,
-    //    ArraySome = 0x1C,
+    ArraySome = 0x1C,
     ArraySort = 0x1D,
-    //    ArrayTake = 0x1E,
+    ArrayTake = 0x1E,
     ///////////////////////////////////////////////////////////////////////
     // Boolean operator codes (start at 0x20)
     BooleanAsString = 0x20,
-    //    BooleanMatch = 0x21,
+    BooleanMatch = 0x21,
     BooleanNegate = 0x22,
     ///////////////////////////////////////////////////////////////////////
     // Bytes operator codes (start at 0x30)
@@ -59,13 +68,15 @@ pub enum RadonOpCodes {
     IntegerAsString = 0x42,
     IntegerGreaterThan = 0x43,
     IntegerLessThan = 0x44,
-    //    IntegerMatch = 0x45,
+    IntegerMatch = 0x45,
     IntegerModulo = 0x46,
     IntegerMultiply = 0x47,
     IntegerNegate = 0x48,
     IntegerPower = 0x49,
-    //    IntegerReciprocal = 0x4A,
-    //    IntegerSum = 0x4B,
+    IntegerReciprocal = 0x4A,
+    IntegerSum = 0x4B,
+    IntegerAge = 0x4C,
+    IntegerMaxAge = 0x4D,
     ///////////////////////////////////////////////////////////////////////
     // Float operator codes (start at 0x50)
     FloatAbsolute = 0x50,
@@ -78,13 +89,13 @@ pub enum RadonOpCodes {
     FloatMultiply = 0x57,
     FloatNegate = 0x58,
     FloatPower = 0x59,
-    //    FloatReciprocal = 0x5A,
+    FloatReciprocal = 0x5A,
     FloatRound = 0x5B,
-    //    FloatSum = 0x5C,
+    FloatSum = 0x5C,
     FloatTruncate = 0x5D,
     ///////////////////////////////////////////////////////////////////////
     // Map operator codes (start at 0x60)
-    //    MapEntries = 0x60,
+    MapEntries = 0x60,
     MapGetArray = 0x61,
     MapGetBoolean = 0x62,
     MapGetBytes = 0x63,
@@ -97,16 +108,20 @@ pub enum RadonOpCodes {
     ///////////////////////////////////////////////////////////////////////
     // String operator codes (start at 0x70)
     StringAsBoolean = 0x70,
-    //    StringAsBytes = 0x71,
+    StringAsBytes = 0x71,
     StringAsFloat = 0x72,
     StringAsInteger = 0x73,
     StringLength = 0x74,
     StringMatch = 0x75,
     StringParseJSONArray = 0x76,
     StringParseJSONMap = 0x77,
-    //    StringParseXML = 0x78,
+    StringParseXML = 0x78,
     StringToLowerCase = 0x79,
     StringToUpperCase = 0x7A,
+    StringRegexCapture = 0x7B,
+    StringRegexFindAll = 0x7C,
+    StringRegexReplace = 0x7D,
+    StringParseDateTime = 0x7E,
 }
 
 impl fmt::Display for RadonOpCodes {
@@ -115,6 +130,120 @@ impl fmt::Display for RadonOpCodes {
     }
 }
 
+/// The kinds of values that RADON operators can be applied to or return.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum RadonTypeKind {
+    Array,
+    Boolean,
+    Bytes,
+    Float,
+    Integer,
+    Map,
+    String,
+}
+
+impl fmt::Display for RadonTypeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Radon{:?}", self)
+    }
+}
+
+impl RadonTypeKind {
+    /// The name of the RADON type of this kind, e.g. `RadonFloat`.
+    pub fn radon_type_name(self) -> &'static str {
+        match self {
+            RadonTypeKind::Array => RadonArray::radon_type_name(),
+            RadonTypeKind::Boolean => RadonBoolean::radon_type_name(),
+            RadonTypeKind::Bytes => RadonBytes::radon_type_name(),
+            RadonTypeKind::Float => RadonFloat::radon_type_name(),
+            RadonTypeKind::Integer => RadonInteger::radon_type_name(),
+            RadonTypeKind::Map => RadonMap::radon_type_name(),
+            RadonTypeKind::String => RadonString::radon_type_name(),
+        }
+    }
+}
+
+impl RadonOpCodes {
+    /// All the operators, except for `Fail`.
+    pub fn all() -> impl Iterator<Item = RadonOpCodes> {
+        (0..=u8::MAX)
+            .filter_map(|code| RadonOpCodes::try_from(code).ok())
+            .filter(|op_code| *op_code != RadonOpCodes::Fail)
+    }
+
+    /// Whether this operator can be used in the scripts of retrieval sources.
+    pub fn is_active(self, active_wips: &ActiveWips) -> bool {
+        use RadonOpCodes::*;
+
+        match self {
+            // The operators that are newer than the protocol can only be used after WIP0021
+            GetPath | ArrayFlatten | ArraySome | ArrayTake | BooleanMatch | IntegerMatch
+            | IntegerReciprocal | IntegerSum | IntegerAge | IntegerMaxAge | FloatReciprocal
+            | FloatSum | MapEntries | StringAsBytes | StringParseXML | StringRegexCapture
+            | StringRegexFindAll | StringRegexReplace | StringParseDateTime => {
+                active_wips.wip0021()
+            }
+            _ => true,
+        }
+    }
+
+    /// The kind of values this operator can be applied to, or `None` for multi-type operators
+    /// (and `Fail`).
+    pub fn input_type(self) -> Option<RadonTypeKind> {
+        match self as u8 {
+            0x10..=0x1F => Some(RadonTypeKind::Array),
+            0x20..=0x2F => Some(RadonTypeKind::Boolean),
+            0x30..=0x3F => Some(RadonTypeKind::Bytes),
+            0x40..=0x4F => Some(RadonTypeKind::Integer),
+            0x50..=0x5F => Some(RadonTypeKind::Float),
+            0x60..=0x6F => Some(RadonTypeKind::Map),
+            0x70..=0x7F => Some(RadonTypeKind::String),
+            _ => None,
+        }
+    }
+
+    /// The kind of values returned by this operator when applied to a value of kind `input`, or
+    /// `None` if it cannot be known without executing it.
+    pub fn output_type(self, input: Option<RadonTypeKind>) -> Option<RadonTypeKind> {
+        use RadonOpCodes::*;
+        use RadonTypeKind::*;
+
+        match self {
+            Identity => input,
+            GetPath | ArrayReduce | BooleanMatch | IntegerMatch | StringMatch | Fail => None,
+            // Adding a float to an integer results in a float
+            IntegerSum => None,
+            ArrayFilter | ArrayFlatten | ArrayGetArray | ArrayMap | ArraySort | ArrayTake
+            | MapEntries | MapGetArray | MapKeys | MapValues | StringParseJSONArray
+            | StringRegexFindAll => Some(Array),
+            ArrayGetBoolean | ArraySome | BooleanNegate | IntegerGreaterThan | IntegerLessThan
+            | FloatGreaterThan | FloatLessThan | MapGetBoolean | StringAsBoolean => Some(Boolean),
+            ArrayGetBytes | BytesHash | MapGetBytes | StringAsBytes => Some(Bytes),
+            ArrayGetFloat | IntegerAsFloat | IntegerReciprocal | FloatAbsolute | FloatModulo
+            | FloatMultiply | FloatNegate | FloatPower | FloatReciprocal | FloatSum
+            | MapGetFloat | StringAsFloat => Some(Float),
+            ArrayCount | ArrayGetInteger | IntegerAbsolute | IntegerModulo | IntegerMultiply
+            | IntegerNegate | IntegerPower | IntegerAge | IntegerMaxAge | FloatCeiling
+            | FloatFloor | FloatRound | FloatTruncate | MapGetInteger | StringAsInteger
+            | StringLength | StringParseDateTime => Some(Integer),
+            ArrayGetMap | MapGetMap | StringParseJSONMap | StringParseXML => Some(Map),
+            ArrayGetString | BooleanAsString | BytesAsString | IntegerAsString | FloatAsString
+            | MapGetString | StringToLowerCase | StringToUpperCase | StringRegexCapture
+            | StringRegexReplace => Some(String),
+        }
+    }
+
+    /// The name of the operator without the kind of its input, e.g. `GetFloat` for both
+    /// `ArrayGetFloat` and `MapGetFloat`.
+    pub fn method_name(self) -> String {
+        let name = self.to_string();
+        match self.input_type() {
+            Some(kind) => name[format!("{:?}", kind).len()..].to_string(),
+            None => name,
+        }
+    }
+}
+
 pub trait Operable {
     fn operate(&self, call: &RadonCall) -> Result<RadonTypes, RadError>;
 
@@ -146,8 +275,6 @@ pub fn identity(input: RadonTypes) -> Result<RadonTypes, RadError> {
 
 #[cfg(test)]
 mod tests {
-    use crate::types::string::RadonString;
-
     use super::*;
 
     #[test]
@@ -159,6 +286,31 @@ mod tests {
         assert_eq!(output, expected);
     }
 
+    #[test]
+    pub fn test_op_codes_types() {
+        assert_eq!(
+            RadonOpCodes::MapGetFloat.input_type(),
+            Some(RadonTypeKind::Map)
+        );
+        assert_eq!(RadonOpCodes::MapGetFloat.method_name(), "GetFloat");
+        assert_eq!(RadonOpCodes::GetPath.input_type(), None);
+        assert_eq!(RadonOpCodes::GetPath.method_name(), "GetPath");
+        assert_eq!(
+            RadonOpCodes::Identity.output_type(Some(RadonTypeKind::Bytes)),
+            Some(RadonTypeKind::Bytes)
+        );
+        assert_eq!(
+            RadonOpCodes::FloatRound.output_type(Some(RadonTypeKind::Float)),
+            Some(RadonTypeKind::Integer)
+        );
+        // Every operator name starts with the name of the kind of its input
+        for op_code in RadonOpCodes::all() {
+            if let Some(kind) = op_code.input_type() {
+                assert!(op_code.to_string().starts_with(&format!("{:?}", kind)));
+            }
+        }
+    }
+
     #[test]
     pub fn test_operate() {
         let input = RadonString::from("Hello world!").into();
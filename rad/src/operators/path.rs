@@ -0,0 +1,353 @@
+use std::{convert::TryFrom, iter::Peekable, str::CharIndices};
+
+use serde_cbor::value::{from_value, Value};
+
+use crate::{
+    error::RadError,
+    types::{array::RadonArray, map::RadonMap, RadonType, RadonTypes},
+};
+
+/// A single step of a path expression.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PathSegment {
+    /// Get the value of a key from a `RadonMap`
+    Key(String),
+    /// Get the item at an index from a `RadonArray`. Negative indexes count from the end
+    Index(i32),
+}
+
+/// Parse a JSONPath-style path expression into a list of segments.
+///
+/// The supported syntax is a subset of JSONPath that always selects exactly one value:
+/// - An optional leading `$`, which stands for the input value.
+/// - `.key` or `['key']` (also `["key"]`) for selecting the value of a key from a map. The
+///   bracket notation allows keys containing any character, escaping quotes and backslashes
+///   with a backslash. The leading dot can be omitted for the first key, as in `data.prices`.
+/// - `[n]` for selecting the item at index `n` from an array, where negative indexes count from
+///   the end of the array.
+///
+/// Wildcards, slices, recursive descent and filter expressions are not supported.
+pub fn parse_path(path: &str) -> Result<Vec<PathSegment>, RadError> {
+    let invalid_path = |description: String| RadError::InvalidPath {
+        path: path.to_string(),
+        description,
+    };
+
+    let mut chars = path.char_indices().peekable();
+    if let Some((_, '$')) = chars.peek() {
+        chars.next();
+    } else if let Some(&(_, c)) = chars.peek() {
+        if c != '.' && c != '[' {
+            let key = read_dotted_key(&mut chars);
+            return parse_segments(chars, vec![PathSegment::Key(key)], invalid_path);
+        }
+    }
+
+    parse_segments(chars, vec![], invalid_path)
+}
+
+fn parse_segments(
+    mut chars: Peekable<CharIndices>,
+    mut segments: Vec<PathSegment>,
+    invalid_path: impl Fn(String) -> RadError,
+) -> Result<Vec<PathSegment>, RadError> {
+    while let Some((position, c)) = chars.next() {
+        match c {
+            '.' => {
+                let key = read_dotted_key(&mut chars);
+                if key.is_empty() {
+                    return Err(invalid_path(format!(
+                        "Empty key after `.` at position {}",
+                        position
+                    )));
+                }
+                segments.push(PathSegment::Key(key));
+            }
+            '[' => {
+                let segment = match chars.peek() {
+                    Some(&(_, quote)) if quote == '\'' || quote == '"' => {
+                        chars.next();
+                        PathSegment::Key(read_quoted_key(&mut chars, quote).ok_or_else(|| {
+                            invalid_path(format!(
+                                "Unterminated quoted key starting at position {}",
+                                position
+                            ))
+                        })?)
+                    }
+                    _ => {
+                        let mut index = String::new();
+                        while let Some(&(_, c)) = chars.peek() {
+                            if c == ']' {
+                                break;
+                            }
+                            index.push(c);
+                            chars.next();
+                        }
+                        let index = index.trim().parse::<i32>().map_err(|_| {
+                            invalid_path(format!(
+                                "Invalid array index `{}` at position {}",
+                                index, position
+                            ))
+                        })?;
+
+                        PathSegment::Index(index)
+                    }
+                };
+                match chars.next() {
+                    Some((_, ']')) => {}
+                    _ => {
+                        return Err(invalid_path(format!(
+                            "Missing `]` for the `[` at position {}",
+                            position
+                        )))
+                    }
+                }
+                segments.push(segment);
+            }
+            c => {
+                return Err(invalid_path(format!(
+                    "Unexpected character `{}` at position {}",
+                    c, position
+                )))
+            }
+        }
+    }
+
+    Ok(segments)
+}
+
+/// Read a key in dot notation, which ends right before the next `.` or `[`.
+fn read_dotted_key(chars: &mut Peekable<CharIndices>) -> String {
+    let mut key = String::new();
+    while let Some(&(_, c)) = chars.peek() {
+        if c == '.' || c == '[' {
+            break;
+        }
+        key.push(c);
+        chars.next();
+    }
+
+    key
+}
+
+/// Read a key in bracket notation, after the opening quote and up to the closing one. Returns
+/// `None` if the closing quote is not found.
+fn read_quoted_key(chars: &mut Peekable<CharIndices>, quote: char) -> Option<String> {
+    let mut key = String::new();
+    while let Some((_, c)) = chars.next() {
+        match c {
+            '\\' => key.push(chars.next()?.1),
+            c if c == quote => return Some(key),
+            c => key.push(c),
+        }
+    }
+
+    None
+}
+
+/// Select the value found at the end of a path, as parsed by `parse_path`.
+pub fn select(input: RadonTypes, segments: &[PathSegment]) -> Result<RadonTypes, RadError> {
+    let mut current = input;
+    for segment in segments {
+        current = match (segment, current) {
+            (PathSegment::Key(key), RadonTypes::Map(map)) => map
+                .value()
+                .remove(key)
+                .ok_or_else(|| RadError::MapKeyNotFound { key: key.clone() })?,
+            (PathSegment::Index(index), RadonTypes::Array(array)) => {
+                let mut items = array.value();
+                let out_of_bounds = || RadError::ArrayIndexOutOfBounds { index: *index };
+                let position = if *index >= 0 {
+                    usize::try_from(*index).map_err(|_| out_of_bounds())?
+                } else {
+                    let from_end = usize::try_from(index.checked_neg().ok_or_else(out_of_bounds)?)
+                        .map_err(|_| out_of_bounds())?;
+                    items
+                        .len()
+                        .checked_sub(from_end)
+                        .ok_or_else(out_of_bounds)?
+                };
+                if position >= items.len() {
+                    return Err(out_of_bounds());
+                }
+
+                items.swap_remove(position)
+            }
+            (PathSegment::Key(_), other) => {
+                return Err(RadError::MismatchingTypes {
+                    method: "GetPath".to_string(),
+                    expected: RadonMap::radon_type_name(),
+                    found: other.radon_type_name(),
+                })
+            }
+            (PathSegment::Index(_), other) => {
+                return Err(RadError::MismatchingTypes {
+                    method: "GetPath".to_string(),
+                    expected: RadonArray::radon_type_name(),
+                    found: other.radon_type_name(),
+                })
+            }
+        };
+    }
+
+    Ok(current)
+}
+
+/// Get a value nested inside a `RadonMap` or `RadonArray` using a path expression, which is the
+/// only argument. See `parse_path` for the supported syntax.
+pub fn get_path(input: RadonTypes, args: &[Value]) -> Result<RadonTypes, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: input.radon_type_name(),
+        operator: "GetPath".to_string(),
+        args: args.to_vec(),
+    };
+
+    if args.len() != 1 {
+        return Err(wrong_args());
+    }
+
+    let path = from_value::<String>(args[0].to_owned()).map_err(|_| wrong_args())?;
+    let segments = parse_path(&path)?;
+
+    select(input, &segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::{
+        operators::string::parse_json,
+        types::{float::RadonFloat, integer::RadonInteger, string::RadonString},
+    };
+
+    fn prices() -> RadonTypes {
+        parse_json(&RadonString::from(
+            r#"{ "data": { "prices": [ { "usd": 1.5 }, { "usd": 2 } ], "a.b": "dotted" } }"#,
+        ))
+        .unwrap()
+    }
+
+    fn path(input: RadonTypes, path: &str) -> Result<RadonTypes, RadError> {
+        get_path(input, &[Value::Text(path.to_string())])
+    }
+
+    #[test]
+    fn test_parse_path() {
+        let expected = vec![
+            PathSegment::Key("data".to_string()),
+            PathSegment::Key("prices".to_string()),
+            PathSegment::Index(0),
+            PathSegment::Key("usd".to_string()),
+        ];
+
+        assert_eq!(parse_path("$.data.prices[0].usd"), Ok(expected.clone()));
+        assert_eq!(parse_path("data.prices[0].usd"), Ok(expected.clone()));
+        assert_eq!(parse_path("$['data'][\"prices\"][0]['usd']"), Ok(expected));
+        assert_eq!(
+            parse_path("$['it\\'s'][-1]"),
+            Ok(vec![
+                PathSegment::Key("it's".to_string()),
+                PathSegment::Index(-1)
+            ])
+        );
+        assert_eq!(parse_path("$"), Ok(vec![]));
+        assert_eq!(parse_path(""), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_parse_path_invalid() {
+        let invalid_path = |path: &str, description: &str| {
+            Err(RadError::InvalidPath {
+                path: path.to_string(),
+                description: description.to_string(),
+            })
+        };
+
+        assert_eq!(
+            parse_path("$.data..prices"),
+            invalid_path("$.data..prices", "Empty key after `.` at position 6")
+        );
+        assert_eq!(
+            parse_path("$.prices[a]"),
+            invalid_path("$.prices[a]", "Invalid array index `a` at position 8")
+        );
+        assert_eq!(
+            parse_path("$.prices[0"),
+            invalid_path("$.prices[0", "Missing `]` for the `[` at position 8")
+        );
+        assert_eq!(
+            parse_path("$['data"),
+            invalid_path("$['data", "Unterminated quoted key starting at position 1")
+        );
+        assert_eq!(
+            parse_path("$$"),
+            invalid_path("$$", "Unexpected character `$` at position 1")
+        );
+    }
+
+    #[test]
+    fn test_get_path() {
+        assert_eq!(
+            path(prices(), "$.data.prices[0].usd"),
+            Ok(RadonTypes::from(RadonFloat::from(1.5)))
+        );
+        assert_eq!(
+            path(prices(), "$.data.prices[-1].usd"),
+            Ok(RadonTypes::from(RadonInteger::from(2)))
+        );
+        assert_eq!(
+            path(prices(), "$.data['a.b']"),
+            Ok(RadonTypes::from(RadonString::from("dotted")))
+        );
+        assert_eq!(path(prices(), "$"), Ok(prices()));
+
+        let mut usd = BTreeMap::new();
+        usd.insert("usd".to_string(), RadonTypes::from(RadonInteger::from(2)));
+        assert_eq!(
+            path(prices(), "$.data.prices[1]"),
+            Ok(RadonTypes::from(RadonMap::from(usd)))
+        );
+    }
+
+    #[test]
+    fn test_get_path_errors() {
+        assert_eq!(
+            path(prices(), "$.data.price[0]"),
+            Err(RadError::MapKeyNotFound {
+                key: "price".to_string()
+            })
+        );
+        assert_eq!(
+            path(prices(), "$.data.prices[2].usd"),
+            Err(RadError::ArrayIndexOutOfBounds { index: 2 })
+        );
+        assert_eq!(
+            path(prices(), "$.data.prices[-3].usd"),
+            Err(RadError::ArrayIndexOutOfBounds { index: -3 })
+        );
+        assert_eq!(
+            path(prices(), "$.data[0]"),
+            Err(RadError::MismatchingTypes {
+                method: "GetPath".to_string(),
+                expected: RadonArray::radon_type_name(),
+                found: RadonMap::radon_type_name(),
+            })
+        );
+        assert_eq!(
+            path(prices(), "$.data.prices.usd"),
+            Err(RadError::MismatchingTypes {
+                method: "GetPath".to_string(),
+                expected: RadonMap::radon_type_name(),
+                found: RadonArray::radon_type_name(),
+            })
+        );
+        assert_eq!(
+            &get_path(prices(), &[Value::Integer(0)])
+                .unwrap_err()
+                .to_string(),
+            "Wrong `RadonMap::GetPath()` arguments: `[Integer(0)]`"
+        );
+    }
+}
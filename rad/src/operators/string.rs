@@ -1,12 +1,21 @@
+use chrono::{
+    format::{ParseResult, Parsed, StrftimeItems},
+    DateTime,
+};
+use num_enum::TryFromPrimitive;
+use regex::{Regex, RegexBuilder};
 use serde_cbor::value::{from_value, Value};
 use std::{
+    collections::BTreeMap,
     convert::{TryFrom, TryInto},
+    fmt,
     str::FromStr,
 };
 
 use crate::{
     error::RadError,
     hash_functions::{self, RadonHashFunctions},
+    operators::bytes::{encoding_from_args, RadonBytesEncoding},
     types::{
         array::RadonArray, boolean::RadonBoolean, bytes::RadonBytes, float::RadonFloat,
         integer::RadonInteger, map::RadonMap, string::RadonString, RadonType, RadonTypes,
@@ -35,6 +44,92 @@ pub fn parse_json_array(input: &RadonString) -> Result<RadonArray, RadError> {
     item.try_into()
 }
 
+/// Maximum nesting depth of the elements of an XML document that can be parsed by `parse_xml`.
+const XML_MAX_DEPTH: usize = 64;
+
+/// Parse an XML document into a `RadonMap` whose only key is the name of the root element.
+///
+/// Elements are mapped as follows:
+/// - An element with no attributes and no child elements becomes a `RadonString` with its text
+///   content (which is empty for elements such as `<a/>`).
+/// - Any other element becomes a `RadonMap`, where:
+///   - Attributes are stored as `RadonString`s under their name prefixed with `@`.
+///   - Child elements are stored under their tag name, always in a `RadonArray` with the values
+///     of all the child elements with that name, preserving the document order. This way the
+///     type of a child does not depend on how many times it appears in a given document.
+///   - The text content, if any, is stored as a `RadonString` under the `#text` key.
+///
+/// Text content is the concatenation of all the text and CDATA nodes directly inside the element,
+/// with leading and trailing whitespace removed. Namespace prefixes are ignored, and comments and
+/// processing instructions are skipped. No type inference is done, so numbers and booleans are
+/// kept as strings and need to be converted using operators such as `StringAsFloat`.
+pub fn parse_xml(input: &RadonString) -> Result<RadonMap, RadError> {
+    let value = input.value();
+    let document = roxmltree::Document::parse(&value).map_err(|xml_error| RadError::XmlParse {
+        description: xml_error.to_string(),
+    })?;
+    let root = document.root_element();
+
+    let mut map = BTreeMap::new();
+    map.insert(root.tag_name().name().to_string(), xml_to_radon(root, 0)?);
+
+    Ok(RadonMap::from(map))
+}
+
+/// Converts an XML element and all its descendants into `RadonTypes`, as described in
+/// `parse_xml`.
+fn xml_to_radon(node: roxmltree::Node, depth: usize) -> Result<RadonTypes, RadError> {
+    if depth >= XML_MAX_DEPTH {
+        return Err(RadError::XmlParse {
+            description: format!(
+                "Maximum nesting depth of {} elements exceeded",
+                XML_MAX_DEPTH
+            ),
+        });
+    }
+
+    let text: String = node
+        .children()
+        .filter(|child| child.is_text())
+        .filter_map(|child| child.text())
+        .collect();
+    let text = text.trim();
+
+    let has_child_elements = node.children().any(|child| child.is_element());
+    if node.attributes().is_empty() && !has_child_elements {
+        return Ok(RadonTypes::from(RadonString::from(text)));
+    }
+
+    let mut map: BTreeMap<String, RadonTypes> = BTreeMap::new();
+    for attribute in node.attributes() {
+        map.insert(
+            format!("@{}", attribute.name()),
+            RadonTypes::from(RadonString::from(attribute.value())),
+        );
+    }
+
+    // Collect the values of the child elements grouped by tag name, preserving their order
+    let mut children: BTreeMap<String, Vec<RadonTypes>> = BTreeMap::new();
+    for child in node.children().filter(|child| child.is_element()) {
+        children
+            .entry(child.tag_name().name().to_string())
+            .or_default()
+            .push(xml_to_radon(child, depth + 1)?);
+    }
+    for (name, values) in children {
+        map.insert(name, RadonTypes::from(RadonArray::from(values)));
+    }
+
+    if !text.is_empty() {
+        map.insert(
+            "#text".to_string(),
+            RadonTypes::from(RadonString::from(text)),
+        );
+    }
+
+    Ok(RadonTypes::from(RadonMap::from(map)))
+}
+
 pub fn radon_trim(input: &RadonString) -> String {
     if input.value().ends_with('\n') {
         input.value()[..input.value().len() - 1].to_string()
@@ -43,6 +138,33 @@ pub fn radon_trim(input: &RadonString) -> String {
     }
 }
 
+pub fn to_bytes(input: &RadonString, args: &[Value]) -> Result<RadonBytes, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonString::radon_type_name(),
+        operator: "AsBytes".to_string(),
+        args: args.to_vec(),
+    };
+
+    let encoding = encoding_from_args(args, wrong_args)?;
+    let string = input.value();
+    let invalid_encoding = |description: String| RadError::InvalidEncoding {
+        encoding: encoding.to_string(),
+        description,
+    };
+
+    let bytes = match encoding {
+        RadonBytesEncoding::Hex => {
+            hex::decode(&string).map_err(|e| invalid_encoding(e.to_string()))?
+        }
+        RadonBytesEncoding::Base64 => {
+            base64::decode(&string).map_err(|e| invalid_encoding(e.to_string()))?
+        }
+        RadonBytesEncoding::Utf8 => string.into_bytes(),
+    };
+
+    Ok(RadonBytes::from(bytes))
+}
+
 pub fn to_float(input: &RadonString) -> Result<RadonFloat, RadError> {
     let str_value = radon_trim(input);
     f64::from_str(&str_value)
@@ -98,9 +220,29 @@ pub fn hash(input: &RadonString, args: &[Value]) -> Result<RadonString, RadError
 }
 
 pub fn string_match(input: &RadonString, args: &[Value]) -> Result<RadonTypes, RadError> {
+    match_key(
+        &input.value(),
+        RadonString::radon_type_name(),
+        "String match",
+        args,
+    )
+}
+
+/// Look up a key in the map of categories given as the first argument, returning the default
+/// value given as the second argument if it is not found. The value found is converted into the
+/// same type as the default value.
+///
+/// This is shared by all the `Match` operators, which use the string representation of their
+/// input as the key.
+pub(crate) fn match_key(
+    key: &str,
+    input_type: &'static str,
+    operator: &str,
+    args: &[Value],
+) -> Result<RadonTypes, RadError> {
     let wrong_args = || RadError::WrongArguments {
-        input_type: RadonString::radon_type_name(),
-        operator: "String match".to_string(),
+        input_type,
+        operator: operator.to_string(),
         args: args.to_vec(),
     };
 
@@ -112,7 +254,7 @@ pub fn string_match(input: &RadonString, args: &[Value]) -> Result<RadonTypes, R
     let map_value = map.value();
 
     map_value
-        .get(&input.value())
+        .get(key)
         .map(|res| match default {
             RadonTypes::Array(_) => Ok(RadonTypes::from(RadonArray::try_from(res.clone())?)),
             RadonTypes::Boolean(_) => Ok(RadonTypes::from(RadonBoolean::try_from(res.clone())?)),
@@ -126,6 +268,222 @@ pub fn string_match(input: &RadonString, args: &[Value]) -> Result<RadonTypes, R
         .unwrap_or(Ok(temp_def))
 }
 
+/// Maximum size in bytes of a compiled regular expression, and also of the cache used by its lazy
+/// DFA. The regex engine guarantees linear time matching, and this limit keeps the cost of
+/// compiling and running a regular expression bounded.
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+/// Maximum size in bytes of the output of `RegexReplace`, which is the same as the default
+/// maximum size of the response of a retrieval. Without it, a short input with many matches and a
+/// long replacement could use an arbitrary amount of memory.
+const REGEX_REPLACE_MAX_OUTPUT_SIZE: usize = 10 * 1024 * 1024;
+
+pub(crate) fn compile_regex(pattern: &str) -> Result<Regex, RadError> {
+    RegexBuilder::new(pattern)
+        .size_limit(REGEX_SIZE_LIMIT)
+        .dfa_size_limit(REGEX_SIZE_LIMIT)
+        .build()
+        .map_err(|e| RadError::RegexCompile {
+            pattern: pattern.to_string(),
+            description: e.to_string(),
+        })
+}
+
+/// Read the arguments of the regex operators that take a pattern and an optional capture group
+/// index, which defaults to 0 (the whole match).
+fn regex_and_group(
+    args: &[Value],
+    wrong_args: impl Fn() -> RadError,
+) -> Result<(Regex, usize), RadError> {
+    let (pattern, group) = match args {
+        [pattern] => (pattern, 0),
+        [pattern, group] => {
+            let group = from_value::<u32>(group.to_owned()).map_err(|_| wrong_args())?;
+            (pattern, usize::try_from(group).map_err(|_| wrong_args())?)
+        }
+        _ => return Err(wrong_args()),
+    };
+    let pattern = from_value::<String>(pattern.to_owned()).map_err(|_| wrong_args())?;
+    let regex = compile_regex(&pattern)?;
+
+    if group >= regex.captures_len() {
+        return Err(wrong_args());
+    }
+
+    Ok((regex, group))
+}
+
+/// Find the first match of a regular expression and return the given capture group, or the whole
+/// match if no group is given. Fails with `RegexNoMatch` if there is no match or the group did not
+/// participate in the match.
+pub fn regex_capture(input: &RadonString, args: &[Value]) -> Result<RadonString, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonString::radon_type_name(),
+        operator: "RegexCapture".to_string(),
+        args: args.to_vec(),
+    };
+
+    let (regex, group) = regex_and_group(args, wrong_args)?;
+    let value = input.value();
+
+    regex
+        .captures(&value)
+        .and_then(|captures| captures.get(group))
+        .map(|m| RadonString::from(m.as_str()))
+        .ok_or_else(|| RadError::RegexNoMatch {
+            pattern: regex.as_str().to_string(),
+        })
+}
+
+/// Find all the non-overlapping matches of a regular expression and return an array with the
+/// given capture group of each of them, or the whole matches if no group is given. Matches in which
+/// the group did not participate are skipped.
+pub fn regex_find_all(input: &RadonString, args: &[Value]) -> Result<RadonArray, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonString::radon_type_name(),
+        operator: "RegexFindAll".to_string(),
+        args: args.to_vec(),
+    };
+
+    let (regex, group) = regex_and_group(args, wrong_args)?;
+    let value = input.value();
+
+    let matches: Vec<RadonTypes> = regex
+        .captures_iter(&value)
+        .filter_map(|captures| captures.get(group))
+        .map(|m| RadonTypes::from(RadonString::from(m.as_str())))
+        .collect();
+
+    Ok(RadonArray::from(matches))
+}
+
+/// Replace all the non-overlapping matches of a regular expression, given as first argument, with
+/// the replacement string given as second argument. The replacement can refer to capture groups
+/// using `$1` or `${name}`, while `$$` stands for a literal `$`.
+pub fn regex_replace(input: &RadonString, args: &[Value]) -> Result<RadonString, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonString::radon_type_name(),
+        operator: "RegexReplace".to_string(),
+        args: args.to_vec(),
+    };
+
+    let (pattern, replacement) = match args {
+        [Value::Text(pattern), Value::Text(replacement)] => (pattern, replacement),
+        _ => return Err(wrong_args()),
+    };
+    let regex = compile_regex(pattern)?;
+    let too_long = || RadError::RegexReplaceTooLong {
+        pattern: pattern.clone(),
+        max_size: REGEX_REPLACE_MAX_OUTPUT_SIZE,
+    };
+
+    // Same as `Regex::replace_all`, but checking the size of the output after every replacement
+    let input = input.value();
+    let mut output = String::new();
+    let mut last_match_end = 0;
+    for captures in regex.captures_iter(&input) {
+        // The capture group 0 is the whole match, which always exists
+        let whole_match = captures.get(0).unwrap();
+        output.push_str(&input[last_match_end..whole_match.start()]);
+        captures.expand(replacement, &mut output);
+        last_match_end = whole_match.end();
+
+        if output.len() > REGEX_REPLACE_MAX_OUTPUT_SIZE {
+            return Err(too_long());
+        }
+    }
+    output.push_str(&input[last_match_end..]);
+
+    if output.len() > REGEX_REPLACE_MAX_OUTPUT_SIZE {
+        return Err(too_long());
+    }
+
+    Ok(RadonString::from(output))
+}
+
+/// Formats of dates with a well-known name, which can be used as the argument of
+/// `StringParseDateTime` instead of a pattern.
+/// **WARNING: these codes are consensus-critical.** They can be renamed but they cannot be
+/// re-assigned without causing a non-backwards-compatible protocol upgrade.
+#[derive(Clone, Copy, Debug, PartialEq, TryFromPrimitive)]
+#[repr(u8)]
+pub enum RadonDateTimeFormat {
+    /// RFC 3339, the profile of ISO 8601 used by most APIs, e.g. `2020-06-01T12:00:00Z`
+    Rfc3339 = 0x00,
+    /// RFC 2822, as used in HTTP and email headers, e.g. `Mon, 01 Jun 2020 12:00:00 +0000`
+    Rfc2822 = 0x01,
+}
+
+impl fmt::Display for RadonDateTimeFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RadonDateTimeFormat::{:?}", self)
+    }
+}
+
+/// Parse a date into the number of seconds since the UNIX epoch.
+///
+/// The only argument is the format of the date: either a `RadonDateTimeFormat` code, or a
+/// `strftime`-like pattern such as `"%Y-%m-%d %H:%M"`. Dates parsed with a pattern are taken as
+/// UTC unless the pattern has a time zone offset, and as midnight if the pattern has no time.
+pub fn parse_date_time(input: &RadonString, args: &[Value]) -> Result<RadonInteger, RadError> {
+    let wrong_args = || RadError::WrongArguments {
+        input_type: RadonString::radon_type_name(),
+        operator: "ParseDateTime".to_string(),
+        args: args.to_vec(),
+    };
+
+    let value = input.value();
+    let (format, timestamp) = match args {
+        [Value::Text(pattern)] => (pattern.clone(), parse_with_pattern(&value, pattern)),
+        [arg] => {
+            let code = from_value::<u8>(arg.to_owned()).map_err(|_| wrong_args())?;
+            let format = RadonDateTimeFormat::try_from(code).map_err(|_| wrong_args())?;
+            let timestamp = match format {
+                RadonDateTimeFormat::Rfc3339 => DateTime::parse_from_rfc3339(&value),
+                RadonDateTimeFormat::Rfc2822 => DateTime::parse_from_rfc2822(&value),
+            }
+            .map(|date_time| date_time.timestamp());
+
+            (format.to_string(), timestamp)
+        }
+        _ => return Err(wrong_args()),
+    };
+
+    timestamp
+        .map(|timestamp| RadonInteger::from(i128::from(timestamp)))
+        .map_err(|e| {
+            log::debug!(
+                "Failed to parse {:?} as a date with format {:?}: {}",
+                value,
+                format,
+                e
+            );
+
+            RadError::ParseDateTime { format }
+        })
+}
+
+fn parse_with_pattern(value: &str, pattern: &str) -> ParseResult<i64> {
+    let mut parsed = Parsed::new();
+    chrono::format::parse(&mut parsed, value, StrftimeItems::new(pattern))?;
+
+    // Fill in the fields that the pattern may not have. A UNIX timestamp (`%s`) already sets
+    // the time, so it must not be overridden.
+    if parsed.timestamp.is_none() {
+        if parsed.hour_div_12.is_none() && parsed.hour_mod_12.is_none() {
+            parsed.set_hour(0)?;
+        }
+        if parsed.minute.is_none() {
+            parsed.set_minute(0)?;
+        }
+    }
+    if parsed.offset.is_none() {
+        parsed.set_offset(0)?;
+    }
+
+    parsed.to_datetime().map(|date_time| date_time.timestamp())
+}
+
 /// Converts a JSON value (`json::JsonValue`) into a CBOR value (`serde_cbor::value::Value`).
 /// Some conversions are totally straightforward, but some others  need some more logic (e.g.
 /// telling apart integers from floats).
@@ -263,6 +621,116 @@ mod tests {
         assert_eq!(output, expected_err);
     }
 
+    #[test]
+    fn test_parse_xml() {
+        let xml = RadonString::from(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <rates base="EUR">
+                <!-- Exchange rates -->
+                <rate currency="USD">1.1856</rate>
+                <rate currency="JPY">124.78</rate>
+                <date>2020-10-16</date>
+                <empty/>
+            </rates>"#,
+        );
+        let output = parse_xml(&xml).unwrap();
+
+        let rate = |currency: &str, value: &str| {
+            let mut rate = BTreeMap::new();
+            rate.insert(
+                "@currency".to_string(),
+                RadonTypes::from(RadonString::from(currency)),
+            );
+            rate.insert(
+                "#text".to_string(),
+                RadonTypes::from(RadonString::from(value)),
+            );
+            RadonTypes::from(RadonMap::from(rate))
+        };
+        let mut rates = BTreeMap::new();
+        rates.insert(
+            "@base".to_string(),
+            RadonTypes::from(RadonString::from("EUR")),
+        );
+        rates.insert(
+            "rate".to_string(),
+            RadonTypes::from(RadonArray::from(vec![
+                rate("USD", "1.1856"),
+                rate("JPY", "124.78"),
+            ])),
+        );
+        // Child elements are always arrays, even if they only appear once
+        rates.insert(
+            "date".to_string(),
+            RadonTypes::from(RadonArray::from(vec![RadonTypes::from(RadonString::from(
+                "2020-10-16",
+            ))])),
+        );
+        rates.insert(
+            "empty".to_string(),
+            RadonTypes::from(RadonArray::from(vec![RadonTypes::from(RadonString::from(
+                "",
+            ))])),
+        );
+        let mut expected_output = BTreeMap::new();
+        expected_output.insert("rates".to_string(), RadonTypes::from(RadonMap::from(rates)));
+
+        assert_eq!(output, RadonMap::from(expected_output));
+    }
+
+    #[test]
+    fn test_parse_xml_text_and_cdata() {
+        let xml = RadonString::from("<title>  Hello <![CDATA[<world>]]>  </title>");
+        let output = parse_xml(&xml).unwrap();
+
+        let mut expected_output = BTreeMap::new();
+        expected_output.insert(
+            "title".to_string(),
+            RadonTypes::from(RadonString::from("Hello <world>")),
+        );
+
+        assert_eq!(output, RadonMap::from(expected_output));
+    }
+
+    #[test]
+    fn test_parse_xml_fail() {
+        let invalid_xml = RadonString::from("<a><b></a>");
+        let output = parse_xml(&invalid_xml).unwrap_err();
+
+        let expected_err = RadError::XmlParse {
+            description: "expected 'b' tag, not 'a' at 1:7".to_string(),
+        };
+        assert_eq!(output, expected_err);
+
+        let not_xml = RadonString::from(r#"{ "Hello": "world" }"#);
+        assert!(matches!(
+            parse_xml(&not_xml).unwrap_err(),
+            RadError::XmlParse { .. }
+        ));
+    }
+
+    #[test]
+    fn test_parse_xml_max_depth() {
+        let deep_xml = RadonString::from(format!(
+            "{}{}",
+            "<a>".repeat(XML_MAX_DEPTH + 1),
+            "</a>".repeat(XML_MAX_DEPTH + 1)
+        ));
+        let output = parse_xml(&deep_xml).unwrap_err();
+
+        let expected_err = RadError::XmlParse {
+            description: "Maximum nesting depth of 64 elements exceeded".to_string(),
+        };
+        assert_eq!(output, expected_err);
+
+        let xml = RadonString::from(format!(
+            "{}{}",
+            "<a>".repeat(XML_MAX_DEPTH),
+            "</a>".repeat(XML_MAX_DEPTH)
+        ));
+        assert!(parse_xml(&xml).is_ok());
+    }
+
     #[test]
     fn test_hash() {
         let input = RadonString::from("Hello, World!");
@@ -288,6 +756,267 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_string_to_bytes() {
+        let expected = RadonBytes::from(b"Hello, World!".to_vec());
+
+        let input = RadonString::from("48656c6c6f2c20576f726c6421");
+        assert_eq!(to_bytes(&input, &[]).unwrap(), expected);
+        let input = RadonString::from("48656C6C6F2C20576F726C6421");
+        assert_eq!(
+            to_bytes(&input, &[Value::from(RadonBytesEncoding::Hex as u8)]).unwrap(),
+            expected
+        );
+
+        let input = RadonString::from("SGVsbG8sIFdvcmxkIQ==");
+        assert_eq!(
+            to_bytes(&input, &[Value::from(RadonBytesEncoding::Base64 as u8)]).unwrap(),
+            expected
+        );
+
+        let input = RadonString::from("Hello, World!");
+        assert_eq!(
+            to_bytes(&input, &[Value::from(RadonBytesEncoding::Utf8 as u8)]).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_string_to_bytes_fail() {
+        let input = RadonString::from("0x1234");
+        assert_eq!(
+            to_bytes(&input, &[]).unwrap_err(),
+            RadError::InvalidEncoding {
+                encoding: RadonBytesEncoding::Hex.to_string(),
+                description: "Invalid character 'x' at position 1".to_string(),
+            }
+        );
+
+        let input = RadonString::from("SGVsbG8*");
+        assert_eq!(
+            to_bytes(&input, &[Value::from(RadonBytesEncoding::Base64 as u8)]).unwrap_err(),
+            RadError::InvalidEncoding {
+                encoding: RadonBytesEncoding::Base64.to_string(),
+                description: "Invalid byte 42, offset 7.".to_string(),
+            }
+        );
+
+        assert_eq!(
+            &to_bytes(&input, &[Value::from(0xFF)])
+                .unwrap_err()
+                .to_string(),
+            "Wrong `RadonString::AsBytes()` arguments: `[Integer(255)]`"
+        );
+    }
+
+    #[test]
+    fn test_regex_capture() {
+        let input = RadonString::from(r#"<span class="price">1,234.56 USD</span>"#);
+        let pattern = Value::Text(r"([\d,]+)\.(\d+) (?P<currency>[A-Z]{3})".to_string());
+
+        let output = regex_capture(&input, &[pattern.clone()]).unwrap();
+        assert_eq!(output, RadonString::from("1,234.56 USD"));
+
+        let output = regex_capture(&input, &[pattern.clone(), Value::Integer(1)]).unwrap();
+        assert_eq!(output, RadonString::from("1,234"));
+
+        let output = regex_capture(&input, &[pattern.clone(), Value::Integer(3)]).unwrap();
+        assert_eq!(output, RadonString::from("USD"));
+
+        let args = [pattern, Value::Integer(4)];
+        let output = regex_capture(&input, &args);
+        assert_eq!(
+            output.unwrap_err(),
+            RadError::WrongArguments {
+                input_type: RadonString::radon_type_name(),
+                operator: "RegexCapture".to_string(),
+                args: args.to_vec(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_regex_capture_no_match() {
+        let input = RadonString::from("no numbers here");
+        let output = regex_capture(&input, &[Value::Text(r"\d+".to_string())]);
+
+        assert_eq!(
+            output.unwrap_err(),
+            RadError::RegexNoMatch {
+                pattern: r"\d+".to_string()
+            }
+        );
+
+        // The optional group does not participate in the match
+        let output = regex_capture(
+            &input,
+            &[
+                Value::Text("numbers( here)?(!)?".to_string()),
+                Value::Integer(2),
+            ],
+        );
+        assert_eq!(
+            output.unwrap_err(),
+            RadError::RegexNoMatch {
+                pattern: "numbers( here)?(!)?".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_regex_find_all() {
+        let input = RadonString::from("BTC=9500, ETH=250, XRP=");
+        let pattern = Value::Text(r"([A-Z]+)=(\d*)".to_string());
+
+        let output = regex_find_all(&input, &[pattern.clone(), Value::Integer(1)]).unwrap();
+        let expected = RadonArray::from(vec![
+            RadonString::from("BTC").into(),
+            RadonString::from("ETH").into(),
+            RadonString::from("XRP").into(),
+        ]);
+        assert_eq!(output, expected);
+
+        let output = regex_find_all(&input, &[pattern]).unwrap();
+        let expected = RadonArray::from(vec![
+            RadonString::from("BTC=9500").into(),
+            RadonString::from("ETH=250").into(),
+            RadonString::from("XRP=").into(),
+        ]);
+        assert_eq!(output, expected);
+
+        let output = regex_find_all(&input, &[Value::Text("DOGE".to_string())]).unwrap();
+        assert_eq!(output, RadonArray::from(vec![]));
+    }
+
+    #[test]
+    fn test_regex_replace() {
+        let input = RadonString::from("2020-10-16");
+        let args = [
+            Value::Text(r"(\d+)-(\d+)-(\d+)".to_string()),
+            Value::Text("$3/$2/$1".to_string()),
+        ];
+        let output = regex_replace(&input, &args).unwrap();
+        assert_eq!(output, RadonString::from("16/10/2020"));
+
+        let input = RadonString::from("1,234,567");
+        let args = [Value::Text(",".to_string()), Value::Text("".to_string())];
+        let output = regex_replace(&input, &args).unwrap();
+        assert_eq!(output, RadonString::from("1234567"));
+
+        let output = regex_replace(&input, &[Value::Text(",".to_string())]);
+        assert_eq!(
+            &output.unwrap_err().to_string(),
+            "Wrong `RadonString::RegexReplace()` arguments: `[Text(\",\")]`"
+        );
+    }
+
+    #[test]
+    fn test_regex_replace_too_long() {
+        // Every match is replaced with a string that is 16 KiB long, so the output would be 16 MiB
+        let input = RadonString::from("a".repeat(1024));
+        let args = [
+            Value::Text("a".to_string()),
+            Value::Text("b".repeat(16 * 1024)),
+        ];
+        let output = regex_replace(&input, &args).unwrap_err();
+        assert_eq!(
+            output,
+            RadError::RegexReplaceTooLong {
+                pattern: "a".to_string(),
+                max_size: REGEX_REPLACE_MAX_OUTPUT_SIZE,
+            }
+        );
+    }
+
+    #[test]
+    fn test_regex_invalid_pattern() {
+        let input = RadonString::from("Hello");
+        let output = regex_capture(&input, &[Value::Text("(unclosed".to_string())]);
+
+        match output.unwrap_err() {
+            RadError::RegexCompile { pattern, .. } => assert_eq!(pattern, "(unclosed"),
+            e => panic!("Unexpected error: {}", e),
+        }
+
+        // Patterns that compile into huge programs are rejected
+        let output = regex_capture(&input, &[Value::Text(r"(\w{100}){100}".to_string())]);
+
+        match output.unwrap_err() {
+            RadError::RegexCompile { pattern, .. } => assert_eq!(pattern, r"(\w{100}){100}"),
+            e => panic!("Unexpected error: {}", e),
+        }
+    }
+
+    #[test]
+    fn test_parse_date_time_known_formats() {
+        let rfc3339 = Value::Integer(RadonDateTimeFormat::Rfc3339 as i128);
+        let rfc2822 = Value::Integer(RadonDateTimeFormat::Rfc2822 as i128);
+
+        let input = RadonString::from("2020-06-01T12:00:00Z");
+        let output = parse_date_time(&input, &[rfc3339.clone()]).unwrap();
+        assert_eq!(output, RadonInteger::from(1_591_012_800));
+
+        let input = RadonString::from("2020-06-01T14:00:00.5+02:00");
+        let output = parse_date_time(&input, &[rfc3339]).unwrap();
+        assert_eq!(output, RadonInteger::from(1_591_012_800));
+
+        let input = RadonString::from("Mon, 01 Jun 2020 12:00:00 +0000");
+        let output = parse_date_time(&input, &[rfc2822.clone()]).unwrap();
+        assert_eq!(output, RadonInteger::from(1_591_012_800));
+
+        let input = RadonString::from("2020-06-01T12:00:00Z");
+        assert_eq!(
+            parse_date_time(&input, &[rfc2822]).unwrap_err(),
+            RadError::ParseDateTime {
+                format: "RadonDateTimeFormat::Rfc2822".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_date_time_pattern() {
+        let pattern = |pattern: &str| [Value::Text(pattern.to_string())];
+
+        let input = RadonString::from("2020-06-01 12:00");
+        let output = parse_date_time(&input, &pattern("%Y-%m-%d %H:%M")).unwrap();
+        assert_eq!(output, RadonInteger::from(1_591_012_800));
+
+        // Dates without time are taken as midnight
+        let input = RadonString::from("01/06/2020");
+        let output = parse_date_time(&input, &pattern("%d/%m/%Y")).unwrap();
+        assert_eq!(output, RadonInteger::from(1_590_969_600));
+
+        let input = RadonString::from("2020-06-01 14:00:00 +0200");
+        let output = parse_date_time(&input, &pattern("%Y-%m-%d %H:%M:%S %z")).unwrap();
+        assert_eq!(output, RadonInteger::from(1_591_012_800));
+
+        let input = RadonString::from("1591012800");
+        let output = parse_date_time(&input, &pattern("%s")).unwrap();
+        assert_eq!(output, RadonInteger::from(1_591_012_800));
+
+        let input = RadonString::from("June 2020");
+        assert!(matches!(
+            parse_date_time(&input, &pattern("%Y-%m-%d")),
+            Err(RadError::ParseDateTime { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_date_time_wrong_args() {
+        let input = RadonString::from("2020-06-01T12:00:00Z");
+
+        let output = parse_date_time(&input, &[Value::Integer(0xFF)]);
+        assert_eq!(
+            &output.unwrap_err().to_string(),
+            "Wrong `RadonString::ParseDateTime()` arguments: `[Integer(255)]`"
+        );
+
+        assert!(matches!(
+            parse_date_time(&input, &[]),
+            Err(RadError::WrongArguments { .. })
+        ));
+    }
+
     #[test]
     fn test_string_to_integer() {
         let rad_int = RadonInteger::from(10);
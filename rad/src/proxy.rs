@@ -0,0 +1,334 @@
+//! Support for sending the HTTP requests of retrievals through a proxy.
+
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use surf::{
+    http::Uri,
+    middleware::{Body, HttpClient, Request, Response},
+};
+use url::Url;
+
+use crate::error::RadError;
+
+/// Proxy schemes supported by `RetrievalProxy`.
+const PROXY_SCHEMES: &[&str] = &["http", "socks5", "socks5h"];
+
+/// A `surf` client that sends every request through a proxy.
+#[derive(Clone, Debug)]
+pub struct ProxyClient {
+    client: Arc<isahc::HttpClient>,
+}
+
+impl HttpClient for ProxyClient {
+    type Error = isahc::Error;
+
+    fn send(&self, req: Request) -> BoxFuture<'static, Result<Response, Self::Error>> {
+        let client = self.client.clone();
+
+        Box::pin(async move {
+            let (parts, body) = req.into_parts();
+            let body = isahc::Body::reader(body);
+            let req = surf::http::Request::from_parts(parts, body);
+
+            let res = client.send_async(req).await?;
+
+            let (parts, body) = res.into_parts();
+            let body = Body::from_reader(body);
+
+            Ok(surf::http::Response::from_parts(parts, body))
+        })
+    }
+}
+
+/// Proxy through which the HTTP requests of retrievals are sent.
+///
+/// HTTPS requests sent through an HTTP proxy are tunneled using the `CONNECT` method. With
+/// `socks5` the host names are resolved locally, while with `socks5h` they are resolved by the
+/// proxy.
+#[derive(Clone, Debug)]
+pub struct RetrievalProxy {
+    url: Url,
+    bypass: Vec<String>,
+    client: ProxyClient,
+}
+
+impl RetrievalProxy {
+    /// Create a proxy from its URL, e.g. `http://proxy.example.com:3128` or
+    /// `socks5h://127.0.0.1:1080`, along with optional credentials.
+    ///
+    /// Requests to the hosts in `bypass` are not sent through the proxy. Each of its entries
+    /// matches a host name and all of its subdomains, and `*` matches every host.
+    pub fn new(
+        url: &str,
+        username: Option<&str>,
+        password: Option<&str>,
+        bypass: Vec<String>,
+    ) -> Result<Self, RadError> {
+        let invalid_proxy = |message: String| RadError::InvalidProxy {
+            url: url.to_string(),
+            message,
+        };
+
+        let mut proxy_url = Url::parse(url).map_err(|e| invalid_proxy(e.to_string()))?;
+        if !PROXY_SCHEMES.contains(&proxy_url.scheme()) {
+            return Err(invalid_proxy(format!(
+                "unsupported scheme `{}`, expected one of {:?}",
+                proxy_url.scheme(),
+                PROXY_SCHEMES
+            )));
+        }
+        if proxy_url.host_str().is_none() {
+            return Err(invalid_proxy("missing host".to_string()));
+        }
+        // The credentials are percent-encoded into the URL, which is how curl expects them
+        if let Some(username) = username {
+            proxy_url
+                .set_username(username)
+                .map_err(|()| invalid_proxy("invalid username".to_string()))?;
+        }
+        if let Some(password) = password {
+            proxy_url
+                .set_password(Some(password))
+                .map_err(|()| invalid_proxy("invalid password".to_string()))?;
+        }
+
+        let uri: Uri = proxy_url
+            .as_str()
+            .parse()
+            .map_err(|e: surf::http::uri::InvalidUri| invalid_proxy(e.to_string()))?;
+        let client = isahc::HttpClient::builder()
+            .proxy(uri)
+            .build()
+            .map_err(|e| invalid_proxy(e.to_string()))?;
+
+        Ok(Self {
+            url: proxy_url,
+            bypass: bypass
+                .into_iter()
+                .map(|host| host.trim_start_matches('.').to_lowercase())
+                .collect(),
+            client: ProxyClient {
+                client: Arc::new(client),
+            },
+        })
+    }
+
+    /// Whether requests to this URL must be sent directly instead of through the proxy.
+    pub fn bypasses(&self, url: &Url) -> bool {
+        let host = match url.host_str() {
+            Some(host) => host.to_lowercase(),
+            None => return false,
+        };
+
+        self.bypass.iter().any(|entry| {
+            entry == "*"
+                || host == *entry
+                || (host.ends_with(entry.as_str())
+                    && host[..host.len() - entry.len()].ends_with('.'))
+        })
+    }
+
+    /// The client that sends requests through this proxy.
+    pub fn client(&self) -> ProxyClient {
+        self.client.clone()
+    }
+
+    /// The URL of the proxy, without the credentials.
+    pub fn url(&self) -> String {
+        let mut url = self.url.clone();
+        // Removing the credentials of a URL that has a host never fails
+        let _ = url.set_username("");
+        let _ = url.set_password(None);
+
+        url.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::{TcpListener, TcpStream},
+        thread,
+    };
+
+    use futures::executor::block_on;
+
+    use witnet_data_structures::chain::{RADRetrieve, RADType};
+
+    use super::*;
+    use crate::{
+        http_response,
+        transport::{RetrievalLimits, RetrievalResponse},
+    };
+
+    const RESPONSE: &[u8] = b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nHi";
+
+    /// Read the head of an HTTP request, up to the empty line.
+    fn read_request_head(stream: &mut TcpStream) -> String {
+        let mut head = Vec::new();
+        let mut byte = [0];
+        while !head.ends_with(b"\r\n\r\n") {
+            stream.read_exact(&mut byte).unwrap();
+            head.push(byte[0]);
+        }
+
+        String::from_utf8(head).unwrap()
+    }
+
+    /// Start a stand-in for a proxy that accepts a single connection, handling it with the given
+    /// function, and return its address along with a handle for the value returned by the
+    /// function.
+    fn serve_once<T, F>(handle: F) -> (String, thread::JoinHandle<T>)
+    where
+        T: Send + 'static,
+        F: FnOnce(TcpStream) -> T + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let join_handle = thread::spawn(move || handle(listener.accept().unwrap().0));
+
+        (address, join_handle)
+    }
+
+    fn retrieve_get(url: &str) -> RADRetrieve {
+        RADRetrieve {
+            kind: RADType::HttpGet,
+            url: url.to_string(),
+            ..RADRetrieve::default()
+        }
+    }
+
+    #[test]
+    fn test_proxy_invalid() {
+        assert!(matches!(
+            RetrievalProxy::new("ftp://127.0.0.1:21", None, None, vec![]),
+            Err(RadError::InvalidProxy { .. })
+        ));
+        assert!(matches!(
+            RetrievalProxy::new("not a url", None, None, vec![]),
+            Err(RadError::InvalidProxy { .. })
+        ));
+    }
+
+    #[test]
+    fn test_proxy_url_hides_credentials() {
+        let proxy =
+            RetrievalProxy::new("http://127.0.0.1:3128", Some("user"), Some("p@ss"), vec![])
+                .unwrap();
+
+        assert_eq!(proxy.url(), "http://127.0.0.1:3128/");
+    }
+
+    #[test]
+    fn test_proxy_bypass() {
+        let proxy = RetrievalProxy::new(
+            "http://127.0.0.1:3128",
+            None,
+            None,
+            vec!["localhost".to_string(), ".Internal.example".to_string()],
+        )
+        .unwrap();
+        let bypasses = |url| proxy.bypasses(&Url::parse(url).unwrap());
+
+        assert!(bypasses("http://localhost:8080/"));
+        assert!(bypasses("https://internal.example/"));
+        assert!(bypasses("https://api.internal.example/price"));
+        assert!(!bypasses("https://notinternal.example/"));
+        assert!(!bypasses("https://example.com/"));
+
+        let proxy = RetrievalProxy::new("http://127.0.0.1:3128", None, None, vec!["*".to_string()])
+            .unwrap();
+        assert!(proxy.bypasses(&Url::parse("https://example.com/").unwrap()));
+    }
+
+    #[test]
+    fn test_http_proxy() {
+        let (address, request_head) = serve_once(|mut stream| {
+            let head = read_request_head(&mut stream);
+            stream.write_all(RESPONSE).unwrap();
+
+            head
+        });
+        let proxy = RetrievalProxy::new(
+            &format!("http://{}", address),
+            Some("user"),
+            Some("secret"),
+            vec![],
+        )
+        .unwrap();
+
+        let response = block_on(http_response(
+            &retrieve_get("http://data.example/price"),
+            &RetrievalLimits::default(),
+            Some(&proxy),
+        ));
+        assert_eq!(response, Ok(RetrievalResponse::from("Hi")));
+
+        // The proxy receives the absolute URL and the credentials
+        let head = request_head.join().unwrap();
+        assert!(head.starts_with("GET http://data.example/price HTTP/1.1\r\n"));
+        let credentials = base64::encode("user:secret");
+        assert!(head.contains(&format!("Proxy-Authorization: Basic {}\r\n", credentials)));
+    }
+
+    #[test]
+    fn test_socks5_proxy() {
+        let (address, target) = serve_once(|mut stream| {
+            // Greeting: only the username and password method is accepted
+            let mut greeting = [0; 2];
+            stream.read_exact(&mut greeting).unwrap();
+            let mut methods = vec![0; usize::from(greeting[1])];
+            stream.read_exact(&mut methods).unwrap();
+            assert!(methods.contains(&2));
+            stream.write_all(&[5, 2]).unwrap();
+
+            // Username and password authentication
+            let mut auth = [0; 2];
+            stream.read_exact(&mut auth).unwrap();
+            let mut username = vec![0; usize::from(auth[1])];
+            stream.read_exact(&mut username).unwrap();
+            let mut password_len = [0];
+            stream.read_exact(&mut password_len).unwrap();
+            let mut password = vec![0; usize::from(password_len[0])];
+            stream.read_exact(&mut password).unwrap();
+            assert_eq!(
+                (&username[..], &password[..]),
+                (&b"user"[..], &b"secret"[..])
+            );
+            stream.write_all(&[1, 0]).unwrap();
+
+            // Connect request to a domain name, as the proxy resolves host names
+            let mut connect = [0; 5];
+            stream.read_exact(&mut connect).unwrap();
+            assert_eq!(connect[..4], [5, 1, 0, 3]);
+            let mut host = vec![0; usize::from(connect[4])];
+            stream.read_exact(&mut host).unwrap();
+            let mut port = [0; 2];
+            stream.read_exact(&mut port).unwrap();
+            stream.write_all(&[5, 0, 0, 1, 0, 0, 0, 0, 0, 0]).unwrap();
+
+            // Then the connection is used as if it was the target host
+            read_request_head(&mut stream);
+            stream.write_all(RESPONSE).unwrap();
+
+            (String::from_utf8(host).unwrap(), u16::from_be_bytes(port))
+        });
+        let proxy = RetrievalProxy::new(
+            &format!("socks5h://{}", address),
+            Some("user"),
+            Some("secret"),
+            vec![],
+        )
+        .unwrap();
+
+        let response = block_on(http_response(
+            &retrieve_get("http://data.example:8080/price"),
+            &RetrievalLimits::default(),
+            Some(&proxy),
+        ));
+        assert_eq!(response, Ok(RetrievalResponse::from("Hi")));
+        assert_eq!(target.join().unwrap(), ("data.example".to_string(), 8080));
+    }
+}
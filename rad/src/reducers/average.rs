@@ -3,7 +3,7 @@ use std::ops::Div;
 use crate::{
     error::RadError,
     operators::{array as array_operators, float as float_operators},
-    reducers::RadonReducers,
+    reducers::{min_max::compare_floats, RadonReducers},
     types::{array::RadonArray, float::RadonFloat, integer::RadonInteger, RadonType, RadonTypes},
 };
 
@@ -125,6 +125,273 @@ pub fn mean(input: &RadonArray, return_policy: MeanReturnPolicy) -> Result<Radon
     }
 }
 
+/// Computes the median of the values found in a `RadonArray`.
+///
+/// Please note that `input` is assumed to be homogeneus.
+///
+/// When the number of values is even, the median is the average mean of the two central values.
+/// For `Integer` inputs that average is rounded half away from zero, so that the reducer contract
+/// `Fn(Array<T>) -> T` is satisfied, the same way as `AverageMean` does.
+///
+/// # Examples
+///
+/// ```rust
+/// use witnet_rad::{
+///     reducers::average::median,
+///     types::{array::RadonArray, float::RadonFloat, RadonTypes},
+/// };
+///
+/// let values = RadonArray::from(vec![
+///     RadonTypes::Float(RadonFloat::from(1.0)),
+///     RadonTypes::Float(RadonFloat::from(1000.0)),
+///     RadonTypes::Float(RadonFloat::from(3.0)),
+/// ]);
+///
+/// assert_eq!(median(&values), Ok(RadonTypes::Float(RadonFloat::from(3.0))));
+/// ```
+pub fn median(input: &RadonArray) -> Result<RadonTypes, RadError> {
+    let value = input.value();
+
+    match value.first() {
+        None => Ok(RadonTypes::from(RadonFloat::from(std::f64::NAN))),
+        Some(RadonTypes::Float(_)) => {
+            let mut floats = value
+                .iter()
+                .map(|item| match item {
+                    RadonTypes::Float(f64_value) => Ok(f64_value.value()),
+                    _ => Err(RadError::MismatchingTypes {
+                        method: RadonReducers::AverageMedian.to_string(),
+                        expected: RadonFloat::radon_type_name(),
+                        found: item.clone().radon_type_name(),
+                    }),
+                })
+                .collect::<Result<Vec<f64>, RadError>>()?;
+            floats.sort_by(|a, b| compare_floats(*a, *b));
+
+            let middle = floats.len() / 2;
+            let median_value = if floats.len() % 2 == 0 {
+                (floats[middle - 1] + floats[middle]) / 2.0
+            } else {
+                floats[middle]
+            };
+
+            Ok(RadonTypes::from(RadonFloat::from(median_value)))
+        }
+        Some(RadonTypes::Integer(_)) => {
+            let mut integers = value
+                .iter()
+                .map(|item| match item {
+                    RadonTypes::Integer(i128_value) => Ok(i128_value.value()),
+                    _ => Err(RadError::MismatchingTypes {
+                        method: RadonReducers::AverageMedian.to_string(),
+                        expected: RadonInteger::radon_type_name(),
+                        found: item.clone().radon_type_name(),
+                    }),
+                })
+                .collect::<Result<Vec<i128>, RadError>>()?;
+            integers.sort_unstable();
+
+            let middle = integers.len() / 2;
+            let median_value = if integers.len() % 2 == 0 {
+                integer_midpoint(integers[middle - 1], integers[middle])?
+            } else {
+                integers[middle]
+            };
+
+            Ok(RadonTypes::from(RadonInteger::from(median_value)))
+        }
+        Some(RadonTypes::Array(_)) => {
+            let v = array_operators::transpose(input)?;
+
+            let mut median_v = vec![];
+            for v2median in v.value() {
+                if let RadonTypes::Array(v2median) = v2median {
+                    median_v.push(median(&v2median)?);
+                } else {
+                    unreachable!()
+                }
+            }
+
+            Ok(RadonTypes::from(RadonArray::from(median_v)))
+        }
+        Some(_rad_types) => Err(RadError::UnsupportedReducer {
+            array: input.clone(),
+            reducer: RadonReducers::AverageMedian.to_string(),
+        }),
+    }
+}
+
+/// Average of two integers, rounded half away from zero.
+fn integer_midpoint(a: i128, b: i128) -> Result<i128, RadError> {
+    let sum = a.checked_add(b).ok_or(RadError::Overflow)?;
+
+    Ok(if sum % 2 == 0 {
+        sum / 2
+    } else {
+        // `sum` is odd, so `sum + signum` cannot overflow
+        (sum + sum.signum()) / 2
+    })
+}
+
+/// Splits the `[value, weight]` pairs that weighted reducers operate on.
+///
+/// Values must be all `Integer` or all `Float`, while weights can be either `Integer` or `Float`
+/// but must never be negative. The sum of all the weights must be greater than zero.
+// FIXME: Allow for now, since there is no safe cast function from an i128 to float yet
+#[allow(clippy::cast_precision_loss)]
+fn weighted_pairs(
+    input: &RadonArray,
+    reducer: RadonReducers,
+) -> Result<Vec<(RadonTypes, f64)>, RadError> {
+    let unsupported = || RadError::UnsupportedReducer {
+        array: input.clone(),
+        reducer: reducer.to_string(),
+    };
+
+    let pairs = input
+        .value()
+        .into_iter()
+        .map(|item| {
+            let pair = match item {
+                RadonTypes::Array(pair) => pair.value(),
+                _ => return Err(unsupported()),
+            };
+            let (value, weight) = match pair.as_slice() {
+                [value, weight] => (value.clone(), weight),
+                _ => return Err(unsupported()),
+            };
+            match value {
+                RadonTypes::Integer(_) | RadonTypes::Float(_) => {}
+                _ => return Err(unsupported()),
+            }
+            let weight = match weight {
+                RadonTypes::Integer(i128_weight) => i128_weight.value() as f64,
+                RadonTypes::Float(f64_weight) => f64_weight.value(),
+                _ => return Err(unsupported()),
+            };
+            if weight.is_nan() || weight < 0.0 {
+                return Err(RadError::InvalidWeight { weight });
+            }
+
+            Ok((value, weight))
+        })
+        .collect::<Result<Vec<_>, RadError>>()?;
+
+    if let Some((first, _)) = pairs.first() {
+        if let Some((other, _)) = pairs
+            .iter()
+            .find(|(value, _)| value.discriminant() != first.discriminant())
+        {
+            return Err(RadError::MismatchingTypes {
+                method: reducer.to_string(),
+                expected: first.clone().radon_type_name(),
+                found: other.clone().radon_type_name(),
+            });
+        }
+    }
+
+    let total_weight: f64 = pairs.iter().map(|(_, weight)| weight).sum();
+    if !pairs.is_empty() && total_weight <= 0.0 {
+        return Err(RadError::DivisionByZero);
+    }
+
+    Ok(pairs)
+}
+
+/// Extracts the numeric value of an `Integer` or `Float`.
+// FIXME: Allow for now, since there is no safe cast function from an i128 to float yet
+#[allow(clippy::cast_precision_loss)]
+fn numeric_value(value: &RadonTypes) -> f64 {
+    match value {
+        RadonTypes::Integer(i128_value) => i128_value.value() as f64,
+        RadonTypes::Float(f64_value) => f64_value.value(),
+        _ => unreachable!(),
+    }
+}
+
+/// Computes the weighted average mean of a `RadonArray` of `[value, weight]` pairs.
+///
+/// Values must be all `Integer` or all `Float`, and weights must be non-negative `Integer` or
+/// `Float`. As with `AverageMean`, the result is rounded when the values are `Integer`.
+///
+/// # Examples
+///
+/// ```rust
+/// use witnet_rad::{
+///     reducers::average::mean_weighted,
+///     types::{array::RadonArray, float::RadonFloat, integer::RadonInteger, RadonTypes},
+/// };
+///
+/// let pair = |value: f64, weight: i128| {
+///     RadonTypes::from(RadonArray::from(vec![
+///         RadonTypes::Float(RadonFloat::from(value)),
+///         RadonTypes::Integer(RadonInteger::from(weight)),
+///     ]))
+/// };
+/// let values = RadonArray::from(vec![pair(1.0, 3), pair(5.0, 1)]);
+///
+/// assert_eq!(mean_weighted(&values), Ok(RadonTypes::Float(RadonFloat::from(2.0))));
+/// ```
+pub fn mean_weighted(input: &RadonArray) -> Result<RadonTypes, RadError> {
+    let pairs = weighted_pairs(input, RadonReducers::AverageMeanWeighted)?;
+
+    let (weighted_sum, total_weight) = pairs
+        .iter()
+        .fold((0f64, 0f64), |(sum, total), (value, weight)| {
+            (sum + numeric_value(value) * weight, total + weight)
+        });
+    let float_mean = RadonFloat::from(weighted_sum.div(total_weight));
+
+    match pairs.first() {
+        None => Ok(RadonTypes::from(RadonFloat::from(std::f64::NAN))),
+        Some((RadonTypes::Integer(_), _)) => {
+            Ok(RadonTypes::from(float_operators::round(&float_mean)))
+        }
+        Some(_) => Ok(RadonTypes::from(float_mean)),
+    }
+}
+
+/// Computes the weighted median of a `RadonArray` of `[value, weight]` pairs.
+///
+/// Values are sorted and the median is the first value at which the accumulated weight reaches
+/// half of the total weight. If the accumulated weight is exactly half of the total weight, the
+/// median is the average of that value and the next one, so that using equal weights produces
+/// the same result as `AverageMedian`.
+// Comparing the accumulated weight for exact equality is intended here
+#[allow(clippy::float_cmp)]
+pub fn median_weighted(input: &RadonArray) -> Result<RadonTypes, RadError> {
+    let mut pairs = weighted_pairs(input, RadonReducers::AverageMedianWeighted)?;
+    // Values with zero weight must not affect the result
+    pairs.retain(|(_, weight)| *weight > 0.0);
+    pairs.sort_by(|(a, _), (b, _)| match (a, b) {
+        (RadonTypes::Integer(a), RadonTypes::Integer(b)) => a.value().cmp(&b.value()),
+        (a, b) => compare_floats(numeric_value(a), numeric_value(b)),
+    });
+
+    let half_weight = pairs.iter().map(|(_, weight)| weight).sum::<f64>() / 2.0;
+    let mut accumulated_weight = 0f64;
+    for (i, (value, weight)) in pairs.iter().enumerate() {
+        accumulated_weight += weight;
+        if accumulated_weight < half_weight {
+            continue;
+        }
+
+        return match pairs.get(i + 1) {
+            Some((next, _)) if accumulated_weight == half_weight => match (value, next) {
+                (RadonTypes::Integer(a), RadonTypes::Integer(b)) => Ok(RadonTypes::from(
+                    RadonInteger::from(integer_midpoint(a.value(), b.value())?),
+                )),
+                (a, b) => Ok(RadonTypes::from(RadonFloat::from(
+                    (numeric_value(a) + numeric_value(b)) / 2.0,
+                ))),
+            },
+            _ => Ok(value.clone()),
+        };
+    }
+
+    Ok(RadonTypes::from(RadonFloat::from(std::f64::NAN)))
+}
+
 #[cfg(test)]
 mod tests {
     use serde_cbor::Value;
@@ -333,4 +600,201 @@ mod tests {
 
         assert_eq!(output, expected);
     }
+
+    #[test]
+    fn test_reduce_average_median_float() {
+        let input = &RadonArray::from(vec![
+            RadonFloat::from(3f64).into(),
+            RadonFloat::from(1f64).into(),
+            RadonFloat::from(1000f64).into(),
+            RadonFloat::from(2f64).into(),
+        ]);
+        let args = &[Value::Integer(RadonReducers::AverageMedian as i128)];
+        let expected = RadonTypes::from(RadonFloat::from(2.5f64));
+
+        let output = reduce(input, args).unwrap();
+
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_reduce_average_median_integer() {
+        let input = &RadonArray::from(vec![
+            RadonInteger::from(5i128).into(),
+            RadonInteger::from(-100i128).into(),
+            RadonInteger::from(4i128).into(),
+        ]);
+        assert_eq!(
+            median(input).unwrap(),
+            RadonTypes::from(RadonInteger::from(4i128))
+        );
+
+        // Even number of values, the average is rounded half away from zero
+        let input = &RadonArray::from(vec![
+            RadonInteger::from(1i128).into(),
+            RadonInteger::from(2i128).into(),
+        ]);
+        assert_eq!(
+            median(input).unwrap(),
+            RadonTypes::from(RadonInteger::from(2i128))
+        );
+        let input = &RadonArray::from(vec![
+            RadonInteger::from(-1i128).into(),
+            RadonInteger::from(-2i128).into(),
+        ]);
+        assert_eq!(
+            median(input).unwrap(),
+            RadonTypes::from(RadonInteger::from(-2i128))
+        );
+    }
+
+    #[test]
+    fn test_reduce_average_median_integer_overflow() {
+        let input = &RadonArray::from(vec![
+            RadonInteger::from(i128::MAX).into(),
+            RadonInteger::from(i128::MAX).into(),
+        ]);
+        assert_eq!(median(input).unwrap_err(), RadError::Overflow);
+    }
+
+    #[test]
+    fn test_reduce_average_median_arrays() {
+        let array_1 = RadonTypes::from(RadonArray::from(vec![
+            RadonFloat::from(1f64).into(),
+            RadonFloat::from(2f64).into(),
+        ]));
+        let array_2 = RadonTypes::from(RadonArray::from(vec![
+            RadonFloat::from(6f64).into(),
+            RadonFloat::from(10f64).into(),
+        ]));
+        let array_3 = RadonTypes::from(RadonArray::from(vec![
+            RadonFloat::from(7f64).into(),
+            RadonFloat::from(3f64).into(),
+        ]));
+        let input = RadonArray::from(vec![array_1, array_2, array_3]);
+
+        let expected = RadonTypes::from(RadonArray::from(vec![
+            RadonFloat::from(6f64).into(),
+            RadonFloat::from(3f64).into(),
+        ]));
+
+        assert_eq!(median(&input).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_reduce_average_median_empty() {
+        let input = RadonArray::from(vec![]);
+        let output = median(&input).unwrap();
+        assert_eq!(output, RadonTypes::from(RadonFloat::from(std::f64::NAN)));
+    }
+
+    fn weighted(value: RadonTypes, weight: RadonTypes) -> RadonTypes {
+        RadonTypes::from(RadonArray::from(vec![value, weight]))
+    }
+
+    #[test]
+    fn test_reduce_average_mean_weighted() {
+        let input = &RadonArray::from(vec![
+            weighted(RadonFloat::from(1f64).into(), RadonInteger::from(3).into()),
+            weighted(RadonFloat::from(5f64).into(), RadonFloat::from(1f64).into()),
+        ]);
+        let args = &[Value::Integer(RadonReducers::AverageMeanWeighted as i128)];
+        let expected = RadonTypes::from(RadonFloat::from(2f64));
+
+        let output = reduce(input, args).unwrap();
+
+        assert_eq!(output, expected);
+
+        let input = &RadonArray::from(vec![
+            weighted(RadonInteger::from(1).into(), RadonInteger::from(1).into()),
+            weighted(RadonInteger::from(4).into(), RadonInteger::from(2).into()),
+        ]);
+        assert_eq!(
+            mean_weighted(input).unwrap(),
+            RadonTypes::from(RadonInteger::from(3))
+        );
+    }
+
+    #[test]
+    fn test_reduce_average_mean_weighted_invalid() {
+        let input = RadonArray::from(vec![
+            RadonFloat::from(1f64).into(),
+            RadonFloat::from(5f64).into(),
+        ]);
+        assert_eq!(
+            mean_weighted(&input).unwrap_err(),
+            RadError::UnsupportedReducer {
+                array: input,
+                reducer: RadonReducers::AverageMeanWeighted.to_string(),
+            }
+        );
+
+        let input = RadonArray::from(vec![weighted(
+            RadonFloat::from(1f64).into(),
+            RadonFloat::from(-1f64).into(),
+        )]);
+        assert_eq!(
+            mean_weighted(&input).unwrap_err(),
+            RadError::InvalidWeight { weight: -1f64 }
+        );
+
+        let input = RadonArray::from(vec![
+            weighted(RadonFloat::from(1f64).into(), RadonInteger::from(0).into()),
+            weighted(RadonFloat::from(2f64).into(), RadonInteger::from(0).into()),
+        ]);
+        assert_eq!(mean_weighted(&input).unwrap_err(), RadError::DivisionByZero);
+
+        let input = RadonArray::from(vec![
+            weighted(RadonFloat::from(1f64).into(), RadonInteger::from(1).into()),
+            weighted(RadonInteger::from(2).into(), RadonInteger::from(1).into()),
+        ]);
+        assert_eq!(
+            mean_weighted(&input).unwrap_err(),
+            RadError::MismatchingTypes {
+                method: RadonReducers::AverageMeanWeighted.to_string(),
+                expected: RadonFloat::radon_type_name(),
+                found: RadonInteger::radon_type_name(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_reduce_average_median_weighted() {
+        let input = &RadonArray::from(vec![
+            weighted(RadonFloat::from(1f64).into(), RadonInteger::from(1).into()),
+            weighted(
+                RadonFloat::from(100f64).into(),
+                RadonInteger::from(1).into(),
+            ),
+            weighted(RadonFloat::from(3f64).into(), RadonInteger::from(5).into()),
+        ]);
+        let args = &[Value::Integer(RadonReducers::AverageMedianWeighted as i128)];
+        let expected = RadonTypes::from(RadonFloat::from(3f64));
+
+        let output = reduce(input, args).unwrap();
+
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_reduce_average_median_weighted_equal_weights() {
+        // With equal weights, the result is the same as the one of the median
+        let values = vec![
+            RadonInteger::from(7).into(),
+            RadonInteger::from(2).into(),
+            RadonInteger::from(4).into(),
+            RadonInteger::from(1).into(),
+        ];
+        let input = RadonArray::from(
+            values
+                .iter()
+                .cloned()
+                .map(|value| weighted(value, RadonInteger::from(2).into()))
+                .collect::<Vec<_>>(),
+        );
+
+        let expected = RadonTypes::from(RadonInteger::from(3));
+        assert_eq!(median_weighted(&input).unwrap(), expected);
+        assert_eq!(median(&RadonArray::from(values)).unwrap(), expected);
+    }
 }
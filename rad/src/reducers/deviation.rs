@@ -3,9 +3,10 @@ use crate::{
     operators::array::transpose,
     reducers::{
         average::{mean, MeanReturnPolicy},
+        min_max::compare_floats,
         RadonReducers,
     },
-    types::{array::RadonArray, float::RadonFloat, RadonType, RadonTypes},
+    types::{array::RadonArray, float::RadonFloat, integer::RadonInteger, RadonType, RadonTypes},
 };
 use std::ops::Div;
 
@@ -95,6 +96,128 @@ pub fn standard(input: &RadonArray) -> Result<RadonTypes, RadError> {
     }
 }
 
+/// Extracts the numeric values of a homogeneous `Integer` or `Float` array.
+// FIXME: Allow for now, since there is no safe cast function from an i128 to float yet
+#[allow(clippy::cast_precision_loss)]
+fn numeric_values(input: &RadonArray, reducer: RadonReducers) -> Result<Vec<f64>, RadError> {
+    let value = input.value();
+
+    match value.first() {
+        Some(RadonTypes::Float(_)) => value
+            .iter()
+            .map(|item| match item {
+                RadonTypes::Float(f64_value) => Ok(f64_value.value()),
+                _ => Err(RadError::MismatchingTypes {
+                    method: reducer.to_string(),
+                    expected: RadonFloat::radon_type_name(),
+                    found: item.clone().radon_type_name(),
+                }),
+            })
+            .collect(),
+        Some(RadonTypes::Integer(_)) => value
+            .iter()
+            .map(|item| match item {
+                RadonTypes::Integer(i128_value) => Ok(i128_value.value() as f64),
+                _ => Err(RadError::MismatchingTypes {
+                    method: reducer.to_string(),
+                    expected: RadonInteger::radon_type_name(),
+                    found: item.clone().radon_type_name(),
+                }),
+            })
+            .collect(),
+        _ => Err(RadError::UnsupportedReducer {
+            array: input.clone(),
+            reducer: reducer.to_string(),
+        }),
+    }
+}
+
+/// Average mean of a list of floats.
+// FIXME: Allow for now, since there is no safe cast function from a usize to float yet
+#[allow(clippy::cast_precision_loss)]
+fn mean_of(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>().div(values.len() as f64)
+}
+
+/// Median of a list of floats.
+pub(crate) fn median_of(values: &[f64]) -> f64 {
+    let mut values = values.to_vec();
+    values.sort_by(|a, b| compare_floats(*a, *b));
+
+    let middle = values.len() / 2;
+    if values.len() % 2 == 0 {
+        (values[middle - 1] + values[middle]) / 2.0
+    } else {
+        values[middle]
+    }
+}
+
+/// Applies an absolute deviation function to the input, transposing arrays of arrays if needed.
+fn absolute_deviation<F>(
+    input: &RadonArray,
+    reducer: RadonReducers,
+    deviation: F,
+) -> Result<RadonTypes, RadError>
+where
+    F: Fn(&[f64]) -> f64 + Copy,
+{
+    match input.value().first() {
+        None => Ok(RadonTypes::from(RadonFloat::from(std::f64::NAN))),
+        Some(RadonTypes::Array(_)) => {
+            let v = transpose(input)?;
+
+            let mut deviation_v = vec![];
+            for v2dev in v.value() {
+                if let RadonTypes::Array(v2dev) = v2dev {
+                    deviation_v.push(absolute_deviation(&v2dev, reducer, deviation)?);
+                } else {
+                    unreachable!()
+                }
+            }
+
+            Ok(RadonTypes::from(RadonArray::from(deviation_v)))
+        }
+        Some(_) => {
+            let values = numeric_values(input, reducer)?;
+
+            Ok(RadonTypes::from(RadonFloat::from(deviation(&values))))
+        }
+    }
+}
+
+/// Average absolute deviation: the average mean of the absolute distances to the average mean.
+pub fn average_absolute(input: &RadonArray) -> Result<RadonTypes, RadError> {
+    absolute_deviation(input, RadonReducers::DeviationAverageAbsolute, |values| {
+        let center = mean_of(values);
+        let distances: Vec<f64> = values.iter().map(|x| (x - center).abs()).collect();
+
+        mean_of(&distances)
+    })
+}
+
+/// Median absolute deviation: the median of the absolute distances to the median.
+pub fn median_absolute(input: &RadonArray) -> Result<RadonTypes, RadError> {
+    absolute_deviation(input, RadonReducers::DeviationMedianAbsolute, |values| {
+        let center = median_of(values);
+        let distances: Vec<f64> = values.iter().map(|x| (x - center).abs()).collect();
+
+        median_of(&distances)
+    })
+}
+
+/// Maximum absolute deviation: the greatest of the absolute distances to the average mean.
+pub fn maximum_absolute(input: &RadonArray) -> Result<RadonTypes, RadError> {
+    absolute_deviation(input, RadonReducers::DeviationMaximumAbsolute, |values| {
+        let center = mean_of(values);
+
+        values
+            .iter()
+            .map(|x| (x - center).abs())
+            .max_by(|a, b| compare_floats(*a, *b))
+            .unwrap_or(std::f64::NAN)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -318,4 +441,102 @@ mod tests {
 
         assert_eq!(result.unwrap_err(), expected);
     }
+
+    #[test]
+    fn test_reduce_deviation_average_absolute() {
+        let input = &RadonArray::from(vec![
+            RadonInteger::from(1).into(),
+            RadonInteger::from(2).into(),
+            RadonInteger::from(3).into(),
+            RadonInteger::from(6).into(),
+        ]);
+        // Mean is 3, distances are [2, 1, 0, 3]
+        let expected = RadonTypes::from(RadonFloat::from(1.5));
+
+        let output = average_absolute(input).unwrap();
+
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_reduce_deviation_median_absolute() {
+        let input = &RadonArray::from(vec![
+            RadonFloat::from(1f64).into(),
+            RadonFloat::from(1f64).into(),
+            RadonFloat::from(2f64).into(),
+            RadonFloat::from(2f64).into(),
+            RadonFloat::from(4f64).into(),
+            RadonFloat::from(6f64).into(),
+            RadonFloat::from(9f64).into(),
+        ]);
+        // Median is 2, distances are [1, 1, 0, 0, 2, 4, 7]
+        let expected = RadonTypes::from(RadonFloat::from(1f64));
+
+        let output = median_absolute(input).unwrap();
+
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_reduce_deviation_maximum_absolute() {
+        let input = &RadonArray::from(vec![
+            RadonFloat::from(1f64).into(),
+            RadonFloat::from(2f64).into(),
+            RadonFloat::from(9f64).into(),
+        ]);
+        // Mean is 4, distances are [3, 2, 5]
+        let expected = RadonTypes::from(RadonFloat::from(5f64));
+
+        let output = maximum_absolute(input).unwrap();
+
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_reduce_deviation_absolute_arrays() {
+        let array_1 = RadonTypes::from(RadonArray::from(vec![
+            RadonFloat::from(1f64).into(),
+            RadonFloat::from(10f64).into(),
+        ]));
+        let array_2 = RadonTypes::from(RadonArray::from(vec![
+            RadonFloat::from(3f64).into(),
+            RadonFloat::from(10f64).into(),
+        ]));
+        let input = RadonArray::from(vec![array_1, array_2]);
+
+        let expected = RadonTypes::from(RadonArray::from(vec![
+            RadonFloat::from(1f64).into(),
+            RadonFloat::from(0f64).into(),
+        ]));
+
+        assert_eq!(average_absolute(&input).unwrap(), expected);
+        assert_eq!(median_absolute(&input).unwrap(), expected);
+        assert_eq!(maximum_absolute(&input).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_reduce_deviation_absolute_string_unsupported() {
+        let input = RadonArray::from(vec![
+            RadonString::from("Hello").into(),
+            RadonString::from("world").into(),
+        ]);
+
+        assert_eq!(
+            median_absolute(&input).unwrap_err(),
+            RadError::UnsupportedReducer {
+                array: input,
+                reducer: RadonReducers::DeviationMedianAbsolute.to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_reduce_deviation_absolute_empty() {
+        let input = RadonArray::from(vec![]);
+        let nan = RadonTypes::from(RadonFloat::from(std::f64::NAN));
+
+        assert_eq!(average_absolute(&input).unwrap(), nan);
+        assert_eq!(median_absolute(&input).unwrap(), nan);
+        assert_eq!(maximum_absolute(&input).unwrap(), nan);
+    }
 }
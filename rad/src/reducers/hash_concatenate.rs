@@ -0,0 +1,88 @@
+use witnet_crypto::hash::calculate_sha256;
+
+use crate::{
+    error::RadError,
+    reducers::RadonReducers,
+    types::{array::RadonArray, bytes::RadonBytes, RadonType, RadonTypes},
+};
+
+/// Concatenate all the `Bytes` items in the input and hash the result using SHA-256.
+///
+/// This is used for combining the random bytes revealed by the witnesses of an RNG data request,
+/// so that the result cannot be predicted as long as one of the witnesses is honest. The input
+/// must be in a deterministic order, which is the case for the reveals of a data request.
+pub fn hash_concatenate(input: &RadonArray) -> Result<RadonTypes, RadError> {
+    let value = input.value();
+    if value.is_empty() {
+        return Err(RadError::EmptyReduce {
+            reducer: RadonReducers::HashConcatenate.to_string(),
+        });
+    }
+
+    let mut concatenated = vec![];
+    for item in value {
+        match item {
+            RadonTypes::Bytes(bytes) => concatenated.extend(bytes.value()),
+            _ => {
+                return Err(RadError::UnsupportedReducer {
+                    array: input.clone(),
+                    reducer: RadonReducers::HashConcatenate.to_string(),
+                })
+            }
+        }
+    }
+
+    let hash = calculate_sha256(&concatenated);
+
+    Ok(RadonTypes::from(RadonBytes::from(hash.as_ref().to_vec())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::integer::RadonInteger;
+
+    #[test]
+    fn test_hash_concatenate() {
+        let input = RadonArray::from(vec![
+            RadonBytes::from(vec![0x01, 0x02]).into(),
+            RadonBytes::from(vec![0x03]).into(),
+        ]);
+        let expected = RadonTypes::from(RadonBytes::from(
+            calculate_sha256(&[0x01, 0x02, 0x03]).as_ref().to_vec(),
+        ));
+
+        assert_eq!(hash_concatenate(&input).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_hash_concatenate_depends_on_order() {
+        let a = RadonTypes::from(RadonBytes::from(vec![0x01]));
+        let b = RadonTypes::from(RadonBytes::from(vec![0x02]));
+
+        assert_ne!(
+            hash_concatenate(&RadonArray::from(vec![a.clone(), b.clone()])),
+            hash_concatenate(&RadonArray::from(vec![b, a]))
+        );
+    }
+
+    #[test]
+    fn test_hash_concatenate_empty() {
+        assert_eq!(
+            hash_concatenate(&RadonArray::from(vec![])),
+            Err(RadError::EmptyReduce {
+                reducer: RadonReducers::HashConcatenate.to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_hash_concatenate_unsupported() {
+        let input = RadonArray::from(vec![RadonInteger::from(1).into()]);
+
+        assert!(matches!(
+            hash_concatenate(&input),
+            Err(RadError::UnsupportedReducer { .. })
+        ));
+    }
+}
@@ -0,0 +1,221 @@
+use std::cmp::Ordering;
+
+use crate::{
+    error::RadError,
+    operators::array::transpose,
+    reducers::RadonReducers,
+    types::{array::RadonArray, RadonType, RadonTypes},
+};
+
+/// Total order used for comparing floats inside reducers.
+///
+/// `NaN` values are considered to be greater than any other value, so that the result of the
+/// reducers does not depend on the order of the input.
+pub fn compare_floats(a: f64, b: f64) -> Ordering {
+    a.partial_cmp(&b)
+        .unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()))
+}
+
+/// Compare two items of the same type. Returns `None` for unsupported or mismatching types.
+pub(crate) fn compare(a: &RadonTypes, b: &RadonTypes) -> Option<Ordering> {
+    match (a, b) {
+        (RadonTypes::Integer(a), RadonTypes::Integer(b)) => Some(a.value().cmp(&b.value())),
+        (RadonTypes::Float(a), RadonTypes::Float(b)) => Some(compare_floats(a.value(), b.value())),
+        (RadonTypes::String(a), RadonTypes::String(b)) => Some(a.value().cmp(&b.value())),
+        _ => None,
+    }
+}
+
+/// Find the item that is the greatest according to `ordering`.
+///
+/// For `Array` inputs, the arrays are transposed and the reducer is applied element-wise.
+fn extreme(
+    input: &RadonArray,
+    reducer: RadonReducers,
+    ordering: Ordering,
+) -> Result<RadonTypes, RadError> {
+    let value = input.value();
+
+    match value.first() {
+        None => Err(RadError::EmptyReduce {
+            reducer: reducer.to_string(),
+        }),
+        Some(RadonTypes::Array(_)) => {
+            let v = transpose(input)?;
+
+            let mut extreme_v = vec![];
+            for v2extreme in v.value() {
+                if let RadonTypes::Array(v2extreme) = v2extreme {
+                    extreme_v.push(extreme(&v2extreme, reducer, ordering)?);
+                } else {
+                    unreachable!()
+                }
+            }
+
+            Ok(RadonTypes::from(RadonArray::from(extreme_v)))
+        }
+        Some(RadonTypes::Integer(_)) | Some(RadonTypes::Float(_)) | Some(RadonTypes::String(_)) => {
+            let first = &value[0];
+            let mut result = first;
+            for item in value.iter().skip(1) {
+                match compare(item, result) {
+                    Some(o) if o == ordering => result = item,
+                    Some(_) => {}
+                    None => {
+                        return Err(RadError::MismatchingTypes {
+                            method: reducer.to_string(),
+                            expected: first.clone().radon_type_name(),
+                            found: item.clone().radon_type_name(),
+                        })
+                    }
+                }
+            }
+
+            Ok(result.clone())
+        }
+        Some(_rad_types) => Err(RadError::UnsupportedReducer {
+            array: input.clone(),
+            reducer: reducer.to_string(),
+        }),
+    }
+}
+
+/// Returns the smallest value found in a `RadonArray`.
+///
+/// Integers and floats are compared numerically, while strings are compared lexicographically.
+pub fn min(input: &RadonArray) -> Result<RadonTypes, RadError> {
+    extreme(input, RadonReducers::Min, Ordering::Less)
+}
+
+/// Returns the greatest value found in a `RadonArray`.
+///
+/// Integers and floats are compared numerically, while strings are compared lexicographically.
+pub fn max(input: &RadonArray) -> Result<RadonTypes, RadError> {
+    extreme(input, RadonReducers::Max, Ordering::Greater)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_cbor::Value;
+
+    use crate::{
+        operators::array::reduce,
+        types::{
+            boolean::RadonBoolean, float::RadonFloat, integer::RadonInteger, string::RadonString,
+        },
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_reduce_min_max_integer() {
+        let input = &RadonArray::from(vec![
+            RadonInteger::from(3).into(),
+            RadonInteger::from(-1).into(),
+            RadonInteger::from(7).into(),
+        ]);
+
+        let args = &[Value::Integer(RadonReducers::Min as i128)];
+        let output = reduce(input, args).unwrap();
+        assert_eq!(output, RadonTypes::from(RadonInteger::from(-1)));
+
+        let args = &[Value::Integer(RadonReducers::Max as i128)];
+        let output = reduce(input, args).unwrap();
+        assert_eq!(output, RadonTypes::from(RadonInteger::from(7)));
+    }
+
+    #[test]
+    fn test_reduce_min_max_float() {
+        let input = &RadonArray::from(vec![
+            RadonFloat::from(1.5f64).into(),
+            RadonFloat::from(std::f64::NAN).into(),
+            RadonFloat::from(-2.5f64).into(),
+        ]);
+
+        assert_eq!(
+            min(input).unwrap(),
+            RadonTypes::from(RadonFloat::from(-2.5f64))
+        );
+        assert_eq!(
+            max(input).unwrap(),
+            RadonTypes::from(RadonFloat::from(std::f64::NAN))
+        );
+    }
+
+    #[test]
+    fn test_reduce_min_max_string() {
+        let input = &RadonArray::from(vec![
+            RadonString::from("bravo").into(),
+            RadonString::from("alpha").into(),
+            RadonString::from("charlie").into(),
+        ]);
+
+        assert_eq!(
+            min(input).unwrap(),
+            RadonTypes::from(RadonString::from("alpha"))
+        );
+        assert_eq!(
+            max(input).unwrap(),
+            RadonTypes::from(RadonString::from("charlie"))
+        );
+    }
+
+    #[test]
+    fn test_reduce_min_max_arrays() {
+        let array_1 = RadonTypes::from(RadonArray::from(vec![
+            RadonInteger::from(1).into(),
+            RadonInteger::from(20).into(),
+        ]));
+        let array_2 = RadonTypes::from(RadonArray::from(vec![
+            RadonInteger::from(6).into(),
+            RadonInteger::from(10).into(),
+        ]));
+        let input = RadonArray::from(vec![array_1, array_2]);
+
+        let expected = RadonTypes::from(RadonArray::from(vec![
+            RadonInteger::from(1).into(),
+            RadonInteger::from(10).into(),
+        ]));
+        assert_eq!(min(&input).unwrap(), expected);
+
+        let expected = RadonTypes::from(RadonArray::from(vec![
+            RadonInteger::from(6).into(),
+            RadonInteger::from(20).into(),
+        ]));
+        assert_eq!(max(&input).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_reduce_min_max_empty() {
+        let input = RadonArray::from(vec![]);
+
+        assert_eq!(
+            min(&input).unwrap_err(),
+            RadError::EmptyReduce {
+                reducer: RadonReducers::Min.to_string()
+            }
+        );
+        assert_eq!(
+            max(&input).unwrap_err(),
+            RadError::EmptyReduce {
+                reducer: RadonReducers::Max.to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_reduce_min_boolean_unsupported() {
+        let input = RadonArray::from(vec![
+            RadonBoolean::from(true).into(),
+            RadonBoolean::from(false).into(),
+        ]);
+
+        assert_eq!(
+            min(&input).unwrap_err(),
+            RadError::UnsupportedReducer {
+                array: input,
+                reducer: RadonReducers::Min.to_string()
+            }
+        );
+    }
+}
@@ -7,6 +7,7 @@ use serde_cbor::{
 
 use witnet_data_structures::{
     chain::RADFilter,
+    mainnet_validations::ActiveWips,
     radon_report::{RadonReport, ReportContext, Stage},
 };
 
@@ -21,6 +22,15 @@ use crate::{
 pub type RadonCall = (RadonOpCodes, Option<Vec<Value>>);
 pub type RadonScript = Vec<RadonCall>;
 
+/// Maximum execution cost of the script of a retrieval source, including its subscripts.
+pub const RETRIEVAL_EXECUTION_BUDGET: u64 = 2_000_000;
+/// Maximum execution cost of the aggregation stage, including the filters and the reducer.
+pub const AGGREGATION_EXECUTION_BUDGET: u64 = 2_000_000;
+/// Execution cost charged for every call, whatever its input.
+const CALL_BASE_COST: u64 = 1;
+/// Number of bytes of a string or a byte array that cost as much as a single item of an array.
+const BYTES_PER_ITEM: usize = 32;
+
 /// A set of flags for telling the RADON executor what execution features to enable and what
 /// metadata to collect.
 #[derive(Clone, Copy)]
@@ -111,7 +121,7 @@ pub fn execute_radon_script(
             }
 
             // Apply the call
-            let partial_result = operate_in_context(input, call, context);
+            let partial_result = execute_radon_call(input, call, context);
 
             // Keep partial result, if enabled by `partial_results` setting
             if let Some(partial_results) = partial_results.as_mut() {
@@ -134,6 +144,63 @@ pub fn execute_radon_script(
     })
 }
 
+/// Apply a single call on given input data, after charging its execution cost into the context.
+/// Fails with `RadError::ScriptTooExpensive` if the total cost exceeds the budget of the stage,
+/// which is only enforced after WIP0021.
+pub fn execute_radon_call(
+    input: RadonTypes,
+    call: &RadonCall,
+    context: &mut ReportContext<RadonTypes>,
+) -> Result<RadonTypes, RadError> {
+    context.execution_cost = context
+        .execution_cost
+        .saturating_add(call_cost(&input, call));
+    let budget_active = context
+        .active_wips
+        .as_ref()
+        .map_or(true, ActiveWips::wip0021);
+    if let Some(budget) = execution_budget(&context.stage).filter(|_| budget_active) {
+        if context.execution_cost > budget {
+            return Err(RadError::ScriptTooExpensive);
+        }
+    }
+
+    operate_in_context(input, call, context)
+}
+
+/// The maximum execution cost of the scripts of a stage, or `None` if the stage has no budget.
+/// The tally stage is validated by every node, so limiting its cost would need a protocol upgrade.
+pub fn execution_budget(stage: &Stage<RadonTypes>) -> Option<u64> {
+    match stage {
+        Stage::Retrieval(_) => Some(RETRIEVAL_EXECUTION_BUDGET),
+        Stage::Aggregation => Some(AGGREGATION_EXECUTION_BUDGET),
+        Stage::Tally(_) | Stage::Contextless => None,
+    }
+}
+
+/// The execution cost of applying a call on some input. Every call costs `CALL_BASE_COST` plus the
+/// size of its input, except for `ArraySort`, whose cost grows as `n * log2(n)` with the number of
+/// items. The calls in subscripts are charged separately when they are executed.
+pub fn call_cost(input: &RadonTypes, call: &RadonCall) -> u64 {
+    let size = match input {
+        RadonTypes::Array(array) => array.len(),
+        RadonTypes::Map(map) => map.len(),
+        RadonTypes::Bytes(bytes) => bytes.len() / BYTES_PER_ITEM,
+        RadonTypes::String(string) => string.len() / BYTES_PER_ITEM,
+        RadonTypes::Boolean(_)
+        | RadonTypes::Float(_)
+        | RadonTypes::Integer(_)
+        | RadonTypes::RadonError(_) => 0,
+    };
+    let size = u64::try_from(size).unwrap_or(u64::MAX);
+    let cost = match call.0 {
+        RadonOpCodes::ArraySort => size.saturating_mul(u64::from(64 - size.leading_zeros())),
+        _ => size,
+    };
+
+    CALL_BASE_COST.saturating_add(cost)
+}
+
 /// Run any RADON script on given input data, and return `RadonTypes`.
 /// This the optimistic version of `execute_radon_script`, as it returns a value or an error, but
 /// gives no specific details on what happened during the execution
@@ -202,15 +269,49 @@ pub fn unpack_subscript(value: &Value) -> Result<Vec<RadonCall>, RadError> {
     Ok(subscript)
 }
 
+/// Unpack a subscript that is about to be executed, rejecting the operators that are not active
+/// yet, in the same way as the nodes that do not implement them.
+pub fn unpack_subscript_in_context(
+    value: &Value,
+    context: &ReportContext<RadonTypes>,
+) -> Result<Vec<RadonCall>, RadError> {
+    let subscript = unpack_subscript(value)?;
+    if let Some(active_wips) = &context.active_wips {
+        check_radon_script_operators(&subscript, active_wips)?;
+    }
+
+    Ok(subscript)
+}
+
 fn errorify(kind: RadError) -> RadError {
     log::error!("Error unpacking a RADON script: {:?}", kind);
 
     kind
 }
 
+/// Check that all the operators called by a script are active. Operators that are not active yet
+/// are reported as unknown, just like the nodes that do not implement them do.
+///
+/// The calls in subscripts are not checked, as they are only unpacked when they are executed.
+pub fn check_radon_script_operators(
+    script: &[RadonCall],
+    active_wips: &ActiveWips,
+) -> Result<(), RadError> {
+    match script
+        .iter()
+        .find(|(op_code, _)| !op_code.is_active(active_wips))
+    {
+        Some((op_code, _)) => Err(RadError::UnknownOperator {
+            code: i128::from(*op_code as u8),
+        }),
+        None => Ok(()),
+    }
+}
+
 pub fn create_radon_script_from_filters_and_reducer(
     filters: &[RADFilter],
     reducer: u32,
+    active_wips: &ActiveWips,
 ) -> Result<Vec<RadonCall>, RadError> {
     let unknown_filter = |code| RadError::UnknownFilter { code };
     let unknown_reducer = |code| RadError::UnknownReducer { code };
@@ -222,15 +323,7 @@ pub fn create_radon_script_from_filters_and_reducer(
             RadonFilters::try_from(u8::try_from(filter_op).map_err(|_| unknown_filter(filter_op))?)
                 .map_err(|_| unknown_filter(filter_op))?;
 
-        // TODO: Update with more filters
-        match rad_filter {
-            RadonFilters::DeviationStandard | RadonFilters::Mode => {}
-            _ => {
-                return Err(RadError::UnsupportedFilterInAT {
-                    operator: rad_filter as u8,
-                })
-            }
-        };
+        check_filter_in_at(rad_filter, active_wips)?;
 
         let args = if filter.args.is_empty() {
             Some(vec![Value::Integer(filter_op)])
@@ -251,14 +344,7 @@ pub fn create_radon_script_from_filters_and_reducer(
         u8::try_from(reducer).map_err(|_| unknown_reducer(i128::from(reducer)))?,
     )
     .map_err(|_| unknown_reducer(i128::from(reducer)))?;
-    match rad_reducer {
-        RadonReducers::AverageMean | RadonReducers::Mode => {}
-        _ => {
-            return Err(RadError::UnsupportedReducerInAT {
-                operator: rad_reducer as u8,
-            })
-        }
-    };
+    check_reducer_in_at(rad_reducer, active_wips)?;
 
     let args = Some(vec![Value::Integer(i128::from(reducer))]);
     radoncall_vec.push((RadonOpCodes::ArrayReduce, args));
@@ -266,9 +352,53 @@ pub fn create_radon_script_from_filters_and_reducer(
     Ok(radoncall_vec)
 }
 
+/// Check that a filter can be used in the aggregation and tally stages.
+pub fn check_filter_in_at(
+    rad_filter: RadonFilters,
+    active_wips: &ActiveWips,
+) -> Result<(), RadError> {
+    match rad_filter {
+        RadonFilters::DeviationStandard | RadonFilters::Mode => Ok(()),
+        // The rest of the filters can be used after WIP0017
+        _ if active_wips.wip0017() => Ok(()),
+        _ => Err(RadError::UnsupportedFilterInAT {
+            operator: rad_filter as u8,
+        }),
+    }
+}
+
+/// Check that a reducer can be used in the aggregation and tally stages.
+pub fn check_reducer_in_at(
+    rad_reducer: RadonReducers,
+    active_wips: &ActiveWips,
+) -> Result<(), RadError> {
+    match rad_reducer {
+        RadonReducers::AverageMean | RadonReducers::Mode => Ok(()),
+        // The hash concatenation of the random bytes of RNG requests can be used after WIP0019
+        RadonReducers::HashConcatenate if active_wips.wip0019() => Ok(()),
+        // The reducers that only need the values to reduce can be used after WIP0017. The weighted
+        // reducers cannot, because there is no way to pass them the weights.
+        RadonReducers::Min
+        | RadonReducers::Max
+        | RadonReducers::AverageMedian
+        | RadonReducers::DeviationStandard
+        | RadonReducers::DeviationAverageAbsolute
+        | RadonReducers::DeviationMedianAbsolute
+        | RadonReducers::DeviationMaximumAbsolute
+            if active_wips.wip0017() =>
+        {
+            Ok(())
+        }
+        _ => Err(RadError::UnsupportedReducerInAT {
+            operator: rad_reducer as u8,
+        }),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tests::all_wips_active;
     use std::collections::BTreeMap;
 
     #[test]
@@ -511,6 +641,127 @@ mod tests {
         assert_eq!(output.partial_results, Some(partial_expected));
     }
 
+    #[test]
+    fn test_call_cost() {
+        use crate::types::{array::RadonArray, integer::RadonInteger, string::RadonString};
+
+        let items = RadonTypes::from(RadonArray::from(vec![RadonInteger::from(1).into(); 8]));
+        let text = RadonTypes::from(RadonString::from("x".repeat(64)));
+
+        assert_eq!(call_cost(&items, &(RadonOpCodes::ArrayCount, None)), 1 + 8);
+        assert_eq!(
+            call_cost(&items, &(RadonOpCodes::ArraySort, None)),
+            1 + 8 * 4
+        );
+        assert_eq!(
+            call_cost(&text, &(RadonOpCodes::StringParseJSONMap, None)),
+            1 + 2
+        );
+        assert_eq!(
+            call_cost(
+                &RadonInteger::from(1).into(),
+                &(RadonOpCodes::IntegerAsString, None)
+            ),
+            1
+        );
+    }
+
+    #[test]
+    fn test_execution_budget() {
+        use crate::types::{integer::RadonInteger, string::RadonString};
+        use witnet_data_structures::radon_report::{RetrievalMetadata, TallyMetaData};
+
+        let input = RadonTypes::from(RadonString::from("1"));
+        let script = vec![
+            (RadonOpCodes::StringAsInteger, None),
+            (RadonOpCodes::IntegerAsString, None),
+        ];
+        let settings = RadonScriptExecutionSettings::disable_all();
+
+        // The cost of the executed calls is reported
+        let mut context = ReportContext::from_stage(Stage::Retrieval(RetrievalMetadata::default()));
+        let report = execute_radon_script(input.clone(), &script, &mut context, settings).unwrap();
+        assert_eq!(report.result, input);
+        assert_eq!(report.execution_cost, 2);
+
+        // Fail with `ScriptTooExpensive` once the budget of the stage is exhausted
+        let mut context = ReportContext::from_stage(Stage::Retrieval(RetrievalMetadata::default()));
+        context.execution_cost = RETRIEVAL_EXECUTION_BUDGET - 1;
+        let result = execute_radon_call(input.clone(), &script[0], &mut context);
+        assert_eq!(result, Ok(RadonInteger::from(1).into()));
+        let result = execute_radon_call(RadonInteger::from(1).into(), &script[1], &mut context);
+        assert_eq!(result, Err(RadError::ScriptTooExpensive));
+        let report = execute_radon_script(input.clone(), &script, &mut context, settings).unwrap();
+        assert_eq!(
+            report.result,
+            RadonTypes::try_from(Err::<RadonTypes, _>(RadError::ScriptTooExpensive)).unwrap()
+        );
+
+        // The tally stage has no budget
+        let mut context = ReportContext::from_stage(Stage::Tally(TallyMetaData::default()));
+        context.execution_cost = RETRIEVAL_EXECUTION_BUDGET;
+        let report = execute_radon_script(input.clone(), &script, &mut context, settings).unwrap();
+        assert_eq!(report.result, input);
+
+        // The budget is not enforced before WIP0021
+        let mut context = ReportContext::from_stage(Stage::Retrieval(RetrievalMetadata::default()));
+        context.execution_cost = RETRIEVAL_EXECUTION_BUDGET;
+        context.active_wips = Some(ActiveWips {
+            active_wips: Default::default(),
+            block_epoch: 0,
+        });
+        let report = execute_radon_script(input.clone(), &script, &mut context, settings).unwrap();
+        assert_eq!(report.result, input);
+        assert_eq!(report.execution_cost, RETRIEVAL_EXECUTION_BUDGET + 2);
+
+        // But it is after WIP0021
+        let mut context = ReportContext::from_stage(Stage::Retrieval(RetrievalMetadata::default()));
+        context.execution_cost = RETRIEVAL_EXECUTION_BUDGET;
+        context.active_wips = Some(all_wips_active());
+        let result = execute_radon_call(input, &script[0], &mut context);
+        assert_eq!(result, Err(RadError::ScriptTooExpensive));
+    }
+
+    #[test]
+    fn test_inactive_operators() {
+        use crate::types::{array::RadonArray, string::RadonString};
+        use witnet_data_structures::radon_report::RetrievalMetadata;
+
+        let input = RadonTypes::from(RadonArray::from(vec![RadonString::from("<a/>").into()]));
+        // [ArrayMap, [StringParseXML]]
+        let call = (
+            RadonOpCodes::ArrayMap,
+            Some(vec![Value::Array(vec![Value::Integer(
+                RadonOpCodes::StringParseXML as i128,
+            )])]),
+        );
+
+        // Inactive operators are unknown, even in subscripts
+        let mut context = ReportContext::from_stage(Stage::Retrieval(RetrievalMetadata::default()));
+        context.active_wips = Some(ActiveWips {
+            active_wips: Default::default(),
+            block_epoch: 0,
+        });
+        let result = execute_radon_call(input.clone(), &call, &mut context);
+        assert_eq!(
+            result,
+            Err(RadError::Subscript {
+                input_type: "RadonArray".to_string(),
+                operator: "Map".to_string(),
+                inner: Box::new(RadError::UnknownOperator {
+                    code: i128::from(RadonOpCodes::StringParseXML as u8),
+                }),
+            })
+        );
+
+        context.active_wips = Some(all_wips_active());
+        assert!(execute_radon_call(input.clone(), &call, &mut context).is_ok());
+
+        // All the operators are active if the active WIPs are not known
+        context.active_wips = None;
+        assert!(execute_radon_call(input, &call, &mut context).is_ok());
+    }
+
     #[test]
     fn test_floats_as_integers() {
         use crate::types::{integer::RadonInteger, string::RadonString};
@@ -592,55 +843,170 @@ mod tests {
             args: vec![249, 60, 0],
         }];
         let reducer = RadonReducers::AverageMean as u32;
-        let output =
-            create_radon_script_from_filters_and_reducer(filters.as_slice(), reducer).unwrap();
+        let output = create_radon_script_from_filters_and_reducer(
+            filters.as_slice(),
+            reducer,
+            &all_wips_active(),
+        )
+        .unwrap();
 
         assert_eq!(output, expected);
     }
 
     #[test]
-    fn test_create_radon_script_invalid_filter() {
+    fn test_create_radon_script_deviation_relative() {
         let filters = vec![RADFilter {
-            op: RadonFilters::DeviationAbsolute as u32,
-            args: vec![249, 60, 0],
+            op: RadonFilters::DeviationRelative as u32,
+            // CBOR-encoded 0.05f64
+            args: vec![251, 63, 169, 153, 153, 153, 153, 153, 154],
+        }];
+        let reducer = RadonReducers::AverageMedian as u32;
+        let output = create_radon_script_from_filters_and_reducer(
+            filters.as_slice(),
+            reducer,
+            &all_wips_active(),
+        )
+        .unwrap();
+
+        let expected = vec![
+            (
+                RadonOpCodes::ArrayFilter,
+                Some(vec![
+                    Value::Integer(RadonFilters::DeviationRelative as i128),
+                    Value::Float(0.05),
+                ]),
+            ),
+            (
+                RadonOpCodes::ArrayReduce,
+                Some(vec![Value::Integer(RadonReducers::AverageMedian as i128)]),
+            ),
+        ];
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_create_radon_script_filter_before_wip0017() {
+        let before_wip0017 = ActiveWips {
+            active_wips: Default::default(),
+            block_epoch: 0,
+        };
+        let filters = vec![RADFilter {
+            op: RadonFilters::DeviationRelative as u32,
+            // CBOR-encoded 0.05f64
+            args: vec![251, 63, 169, 153, 153, 153, 153, 153, 154],
         }];
         let reducer = RadonReducers::AverageMean as u32;
-        let output =
-            create_radon_script_from_filters_and_reducer(filters.as_slice(), reducer).unwrap_err();
+        let output = create_radon_script_from_filters_and_reducer(
+            filters.as_slice(),
+            reducer,
+            &before_wip0017,
+        )
+        .unwrap_err();
 
         let expected = RadError::UnsupportedFilterInAT {
-            operator: RadonFilters::DeviationAbsolute as u8,
+            operator: RadonFilters::DeviationRelative as u8,
         };
         assert_eq!(output, expected);
+    }
 
+    #[test]
+    fn test_create_radon_script_invalid_filter() {
+        let reducer = RadonReducers::AverageMean as u32;
         let filters = vec![RADFilter {
             op: 99,
             args: vec![],
         }];
-        let output =
-            create_radon_script_from_filters_and_reducer(filters.as_slice(), reducer).unwrap_err();
+        let output = create_radon_script_from_filters_and_reducer(
+            filters.as_slice(),
+            reducer,
+            &all_wips_active(),
+        )
+        .unwrap_err();
 
         let expected = RadError::UnknownFilter { code: 99 };
         assert_eq!(output, expected);
     }
 
+    #[test]
+    fn test_create_radon_script_reducers() {
+        let before_wips = ActiveWips {
+            active_wips: Default::default(),
+            block_epoch: 0,
+        };
+        for reducer in &[RadonReducers::Mode, RadonReducers::AverageMean] {
+            let output =
+                create_radon_script_from_filters_and_reducer(&[], *reducer as u32, &before_wips)
+                    .unwrap();
+            let expected = vec![(
+                RadonOpCodes::ArrayReduce,
+                Some(vec![Value::Integer(*reducer as i128)]),
+            )];
+
+            assert_eq!(output, expected);
+        }
+
+        for reducer in &[
+            RadonReducers::Min,
+            RadonReducers::Max,
+            RadonReducers::AverageMedian,
+            RadonReducers::DeviationStandard,
+            RadonReducers::DeviationAverageAbsolute,
+            RadonReducers::DeviationMedianAbsolute,
+            RadonReducers::DeviationMaximumAbsolute,
+            RadonReducers::HashConcatenate,
+        ] {
+            let output = create_radon_script_from_filters_and_reducer(
+                &[],
+                *reducer as u32,
+                &all_wips_active(),
+            )
+            .unwrap();
+            let expected = vec![(
+                RadonOpCodes::ArrayReduce,
+                Some(vec![Value::Integer(*reducer as i128)]),
+            )];
+            assert_eq!(output, expected);
+
+            // These reducers cannot be used before WIP0017, or WIP0019 for HashConcatenate
+            let output =
+                create_radon_script_from_filters_and_reducer(&[], *reducer as u32, &before_wips)
+                    .unwrap_err();
+            let expected = RadError::UnsupportedReducerInAT {
+                operator: *reducer as u8,
+            };
+            assert_eq!(output, expected);
+        }
+
+        // The weighted reducers need the weights as an argument
+        for reducer in &[
+            RadonReducers::AverageMeanWeighted,
+            RadonReducers::AverageMedianWeighted,
+        ] {
+            let output = create_radon_script_from_filters_and_reducer(
+                &[],
+                *reducer as u32,
+                &all_wips_active(),
+            )
+            .unwrap_err();
+            let expected = RadError::UnsupportedReducerInAT {
+                operator: *reducer as u8,
+            };
+            assert_eq!(output, expected);
+        }
+    }
+
     #[test]
     fn test_create_radon_script_invalid_reducer() {
         let filters = vec![RADFilter {
             op: RadonFilters::DeviationStandard as u32,
             args: vec![249, 60, 0],
         }];
-        let reducer = RadonReducers::Min as u32;
-        let output =
-            create_radon_script_from_filters_and_reducer(filters.as_slice(), reducer).unwrap_err();
-
-        let expected = RadError::UnsupportedReducerInAT {
-            operator: RadonReducers::Min as u8,
-        };
-        assert_eq!(output, expected);
-
-        let output =
-            create_radon_script_from_filters_and_reducer(filters.as_slice(), 99).unwrap_err();
+        let output = create_radon_script_from_filters_and_reducer(
+            filters.as_slice(),
+            99,
+            &all_wips_active(),
+        )
+        .unwrap_err();
 
         let expected = RadError::UnknownReducer { code: 99 };
         assert_eq!(output, expected);
@@ -0,0 +1,375 @@
+//! Step by step execution of data requests.
+//!
+//! Unlike `try_data_request`, which only keeps the partial results of the retrieval scripts, this
+//! keeps the input, the output and the execution time of every single call in every stage, which
+//! eases finding out why a source is not returning the expected value.
+
+use std::time::{Duration, Instant};
+
+use futures::{executor::block_on, future::join_all};
+use serde::Serialize;
+use serde_cbor::value::Value;
+
+use witnet_data_structures::{
+    chain::{RADFilter, RADRequest, RADRetrieve, RADType},
+    mainnet_validations::ActiveWips,
+    radon_report::{ReportContext, RetrievalMetadata, Stage, TallyMetaData, TypeLike},
+};
+
+use crate::{
+    error::RadError,
+    operators::RadonOpCodes,
+    retrieval_response,
+    script::{
+        check_radon_script_operators, create_radon_script_from_filters_and_reducer,
+        execute_radon_call, unpack_radon_script, RadonCall,
+    },
+    transport::{RetrievalResponse, RetrievalTransport},
+    type_check::ScriptStage,
+    types::{array::RadonArray, RadonTypes},
+};
+
+/// The execution of a single call of a RADON script.
+#[derive(Clone, Debug, Serialize)]
+pub struct CallTrace {
+    pub operator: RadonOpCodes,
+    pub arguments: Option<Vec<Value>>,
+    pub input: RadonTypes,
+    pub output: Result<RadonTypes, RadError>,
+    pub elapsed: Duration,
+    /// The execution cost charged for the call, including the calls in its subscripts.
+    pub cost: u64,
+}
+
+/// The execution of the script of one of the stages of a data request.
+#[derive(Clone, Debug, Serialize)]
+pub struct ScriptTrace {
+    pub stage: ScriptStage,
+    /// The input of the script, or `None` if it could not be obtained, e.g. because the HTTP
+    /// request of a retrieval failed.
+    pub input: Option<RadonTypes>,
+    /// The calls that were executed. The execution stops at the first call that fails, so this
+    /// may be shorter than the script.
+    pub calls: Vec<CallTrace>,
+    pub result: Result<RadonTypes, RadError>,
+}
+
+impl ScriptTrace {
+    /// The result of the script as it is passed to the next stage, with any error intercepted
+    /// into a `RadonTypes::RadonError`.
+    pub fn intercepted_result(&self) -> RadonTypes {
+        RadonTypes::intercept(self.result.clone())
+    }
+
+    fn failed(stage: ScriptStage, error: RadError) -> Self {
+        Self {
+            stage,
+            input: None,
+            calls: vec![],
+            result: Err(error),
+        }
+    }
+}
+
+/// The execution of all the stages of a data request.
+#[derive(Clone, Debug, Serialize)]
+pub struct DataRequestTrace {
+    pub retrieve: Vec<ScriptTrace>,
+    pub aggregate: ScriptTrace,
+    pub tally: ScriptTrace,
+}
+
+/// Run a RADON script one call at a time, keeping track of the input, output and execution time
+/// of each of the calls.
+pub fn trace_radon_script(
+    stage: ScriptStage,
+    input: RadonTypes,
+    script: &[RadonCall],
+    context: &mut ReportContext<RadonTypes>,
+) -> ScriptTrace {
+    let mut calls = Vec::with_capacity(script.len());
+    let mut result = Ok(input.clone());
+
+    for (i, call) in script.iter().enumerate() {
+        let call_input = match result {
+            Ok(value) => value,
+            Err(_) => break,
+        };
+        context.call_index = Some(i);
+
+        let cost_before = context.execution_cost;
+        let start = Instant::now();
+        let output = execute_radon_call(call_input.clone(), call, context);
+        let elapsed = start.elapsed();
+
+        result = output.clone();
+        calls.push(CallTrace {
+            operator: call.0,
+            arguments: call.1.clone(),
+            input: call_input,
+            output,
+            elapsed,
+            cost: context.execution_cost - cost_before,
+        });
+    }
+
+    ScriptTrace {
+        stage,
+        input: Some(input),
+        calls,
+        result,
+    }
+}
+
+/// Execute a data request locally, performing the retrievals using the given transport, and keep
+/// track of every call of every stage.
+/// As in `try_data_request`, the tally stage is run on the result of the aggregation stage, as if
+/// it was reported by a single witness.
+pub fn trace_data_request(
+    request: &RADRequest,
+    transport: &dyn RetrievalTransport,
+    active_wips: &ActiveWips,
+) -> DataRequestTrace {
+    let responses = block_on(join_all(
+        request
+            .retrieve
+            .iter()
+            .map(|retrieve| retrieval_response(retrieve, transport))
+            .collect::<Vec<_>>(),
+    ));
+
+    let retrieve: Vec<ScriptTrace> = request
+        .retrieve
+        .iter()
+        .zip(responses)
+        .enumerate()
+        .map(|(i, (retrieve, response))| trace_retrieval(i, retrieve, response, active_wips))
+        .collect();
+
+    let items = retrieve
+        .iter()
+        .map(ScriptTrace::intercepted_result)
+        .collect();
+    let mut context = ReportContext::from_stage(Stage::Aggregation);
+    let aggregate = trace_filters_and_reducer(
+        ScriptStage::Aggregation,
+        items,
+        &request.aggregate.filters,
+        request.aggregate.reducer,
+        &mut context,
+        active_wips,
+    );
+
+    let items = vec![aggregate.intercepted_result()];
+    let metadata = TallyMetaData {
+        liars: vec![false; items.len()],
+        errors: vec![false; items.len()],
+        ..TallyMetaData::default()
+    };
+    let mut context = ReportContext::from_stage(Stage::Tally(metadata));
+    let tally = trace_filters_and_reducer(
+        ScriptStage::Tally,
+        items,
+        &request.tally.filters,
+        request.tally.reducer,
+        &mut context,
+        active_wips,
+    );
+
+    DataRequestTrace {
+        retrieve,
+        aggregate,
+        tally,
+    }
+}
+
+fn trace_retrieval(
+    index: usize,
+    retrieve: &RADRetrieve,
+    response: Result<RetrievalResponse, RadError>,
+    active_wips: &ActiveWips,
+) -> ScriptTrace {
+    let stage = ScriptStage::Retrieval(index);
+    let binary = match retrieve.kind {
+        RADType::HttpGet | RADType::HttpPost => retrieve.binary,
+        RADType::Rng => true,
+    };
+    let input = response.and_then(|response| response.into_radon_types(binary));
+    let input = match input {
+        Ok(input) => input,
+        Err(error) => return ScriptTrace::failed(stage, error),
+    };
+    let script = unpack_radon_script(&retrieve.script).and_then(|script| {
+        check_radon_script_operators(&script, active_wips)?;
+        Ok(script)
+    });
+    let script = match script {
+        Ok(script) => script,
+        Err(error) => return ScriptTrace::failed(stage, error),
+    };
+    let mut context = ReportContext::from_stage(Stage::Retrieval(RetrievalMetadata::default()));
+    context.active_wips = Some(active_wips.clone());
+
+    trace_radon_script(stage, input, &script, &mut context)
+}
+
+fn trace_filters_and_reducer(
+    stage: ScriptStage,
+    items: Vec<RadonTypes>,
+    filters: &[RADFilter],
+    reducer: u32,
+    context: &mut ReportContext<RadonTypes>,
+    active_wips: &ActiveWips,
+) -> ScriptTrace {
+    match create_radon_script_from_filters_and_reducer(filters, reducer, active_wips) {
+        Ok(script) => {
+            context.active_wips = Some(active_wips.clone());
+            let input = RadonTypes::from(RadonArray::from(items));
+            trace_radon_script(stage, input, &script, context)
+        }
+        Err(error) => ScriptTrace::failed(stage, error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::future::BoxFuture;
+
+    use witnet_data_structures::chain::{RADAggregate, RADTally};
+
+    use super::*;
+    use crate::{
+        language::compile,
+        reducers::RadonReducers,
+        tests::all_wips_active,
+        types::{float::RadonFloat, string::RadonString, RadonType},
+    };
+
+    /// Transport that answers with the response of the source with the same URL, or with an HTTP
+    /// error if there is none.
+    struct FixedResponses(Vec<(&'static str, &'static str)>);
+
+    impl RetrievalTransport for FixedResponses {
+        fn retrieve<'a>(
+            &'a self,
+            retrieve: &'a RADRetrieve,
+        ) -> BoxFuture<'a, Result<RetrievalResponse, RadError>> {
+            let response = self
+                .0
+                .iter()
+                .find(|(url, _)| *url == retrieve.url)
+                .map(|(_, response)| RetrievalResponse::from(*response))
+                .ok_or(RadError::HttpStatus { status_code: 404 });
+
+            Box::pin(async move { response })
+        }
+    }
+
+    fn price_request(urls: &[&str]) -> RADRequest {
+        let script = compile(r#"parseJSONMap().getFloat("price")"#).unwrap();
+        let mean = RadonReducers::AverageMean as u32;
+
+        RADRequest {
+            time_lock: 0,
+            retrieve: urls
+                .iter()
+                .map(|url| RADRetrieve {
+                    kind: RADType::HttpGet,
+                    url: url.to_string(),
+                    script: script.clone(),
+                    ..RADRetrieve::default()
+                })
+                .collect(),
+            aggregate: RADAggregate {
+                filters: vec![],
+                reducer: mean,
+            },
+            tally: RADTally {
+                filters: vec![],
+                reducer: mean,
+            },
+        }
+    }
+
+    #[test]
+    fn test_trace_radon_script_stops_at_error() {
+        let script =
+            unpack_radon_script(&compile("asFloat().absolute().round()").unwrap()).unwrap();
+        let mut context = ReportContext::default();
+        let trace = trace_radon_script(
+            ScriptStage::Retrieval(0),
+            RadonString::from("not a number").into(),
+            &script,
+            &mut context,
+        );
+
+        assert_eq!(trace.calls.len(), 1);
+        assert_eq!(trace.calls[0].operator, RadonOpCodes::StringAsFloat);
+        assert_eq!(
+            trace.calls[0].input,
+            RadonString::from("not a number").into()
+        );
+        assert!(trace.calls[0].output.is_err());
+        assert_eq!(trace.result, trace.calls[0].output);
+    }
+
+    #[test]
+    fn test_trace_data_request() {
+        let transport = FixedResponses(vec![
+            ("https://a.example/", r#"{"price": 1.5}"#),
+            ("https://b.example/", r#"{"price": 2.5}"#),
+        ]);
+        let request = price_request(&["https://a.example/", "https://b.example/"]);
+        let trace = trace_data_request(&request, &transport, &all_wips_active());
+
+        assert_eq!(trace.retrieve.len(), 2);
+        let source = &trace.retrieve[1];
+        assert_eq!(source.stage, ScriptStage::Retrieval(1));
+        assert_eq!(
+            source.input,
+            Some(RadonString::from(r#"{"price": 2.5}"#).into())
+        );
+        assert_eq!(source.calls.len(), 2);
+        assert_eq!(source.calls[1].operator, RadonOpCodes::MapGetFloat);
+        assert_eq!(source.result, Ok(RadonFloat::from(2.5).into()));
+
+        assert_eq!(
+            trace.aggregate.input,
+            Some(
+                RadonArray::from(vec![
+                    RadonFloat::from(1.5).into(),
+                    RadonFloat::from(2.5).into()
+                ])
+                .into()
+            )
+        );
+        assert_eq!(trace.aggregate.calls.len(), 1);
+        assert_eq!(trace.aggregate.result, Ok(RadonFloat::from(2.0).into()));
+        assert_eq!(trace.tally.stage, ScriptStage::Tally);
+        assert_eq!(trace.tally.result, Ok(RadonFloat::from(2.0).into()));
+    }
+
+    #[test]
+    fn test_trace_data_request_failed_retrieval() {
+        let transport = FixedResponses(vec![("https://a.example/", r#"{"price": 1.5}"#)]);
+        let request = price_request(&["https://a.example/", "https://b.example/"]);
+        let trace = trace_data_request(&request, &transport, &all_wips_active());
+
+        let source = &trace.retrieve[1];
+        assert_eq!(source.input, None);
+        assert!(source.calls.is_empty());
+        assert_eq!(
+            source.result,
+            Err(RadError::HttpStatus { status_code: 404 })
+        );
+
+        // The error is passed to the aggregation as a value
+        match &trace.aggregate.input {
+            Some(RadonTypes::Array(items)) => {
+                assert_eq!(items.value()[0], RadonFloat::from(1.5).into());
+                assert!(matches!(items.value()[1], RadonTypes::RadonError(_)));
+            }
+            input => panic!("Unexpected aggregation input: {:?}", input),
+        }
+    }
+}
@@ -0,0 +1,656 @@
+//! Pluggable transports for performing the HTTP requests of the retrieval stage.
+//!
+//! Retrievals are performed over the network using `HttpTransport` by default. A `Cassette` can
+//! be used instead for recording the responses into a file, and then replaying them without any
+//! network access, which allows for reproducible runs of data requests.
+
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Mutex, PoisonError},
+    time::Duration,
+};
+
+use encoding_rs::Encoding;
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+
+use witnet_data_structures::chain::{RADRetrieve, RADType};
+
+use crate::{
+    error::RadError,
+    http_response,
+    proxy::RetrievalProxy,
+    types::{bytes::RadonBytes, string::RadonString, RadonTypes},
+    Result,
+};
+
+/// A way of performing the request described by a retrieval source.
+pub trait RetrievalTransport: Send + Sync {
+    /// Perform the request and return the body of the response, along with its content type.
+    fn retrieve<'a>(
+        &'a self,
+        retrieve: &'a RADRetrieve,
+    ) -> BoxFuture<'a, Result<RetrievalResponse>>;
+}
+
+/// The response to the request of a retrieval source.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RetrievalResponse {
+    /// Raw body of the response
+    pub body: Vec<u8>,
+    /// Value of the `Content-Type` header of the response, if any
+    pub content_type: Option<String>,
+}
+
+impl RetrievalResponse {
+    /// The charset declared in the content type, if any.
+    pub fn charset(&self) -> Option<&str> {
+        self.content_type
+            .as_deref()?
+            .split(';')
+            .skip(1)
+            .filter_map(|parameter| {
+                let mut name_value = parameter.splitn(2, '=');
+                let name = name_value.next()?.trim();
+                let value = name_value.next()?.trim().trim_matches('"');
+
+                Some((name, value))
+            })
+            .find(|(name, _)| name.eq_ignore_ascii_case("charset"))
+            .map(|(_, value)| value)
+    }
+
+    /// Decode the body into text using the charset declared in the content type, or UTF-8 if there
+    /// is none. Bodies that are not valid in their charset are rejected.
+    pub fn text(&self) -> Result<String> {
+        let charset = match self.charset() {
+            Some(charset) => charset,
+            None => {
+                return String::from_utf8(self.body.clone()).map_err(|x| RadError::HttpOther {
+                    message: x.to_string(),
+                })
+            }
+        };
+        let encoding =
+            Encoding::for_label(charset.as_bytes()).ok_or_else(|| RadError::HttpOther {
+                message: format!("Unsupported charset `{}`", charset),
+            })?;
+
+        encoding
+            .decode_without_bom_handling_and_without_replacement(&self.body)
+            .map(Cow::into_owned)
+            .ok_or_else(|| RadError::HttpOther {
+                message: format!("Response body is not valid {}", encoding.name()),
+            })
+    }
+
+    /// Convert the response into the input of the script of a source: the raw body as `Bytes` for
+    /// binary sources, or the decoded body as a `String` otherwise.
+    pub fn into_radon_types(self, binary: bool) -> Result<RadonTypes> {
+        if binary {
+            Ok(RadonTypes::from(RadonBytes::from(self.body)))
+        } else {
+            self.text()
+                .map(|text| RadonTypes::from(RadonString::from(text)))
+        }
+    }
+}
+
+impl From<String> for RetrievalResponse {
+    fn from(body: String) -> Self {
+        Self {
+            body: body.into_bytes(),
+            content_type: None,
+        }
+    }
+}
+
+impl From<&str> for RetrievalResponse {
+    fn from(body: &str) -> Self {
+        Self::from(body.to_string())
+    }
+}
+
+/// Limits applied to each of the HTTP requests performed by `HttpTransport`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RetrievalLimits {
+    /// Maximum time to wait for the response headers since the request starts, or `None` for no
+    /// limit. This includes resolving the host name and establishing the connection.
+    pub connect_timeout: Option<Duration>,
+    /// Maximum time to wait for the whole response body once the headers have been received, or
+    /// `None` for no limit.
+    pub read_timeout: Option<Duration>,
+    /// How many times a request is retried after failing with a transient error: a timeout, a
+    /// connection error or a `429` or `5xx` status code. Only HTTP-GET requests are retried,
+    /// because other methods are not guaranteed to be idempotent.
+    pub max_retries: u32,
+    /// Time to wait before the first retry. It is doubled before each of the following retries.
+    pub retry_backoff: Duration,
+    /// Maximum time since the first attempt of a request within which it can be retried, or
+    /// `None` for no limit. A retry is not performed if it would start after this time.
+    pub max_retry_duration: Option<Duration>,
+    /// Maximum size in bytes of the response body, or `None` for no limit.
+    pub max_response_size: Option<usize>,
+}
+
+impl Default for RetrievalLimits {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Some(Duration::from_secs(10)),
+            read_timeout: Some(Duration::from_secs(10)),
+            max_retries: 2,
+            retry_backoff: Duration::from_millis(250),
+            max_retry_duration: Some(Duration::from_secs(30)),
+            max_response_size: Some(10 * 1024 * 1024),
+        }
+    }
+}
+
+/// Transport that performs the requests over the network.
+#[derive(Clone, Debug, Default)]
+pub struct HttpTransport {
+    limits: RetrievalLimits,
+    proxy: Option<RetrievalProxy>,
+}
+
+impl HttpTransport {
+    /// Create a transport that applies the given limits to each request.
+    pub fn with_limits(limits: RetrievalLimits) -> Self {
+        Self {
+            limits,
+            proxy: None,
+        }
+    }
+
+    /// Send the requests through the given proxy, if any, except those to the hosts that bypass
+    /// it.
+    pub fn with_proxy(mut self, proxy: Option<RetrievalProxy>) -> Self {
+        self.proxy = proxy;
+
+        self
+    }
+
+    /// The limits applied to each request.
+    pub fn limits(&self) -> &RetrievalLimits {
+        &self.limits
+    }
+
+    /// The proxy through which the requests are sent, if any.
+    pub fn proxy(&self) -> Option<&RetrievalProxy> {
+        self.proxy.as_ref()
+    }
+}
+
+impl RetrievalTransport for HttpTransport {
+    fn retrieve<'a>(
+        &'a self,
+        retrieve: &'a RADRetrieve,
+    ) -> BoxFuture<'a, Result<RetrievalResponse>> {
+        Box::pin(http_response(retrieve, &self.limits, self.proxy.as_ref()))
+    }
+}
+
+/// Whether a `Cassette` records new responses or replays the recorded ones.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CassetteMode {
+    /// Perform the requests over the network and save the responses into the cassette file
+    Record,
+    /// Serve the responses from the cassette file, without any network access
+    Replay,
+}
+
+/// The outcome of a request, as stored in a cassette file.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum RecordedResponse {
+    /// Body of a successful response without a content type
+    Body(String),
+    /// Body of a successful response, along with its content type
+    TypedBody { content_type: String, body: String },
+    /// Hex-encoded body of a successful response that is not valid UTF-8
+    BinaryBody {
+        content_type: Option<String>,
+        body_hex: String,
+    },
+    /// Status code of a response with a non-success status
+    HttpStatus(u16),
+    /// Message of any other error found when performing the request
+    HttpOther(String),
+}
+
+impl RecordedResponse {
+    /// Returns `None` for errors that happen before performing the request (e.g. invalid URLs),
+    /// because these are not recorded.
+    fn from_result(result: &Result<RetrievalResponse>) -> Option<Self> {
+        match result {
+            Ok(response) => Some(
+                match (
+                    String::from_utf8(response.body.clone()),
+                    response.content_type.clone(),
+                ) {
+                    (Ok(body), None) => RecordedResponse::Body(body),
+                    (Ok(body), Some(content_type)) => {
+                        RecordedResponse::TypedBody { content_type, body }
+                    }
+                    (Err(_), content_type) => RecordedResponse::BinaryBody {
+                        content_type,
+                        body_hex: hex::encode(&response.body),
+                    },
+                },
+            ),
+            Err(RadError::HttpStatus { status_code }) => {
+                Some(RecordedResponse::HttpStatus(*status_code))
+            }
+            Err(RadError::HttpOther { message }) => {
+                Some(RecordedResponse::HttpOther(message.clone()))
+            }
+            Err(RadError::HttpRetriesExhausted { last_error, .. }) => {
+                Self::from_result(&Err(*last_error.clone()))
+            }
+            Err(_) => None,
+        }
+    }
+
+    fn into_result(self) -> Result<RetrievalResponse> {
+        match self {
+            RecordedResponse::Body(body) => Ok(RetrievalResponse::from(body)),
+            RecordedResponse::TypedBody { content_type, body } => Ok(RetrievalResponse {
+                body: body.into_bytes(),
+                content_type: Some(content_type),
+            }),
+            RecordedResponse::BinaryBody {
+                content_type,
+                body_hex,
+            } => Ok(RetrievalResponse {
+                body: hex::decode(body_hex).map_err(|e| RadError::HttpOther {
+                    message: format!("Invalid recorded body: {}", e),
+                })?,
+                content_type,
+            }),
+            RecordedResponse::HttpStatus(status_code) => Err(RadError::HttpStatus { status_code }),
+            RecordedResponse::HttpOther(message) => Err(RadError::HttpOther { message }),
+        }
+    }
+}
+
+/// Key that identifies a request in a cassette: the method and the URL, followed by the content
+/// type and the headers as quoted `Name: value` strings, and then by the hex-encoded body in the
+/// case of HTTP-POST requests with a body.
+pub fn cassette_key(retrieve: &RADRetrieve) -> String {
+    let mut key = match retrieve.kind {
+        RADType::HttpGet => format!("GET {}", retrieve.url),
+        RADType::HttpPost => format!("POST {}", retrieve.url),
+        // RNG sources are never retrieved through a transport
+        RADType::Rng => return "RNG".to_string(),
+    };
+
+    let content_type = Some(("Content-Type", retrieve.content_type.as_str()))
+        .filter(|(_, content_type)| !content_type.is_empty());
+    let headers = retrieve
+        .headers
+        .iter()
+        .map(|(name, value)| (name.as_str(), value.as_str()));
+    for (name, value) in content_type.into_iter().chain(headers) {
+        key.push_str(&format!(" {:?}", format!("{}: {}", name, value)));
+    }
+
+    if retrieve.kind == RADType::HttpPost && !retrieve.body.is_empty() {
+        key.push_str(&format!(" {}", hex::encode(&retrieve.body)));
+    }
+
+    key
+}
+
+/// Transport that records the responses to a JSON file, or replays them from it.
+///
+/// The file contains a map from the key of each request, as returned by `cassette_key`, to its
+/// recorded response. Only the last response for each key is kept.
+#[derive(Debug)]
+pub struct Cassette {
+    path: PathBuf,
+    mode: CassetteMode,
+    /// Transport through which the requests are performed when recording
+    transport: HttpTransport,
+    responses: Mutex<BTreeMap<String, RecordedResponse>>,
+}
+
+impl Cassette {
+    /// Open a cassette for recording the responses to the requests performed through the given
+    /// transport. If the file already exists, its responses are kept unless the same requests are
+    /// performed again.
+    pub fn record<P: AsRef<Path>>(path: P, transport: HttpTransport) -> Result<Self> {
+        let path = path.as_ref();
+        let responses = if path.exists() {
+            Self::load(path)?
+        } else {
+            BTreeMap::new()
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            mode: CassetteMode::Record,
+            transport,
+            responses: Mutex::new(responses),
+        })
+    }
+
+    /// Open an existing cassette for replaying its responses.
+    pub fn replay<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            mode: CassetteMode::Replay,
+            transport: HttpTransport::default(),
+            responses: Mutex::new(Self::load(path)?),
+        })
+    }
+
+    /// Whether this cassette records or replays responses.
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    fn io_error(path: &Path, message: String) -> RadError {
+        RadError::CassetteIo {
+            path: path.display().to_string(),
+            message,
+        }
+    }
+
+    fn load(path: &Path) -> Result<BTreeMap<String, RecordedResponse>> {
+        let contents = fs::read_to_string(path).map_err(|e| Self::io_error(path, e.to_string()))?;
+
+        serde_json::from_str(&contents).map_err(|e| Self::io_error(path, e.to_string()))
+    }
+
+    fn save(&self, responses: &BTreeMap<String, RecordedResponse>) -> Result<()> {
+        let contents = serde_json::to_string_pretty(responses)
+            .map_err(|e| Self::io_error(&self.path, e.to_string()))?;
+
+        fs::write(&self.path, contents).map_err(|e| Self::io_error(&self.path, e.to_string()))
+    }
+
+    fn replayed_response(&self, key: String) -> Result<RetrievalResponse> {
+        let responses = self
+            .responses
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        match responses.get(&key) {
+            Some(response) => response.clone().into_result(),
+            None => Err(RadError::NotInCassette { key }),
+        }
+    }
+
+    fn record_response(&self, key: String, result: &Result<RetrievalResponse>) -> Result<()> {
+        if let Some(response) = RecordedResponse::from_result(result) {
+            let mut responses = self
+                .responses
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner);
+            responses.insert(key, response);
+            self.save(&responses)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl RetrievalTransport for Cassette {
+    fn retrieve<'a>(
+        &'a self,
+        retrieve: &'a RADRetrieve,
+    ) -> BoxFuture<'a, Result<RetrievalResponse>> {
+        Box::pin(async move {
+            let key = cassette_key(retrieve);
+
+            match self.mode {
+                CassetteMode::Replay => self.replayed_response(key),
+                CassetteMode::Record => {
+                    let result = self.transport.retrieve(retrieve).await;
+                    self.record_response(key, &result)?;
+
+                    result
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+
+    fn temp_cassette_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "witnet_rad_cassette_{}_{}.json",
+            name,
+            std::process::id()
+        ))
+    }
+
+    fn retrieve_get(url: &str) -> RADRetrieve {
+        RADRetrieve {
+            kind: RADType::HttpGet,
+            url: url.to_string(),
+            ..RADRetrieve::default()
+        }
+    }
+
+    #[test]
+    fn test_cassette_key() {
+        assert_eq!(
+            cassette_key(&retrieve_get("https://example.com/")),
+            "GET https://example.com/"
+        );
+
+        let mut retrieve = RADRetrieve {
+            kind: RADType::HttpPost,
+            url: "https://example.com/".to_string(),
+            ..RADRetrieve::default()
+        };
+        assert_eq!(cassette_key(&retrieve), "POST https://example.com/");
+
+        retrieve.body = b"{}".to_vec();
+        assert_eq!(cassette_key(&retrieve), "POST https://example.com/ 7b7d");
+
+        retrieve.content_type = "application/json".to_string();
+        retrieve.headers = vec![("X-Api-Key".to_string(), "\"secret\"".to_string())];
+        assert_eq!(
+            cassette_key(&retrieve),
+            r#"POST https://example.com/ "Content-Type: application/json" "X-Api-Key: \"secret\"" 7b7d"#
+        );
+    }
+
+    #[test]
+    fn test_cassette_replay() {
+        let path = temp_cassette_path("replay");
+        fs::write(
+            &path,
+            r#"{
+                "GET https://example.com/ok": { "body": "{\"price\": 1}" },
+                "GET https://example.com/not_found": { "http_status": 404 },
+                "GET https://example.com/timeout": { "http_other": "Timed out" }
+            }"#,
+        )
+        .unwrap();
+        let cassette = Cassette::replay(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let replay = |url| block_on(cassette.retrieve(&retrieve_get(url)));
+        assert_eq!(
+            replay("https://example.com/ok"),
+            Ok(RetrievalResponse::from("{\"price\": 1}"))
+        );
+        assert_eq!(
+            replay("https://example.com/not_found"),
+            Err(RadError::HttpStatus { status_code: 404 })
+        );
+        assert_eq!(
+            replay("https://example.com/timeout"),
+            Err(RadError::HttpOther {
+                message: "Timed out".to_string()
+            })
+        );
+        assert_eq!(
+            replay("https://example.com/missing"),
+            Err(RadError::NotInCassette {
+                key: "GET https://example.com/missing".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_cassette_record_and_replay() {
+        let path = temp_cassette_path("record");
+        let _ = fs::remove_file(&path);
+
+        let cassette = Cassette::record(&path, HttpTransport::default()).unwrap();
+        assert_eq!(cassette.mode(), CassetteMode::Record);
+        cassette
+            .record_response(
+                "GET https://example.com/".to_string(),
+                &Ok(RetrievalResponse::from("Hi")),
+            )
+            .unwrap();
+        // Errors that happen before performing the request are not recorded
+        cassette
+            .record_response(
+                "GET invalid".to_string(),
+                &Err(RadError::InvalidHttpHeader {
+                    name: "a b".to_string(),
+                    value: "c".to_string(),
+                }),
+            )
+            .unwrap();
+
+        let cassette = Cassette::replay(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(cassette.mode(), CassetteMode::Replay);
+        assert_eq!(
+            block_on(cassette.retrieve(&retrieve_get("https://example.com/"))),
+            Ok(RetrievalResponse::from("Hi"))
+        );
+        assert_eq!(
+            block_on(cassette.retrieve(&retrieve_get("invalid"))),
+            Err(RadError::NotInCassette {
+                key: "GET invalid".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_cassette_record_binary_body() {
+        let path = temp_cassette_path("binary");
+        let _ = fs::remove_file(&path);
+
+        let typed = RetrievalResponse {
+            body: b"{}".to_vec(),
+            content_type: Some("application/json".to_string()),
+        };
+        let binary = RetrievalResponse {
+            body: vec![0x89, 0x50, 0x4e, 0x47, 0xff],
+            content_type: Some("image/png".to_string()),
+        };
+        let cassette = Cassette::record(&path, HttpTransport::default()).unwrap();
+        cassette
+            .record_response(
+                "GET https://example.com/json".to_string(),
+                &Ok(typed.clone()),
+            )
+            .unwrap();
+        cassette
+            .record_response(
+                "GET https://example.com/png".to_string(),
+                &Ok(binary.clone()),
+            )
+            .unwrap();
+
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("\"body_hex\": \"89504e47ff\""));
+
+        let cassette = Cassette::replay(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            block_on(cassette.retrieve(&retrieve_get("https://example.com/json"))),
+            Ok(typed)
+        );
+        assert_eq!(
+            block_on(cassette.retrieve(&retrieve_get("https://example.com/png"))),
+            Ok(binary)
+        );
+    }
+
+    #[test]
+    fn test_retrieval_response_charset() {
+        let response = |body: &[u8], content_type: &str| RetrievalResponse {
+            body: body.to_vec(),
+            content_type: Some(content_type.to_string()),
+        };
+
+        assert_eq!(
+            response(b"", "text/html; Charset=\"ISO-8859-1\"").charset(),
+            Some("ISO-8859-1")
+        );
+        assert_eq!(response(b"", "application/json").charset(), None);
+
+        // Without a charset, the body must be valid UTF-8
+        assert_eq!(
+            response("caf\u{e9}".as_bytes(), "text/plain").text(),
+            Ok("caf\u{e9}".to_string())
+        );
+        assert!(response(b"caf\xe9", "text/plain").text().is_err());
+        assert_eq!(
+            response(b"caf\xe9", "text/plain; charset=iso-8859-1").text(),
+            Ok("caf\u{e9}".to_string())
+        );
+        assert_eq!(
+            response(b"\x82\xa0", "text/plain; charset=Shift_JIS").text(),
+            Ok("\u{3042}".to_string())
+        );
+        assert!(matches!(
+            response(b"a", "text/plain; charset=unknown").text(),
+            Err(RadError::HttpOther { .. })
+        ));
+    }
+
+    #[test]
+    fn test_retrieval_response_into_radon_types() {
+        let response = RetrievalResponse {
+            body: vec![0xff, 0x00],
+            content_type: Some("application/octet-stream".to_string()),
+        };
+
+        assert_eq!(
+            response.clone().into_radon_types(true),
+            Ok(RadonTypes::from(RadonBytes::from(vec![0xff, 0x00])))
+        );
+        assert!(response.into_radon_types(false).is_err());
+        assert_eq!(
+            RetrievalResponse::from("Hi").into_radon_types(false),
+            Ok(RadonTypes::from(RadonString::from("Hi")))
+        );
+    }
+
+    #[test]
+    fn test_cassette_replay_missing_file() {
+        let path = temp_cassette_path("missing");
+
+        match Cassette::replay(&path).unwrap_err() {
+            RadError::CassetteIo {
+                path: error_path, ..
+            } => {
+                assert_eq!(error_path, path.display().to_string())
+            }
+            e => panic!("Unexpected error: {}", e),
+        }
+    }
+}
@@ -0,0 +1,1068 @@
+//! Static type checking of the RADON scripts of a data request.
+//!
+//! The checker follows the types of the values flowing through the retrieval scripts, the
+//! aggregation and the tally without executing anything, and reports the mistakes that would
+//! make the data request fail for sure: operators applied to values of the wrong type, wrong
+//! arguments, and unknown or unsupported filters and reducers. It also reports the operators,
+//! filters and reducers that are not allowed yet by the active WIPs, which would make the data
+//! request invalid.
+//!
+//! The type of some values cannot be known without executing the scripts (e.g. the result of
+//! `GetPath` or `ArrayReduce` on values of unknown type). The operators applied to them are not
+//! checked, so the absence of issues does not guarantee that the data request will succeed.
+
+use std::{convert::TryFrom, fmt};
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_cbor::value::{from_value, Value};
+
+use witnet_data_structures::{
+    chain::{RADFilter, RADRequest, RADType},
+    mainnet_validations::ActiveWips,
+};
+
+use crate::{
+    error::RadError,
+    filters::RadonFilters,
+    hash_functions::RadonHashFunctions,
+    operators::{
+        bytes::RadonBytesEncoding,
+        path::parse_path,
+        string::{compile_regex, RadonDateTimeFormat},
+        RadonOpCodes, RadonTypeKind,
+    },
+    reducers::RadonReducers,
+    script::{
+        check_filter_in_at, check_reducer_in_at, unpack_radon_script, unpack_subscript, RadonCall,
+    },
+};
+
+/// The stage of a data request in which an issue was found.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub enum ScriptStage {
+    /// The script of the retrieval source with this index
+    Retrieval(usize),
+    Aggregation,
+    Tally,
+}
+
+impl fmt::Display for ScriptStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScriptStage::Retrieval(index) => write!(f, "retrieval source {}", index),
+            ScriptStage::Aggregation => write!(f, "aggregation"),
+            ScriptStage::Tally => write!(f, "tally"),
+        }
+    }
+}
+
+/// A mistake found in a script of a data request.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TypeIssue {
+    pub stage: ScriptStage,
+    /// Index of the call where the issue was found, followed by the indexes of the calls inside
+    /// of subscripts, if any. In the aggregation and tally stages, the filters come first and the
+    /// reducer is the last call.
+    pub call: Vec<usize>,
+    pub error: RadError,
+}
+
+impl fmt::Display for TypeIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let call = self
+            .call
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(".");
+
+        write!(f, "In {}, call {}: {}", self.stage, call, self.error)
+    }
+}
+
+/// The inferred type of a value. `None` means that the type is not known.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct InferredType {
+    kind: Option<RadonTypeKind>,
+    /// The type of the items, for arrays whose items are known to have the same type
+    items: Option<RadonTypeKind>,
+}
+
+impl InferredType {
+    fn of(kind: Option<RadonTypeKind>) -> Self {
+        Self { kind, items: None }
+    }
+
+    fn array_of(items: Option<RadonTypeKind>) -> Self {
+        Self {
+            kind: Some(RadonTypeKind::Array),
+            items,
+        }
+    }
+}
+
+/// Check the retrieval, aggregation and tally scripts of a data request, returning all the issues
+/// found.
+pub fn check_rad_request(rad_request: &RADRequest, active_wips: &ActiveWips) -> Vec<TypeIssue> {
+    let mut checker = Checker::default();
+
+    let mut retrieval_outputs = vec![];
+    for (index, retrieve) in rad_request.retrieve.iter().enumerate() {
+        checker.stage = ScriptStage::Retrieval(index);
+        // Binary and RNG sources pass their response to the script as bytes
+        let input = if retrieve.binary || retrieve.kind == RADType::Rng {
+            RadonTypeKind::Bytes
+        } else {
+            RadonTypeKind::String
+        };
+        let output = match unpack_radon_script(&retrieve.script) {
+            Ok(script) => {
+                checker.check_operators(&script, active_wips);
+                checker.check_script(&script, InferredType::of(Some(input)))
+            }
+            Err(error) => {
+                checker.report(error);
+                InferredType::default()
+            }
+        };
+        retrieval_outputs.push(output.kind);
+    }
+
+    // The aggregation is applied to the array of the results of all the sources
+    let first = retrieval_outputs.first().cloned().unwrap_or(None);
+    let items = if retrieval_outputs.iter().all(|kind| *kind == first) {
+        first
+    } else {
+        None
+    };
+
+    checker.stage = ScriptStage::Aggregation;
+    let aggregation_output = checker.check_filters_and_reducer(
+        &rad_request.aggregate.filters,
+        rad_request.aggregate.reducer,
+        InferredType::array_of(items),
+        active_wips,
+    );
+
+    // The tally is applied to the array of the results of the aggregation reported by witnesses
+    checker.stage = ScriptStage::Tally;
+    checker.check_filters_and_reducer(
+        &rad_request.tally.filters,
+        rad_request.tally.reducer,
+        InferredType::array_of(aggregation_output.kind),
+        active_wips,
+    );
+
+    checker.issues
+}
+
+/// Check a single script applied to a value of the given type, returning all the issues found.
+/// The `stage` of the issues is `ScriptStage::Retrieval(0)`.
+pub fn check_script(script: &[RadonCall], input: Option<RadonTypeKind>) -> Vec<TypeIssue> {
+    let mut checker = Checker::default();
+    checker.check_script(script, InferredType::of(input));
+
+    checker.issues
+}
+
+struct Checker {
+    stage: ScriptStage,
+    /// Index of the calls being checked, one for each level of subscripts
+    call: Vec<usize>,
+    issues: Vec<TypeIssue>,
+}
+
+impl Default for Checker {
+    fn default() -> Self {
+        Self {
+            stage: ScriptStage::Retrieval(0),
+            call: vec![],
+            issues: vec![],
+        }
+    }
+}
+
+impl Checker {
+    /// Report an issue in the call being checked.
+    fn report(&mut self, error: RadError) {
+        self.issues.push(TypeIssue {
+            stage: self.stage,
+            call: self.call.clone(),
+            error,
+        });
+    }
+
+    /// Check that the operators of a retrieval script are active. Like in the validation of data
+    /// requests, only the top level calls are checked.
+    fn check_operators(&mut self, script: &[RadonCall], active_wips: &ActiveWips) {
+        for (index, (op_code, _)) in script.iter().enumerate() {
+            if !op_code.is_active(active_wips) {
+                self.call.push(index);
+                self.report(RadError::UnknownOperator {
+                    code: i128::from(*op_code as u8),
+                });
+                self.call.pop();
+            }
+        }
+    }
+
+    fn check_script(&mut self, script: &[RadonCall], input: InferredType) -> InferredType {
+        let mut current = input;
+        for (index, call) in script.iter().enumerate() {
+            self.call.push(index);
+            current = self.check_call(call, current);
+            self.call.pop();
+        }
+
+        current
+    }
+
+    /// Check a single call and infer the type of its result. After any issue, the type of the
+    /// result is unknown, so that the same mistake is not reported again by the following calls.
+    fn check_call(&mut self, call: &RadonCall, input: InferredType) -> InferredType {
+        let (op_code, args) = call;
+        let op_code = *op_code;
+        let args = args.as_ref().map(Vec::as_slice);
+
+        let input_is_valid = match (op_code, input.kind) {
+            (_, None) | (RadonOpCodes::Identity, _) => true,
+            (RadonOpCodes::GetPath, Some(kind)) => {
+                kind == RadonTypeKind::Array || kind == RadonTypeKind::Map
+            }
+            (op_code, Some(kind)) => op_code.input_type() == Some(kind),
+        };
+        if !input_is_valid || op_code == RadonOpCodes::Fail {
+            let error = match (op_code.input_type(), input.kind) {
+                (Some(expected), Some(found)) => RadError::MismatchingTypes {
+                    method: op_code.to_string(),
+                    expected: expected.radon_type_name(),
+                    found: found.radon_type_name(),
+                },
+                (_, found) => RadError::UnsupportedOperator {
+                    input_type: found
+                        .map_or("unknown", RadonTypeKind::radon_type_name)
+                        .to_string(),
+                    operator: op_code.to_string(),
+                    args: args.map(<[Value]>::to_vec),
+                },
+            };
+            self.report(error);
+
+            return InferredType::default();
+        }
+
+        let input_type_name = op_code
+            .input_type()
+            .or(input.kind)
+            .map_or("unknown", RadonTypeKind::radon_type_name);
+        let wrong_args = || RadError::WrongArguments {
+            input_type: input_type_name,
+            operator: op_code.to_string(),
+            args: args.map(<[Value]>::to_vec).unwrap_or_default(),
+        };
+
+        match self.check_arguments(op_code, args, input) {
+            Ok(Some(output)) => output,
+            Ok(None) => {
+                self.report(wrong_args());
+                InferredType::default()
+            }
+            Err(error) => {
+                self.report(error);
+                InferredType::default()
+            }
+        }
+    }
+
+    /// Check the arguments of a call, returning the type of its result if they are valid, `None`
+    /// if they are wrong, or a more specific error.
+    fn check_arguments(
+        &mut self,
+        op_code: RadonOpCodes,
+        args: Option<&[Value]>,
+        input: InferredType,
+    ) -> Result<Option<InferredType>, RadError> {
+        use RadonOpCodes::*;
+
+        let output = InferredType::of(op_code.output_type(input.kind));
+        let valid = match (op_code, args) {
+            (Identity, None) => return Ok(Some(input)),
+            (GetPath, Some([path])) => {
+                if let Value::Text(path) = path {
+                    parse_path(path)?;
+                }
+                fits::<String>(path)
+            }
+            (ArrayFilter, Some([subscript @ Value::Array(_)])) => {
+                let result = self.check_subscript(subscript, input.items)?;
+                expect_boolean(op_code, result)?;
+                return Ok(Some(InferredType::array_of(input.items)));
+            }
+            (ArrayFilter, Some([code, extra_args @ ..])) => {
+                let filter = filter_from_code(code)?;
+                check_filter(filter, extra_args, input.items)?;
+                return Ok(Some(InferredType::array_of(input.items)));
+            }
+            (ArrayMap, Some([subscript])) => {
+                let result = self.check_subscript(subscript, input.items)?;
+                return Ok(Some(InferredType::array_of(result.kind)));
+            }
+            (ArraySome, Some([subscript])) => {
+                let result = self.check_subscript(subscript, input.items)?;
+                expect_boolean(op_code, result)?;
+                true
+            }
+            (ArraySort, Some([])) => return Ok(Some(input)),
+            (ArraySort, Some([subscript])) => {
+                self.check_subscript(subscript, input.items)?;
+                return Ok(Some(input));
+            }
+            (ArrayReduce, Some([code])) => {
+                let reducer = reducer_from_code(code)?;
+                return Ok(Some(check_reducer(reducer, input.items)?));
+            }
+            (ArrayTake, Some([n])) => {
+                if !fits::<u32>(n) {
+                    return Ok(None);
+                }
+                return Ok(Some(input));
+            }
+            (ArrayFlatten, None) | (ArrayFlatten, Some([])) => true,
+            (ArrayFlatten, Some([depth])) => fits::<u32>(depth),
+            (ArrayGetArray, Some([index]))
+            | (ArrayGetBoolean, Some([index]))
+            | (ArrayGetBytes, Some([index]))
+            | (ArrayGetFloat, Some([index]))
+            | (ArrayGetInteger, Some([index]))
+            | (ArrayGetMap, Some([index]))
+            | (ArrayGetString, Some([index])) => fits::<i32>(index),
+            (BytesAsString, None)
+            | (BytesAsString, Some([]))
+            | (StringAsBytes, None)
+            | (StringAsBytes, Some([])) => true,
+            (BytesAsString, Some([encoding])) | (StringAsBytes, Some([encoding])) => {
+                known_code::<RadonBytesEncoding>(encoding)
+            }
+            (BytesHash, Some([hash_function])) => known_code::<RadonHashFunctions>(hash_function),
+            (FloatGreaterThan, Some([x]))
+            | (FloatLessThan, Some([x]))
+            | (FloatModulo, Some([x]))
+            | (FloatMultiply, Some([x]))
+            | (FloatPower, Some([x]))
+            | (FloatSum, Some([x])) => fits::<f64>(x),
+            (IntegerGreaterThan, Some([x]))
+            | (IntegerLessThan, Some([x]))
+            | (IntegerModulo, Some([x]))
+            | (IntegerMaxAge, Some([x]))
+            | (IntegerMultiply, Some([x])) => fits::<i128>(x),
+            // Either an integer or a float can be added to an integer
+            (IntegerSum, Some([x])) => fits::<i128>(x) || fits::<f64>(x),
+            (IntegerPower, Some([x])) => fits::<u32>(x),
+            (MapGetArray, Some([key]))
+            | (MapGetBoolean, Some([key]))
+            | (MapGetBytes, Some([key]))
+            | (MapGetFloat, Some([key]))
+            | (MapGetInteger, Some([key]))
+            | (MapGetMap, Some([key]))
+            | (MapGetString, Some([key])) => fits::<String>(key),
+            (BooleanMatch, Some([Value::Map(_), default]))
+            | (IntegerMatch, Some([Value::Map(_), default]))
+            | (StringMatch, Some([Value::Map(_), default])) => {
+                // The result has the same type as the default value
+                return Ok(value_kind(default).map(|kind| InferredType::of(Some(kind))));
+            }
+            (StringParseDateTime, Some([Value::Text(_)])) => true,
+            (StringParseDateTime, Some([format])) => known_code::<RadonDateTimeFormat>(format),
+            (StringRegexCapture, Some([pattern]))
+            | (StringRegexFindAll, Some([pattern]))
+            | (StringRegexReplace, Some([pattern, Value::Text(_)])) => check_regex(pattern)?,
+            (StringRegexCapture, Some([pattern, group]))
+            | (StringRegexFindAll, Some([pattern, group])) => {
+                check_regex(pattern)? && fits::<u32>(group)
+            }
+            (op_code, None) => takes_no_arguments(op_code),
+            _ => false,
+        };
+
+        if !valid {
+            return Ok(None);
+        }
+
+        Ok(Some(match op_code {
+            StringRegexFindAll => InferredType::array_of(Some(RadonTypeKind::String)),
+            MapKeys => InferredType::array_of(Some(RadonTypeKind::String)),
+            _ => output,
+        }))
+    }
+
+    /// Check a subscript applied to each of the items of an array, and return the type of its
+    /// result.
+    fn check_subscript(
+        &mut self,
+        subscript: &Value,
+        items: Option<RadonTypeKind>,
+    ) -> Result<InferredType, RadError> {
+        let subscript = unpack_subscript(subscript)?;
+
+        Ok(self.check_script(&subscript, InferredType::of(items)))
+    }
+
+    fn check_filters_and_reducer(
+        &mut self,
+        filters: &[RADFilter],
+        reducer: u32,
+        input: InferredType,
+        active_wips: &ActiveWips,
+    ) -> InferredType {
+        for (index, filter) in filters.iter().enumerate() {
+            self.call.push(index);
+            if let Err(error) = check_rad_filter(filter, input.items, active_wips) {
+                self.report(error);
+            }
+            self.call.pop();
+        }
+
+        self.call.push(filters.len());
+        let code = Value::Integer(i128::from(reducer));
+        let output = reducer_from_code(&code)
+            .and_then(|reducer| {
+                check_reducer_in_at(reducer, active_wips)?;
+                check_reducer(reducer, input.items)
+            })
+            .unwrap_or_else(|error| {
+                self.report(error);
+                InferredType::default()
+            });
+        self.call.pop();
+
+        output
+    }
+}
+
+/// Whether the operator can only be called without arguments. These must be encoded as a bare
+/// operator code, not as an array with no arguments.
+fn takes_no_arguments(op_code: RadonOpCodes) -> bool {
+    use RadonOpCodes::*;
+
+    matches!(
+        op_code,
+        ArrayCount
+            | BooleanAsString
+            | BooleanNegate
+            | FloatAbsolute
+            | FloatAsString
+            | FloatCeiling
+            | FloatFloor
+            | FloatNegate
+            | FloatReciprocal
+            | FloatRound
+            | FloatTruncate
+            | IntegerAbsolute
+            | IntegerAge
+            | IntegerAsFloat
+            | IntegerAsString
+            | IntegerNegate
+            | IntegerReciprocal
+            | MapEntries
+            | MapKeys
+            | MapValues
+            | StringAsBoolean
+            | StringAsFloat
+            | StringAsInteger
+            | StringLength
+            | StringParseJSONArray
+            | StringParseJSONMap
+            | StringParseXML
+            | StringToLowerCase
+            | StringToUpperCase
+    )
+}
+
+/// Whether the argument can be deserialized into `T`, in the same way the operators do.
+fn fits<T: DeserializeOwned>(value: &Value) -> bool {
+    from_value::<T>(value.clone()).is_ok()
+}
+
+fn known_code<T: TryFrom<u8>>(value: &Value) -> bool {
+    from_value::<u8>(value.clone())
+        .ok()
+        .and_then(|code| T::try_from(code).ok())
+        .is_some()
+}
+
+/// The kind of the RADON value a CBOR value is converted into, if any.
+fn value_kind(value: &Value) -> Option<RadonTypeKind> {
+    match value {
+        Value::Array(_) => Some(RadonTypeKind::Array),
+        Value::Bool(_) => Some(RadonTypeKind::Boolean),
+        Value::Bytes(_) => Some(RadonTypeKind::Bytes),
+        Value::Float(_) => Some(RadonTypeKind::Float),
+        Value::Integer(_) => Some(RadonTypeKind::Integer),
+        Value::Map(_) => Some(RadonTypeKind::Map),
+        Value::Text(_) => Some(RadonTypeKind::String),
+        _ => None,
+    }
+}
+
+fn check_regex(pattern: &Value) -> Result<bool, RadError> {
+    match pattern {
+        Value::Text(pattern) => compile_regex(pattern).map(|_| true),
+        _ => Ok(false),
+    }
+}
+
+fn expect_boolean(op_code: RadonOpCodes, result: InferredType) -> Result<(), RadError> {
+    match result.kind {
+        Some(kind) if kind != RadonTypeKind::Boolean => Err(RadError::MismatchingTypes {
+            method: op_code.to_string(),
+            expected: RadonTypeKind::Boolean.radon_type_name(),
+            found: kind.radon_type_name(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+fn filter_from_code(code: &Value) -> Result<RadonFilters, RadError> {
+    let code = match code {
+        Value::Integer(code) => *code,
+        _ => return Err(RadError::NotIntegerOperator),
+    };
+
+    u8::try_from(code)
+        .ok()
+        .and_then(|code| RadonFilters::try_from(code).ok())
+        .ok_or(RadError::UnknownFilter { code })
+}
+
+fn reducer_from_code(code: &Value) -> Result<RadonReducers, RadError> {
+    let code = match code {
+        Value::Integer(code) => *code,
+        _ => return Err(RadError::NotIntegerOperator),
+    };
+
+    u8::try_from(code)
+        .ok()
+        .and_then(|code| RadonReducers::try_from(code).ok())
+        .ok_or(RadError::UnknownReducer { code })
+}
+
+fn check_rad_filter(
+    filter: &RADFilter,
+    items: Option<RadonTypeKind>,
+    active_wips: &ActiveWips,
+) -> Result<(), RadError> {
+    let filter_code = filter_from_code(&Value::Integer(i128::from(filter.op)))?;
+    check_filter_in_at(filter_code, active_wips)?;
+    let extra_args = if filter.args.is_empty() {
+        vec![]
+    } else {
+        vec![
+            serde_cbor::from_slice(&filter.args).map_err(|e| RadError::BufferIsNotValue {
+                description: e.to_string(),
+            })?,
+        ]
+    };
+
+    check_filter(filter_code, &extra_args, items)
+}
+
+/// Check the arguments of a filter and whether it supports items of the given type.
+fn check_filter(
+    filter: RadonFilters,
+    extra_args: &[Value],
+    items: Option<RadonTypeKind>,
+) -> Result<(), RadError> {
+    use RadonFilters::*;
+    use RadonTypeKind::*;
+
+    let (args_are_valid, supported_items): (bool, &[RadonTypeKind]) = match filter {
+        GreaterThan | LessThan | GreaterOrEqualThan | LessOrEqualThan => {
+            (is_number(extra_args), &[Float, Integer])
+        }
+        Equals | NotEquals => (
+            matches!(
+                extra_args,
+                [Value::Integer(_)] | [Value::Float(_)] | [Value::Text(_)] | [Value::Bool(_)]
+            ),
+            &[Boolean, Float, Integer, String],
+        ),
+        DeviationAbsolute | NotDeviationAbsolute | DeviationRelative | NotDeviationRelative => {
+            (is_number(extra_args), &[Float, Integer])
+        }
+        DeviationStandard | NotDeviationStandard => {
+            (is_number(extra_args), &[Array, Float, Integer])
+        }
+        Top | Bottom | NotTop | NotBottom => (
+            match extra_args {
+                [n] => fits::<u32>(n),
+                _ => false,
+            },
+            &[Float, Integer, String],
+        ),
+        Mode | NotMode => (true, &[Array, Boolean, Bytes, Float, Integer, Map, String]),
+    };
+
+    if !args_are_valid {
+        return Err(RadError::WrongArguments {
+            input_type: Array.radon_type_name(),
+            operator: filter.to_string(),
+            args: extra_args.to_vec(),
+        });
+    }
+
+    match items {
+        Some(kind) if !supported_items.contains(&kind) => Err(RadError::UnsupportedItemType {
+            operator: filter.to_string(),
+            item_type: kind.radon_type_name(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+fn is_number(args: &[Value]) -> bool {
+    matches!(args, [Value::Integer(_)] | [Value::Float(_)])
+}
+
+/// Check whether a reducer supports items of the given type, and infer the type of its result.
+fn check_reducer(
+    reducer: RadonReducers,
+    items: Option<RadonTypeKind>,
+) -> Result<InferredType, RadError> {
+    use RadonReducers::*;
+    use RadonTypeKind::*;
+
+    let supported_items: &[RadonTypeKind] = match reducer {
+        Mode => &[Array, Boolean, Bytes, Float, Integer, Map, String],
+        Min | Max => &[Array, Float, Integer, String],
+        // The weighted reducers take arrays of value and weight pairs
+        AverageMeanWeighted | AverageMedianWeighted => &[Array],
+        AverageMean
+        | AverageMedian
+        | DeviationStandard
+        | DeviationAverageAbsolute
+        | DeviationMedianAbsolute
+        | DeviationMaximumAbsolute => &[Array, Float, Integer],
+        HashConcatenate => &[Bytes],
+    };
+
+    match items {
+        Some(kind) if !supported_items.contains(&kind) => Err(RadError::UnsupportedItemType {
+            operator: reducer.to_string(),
+            item_type: kind.radon_type_name(),
+        }),
+        // The result is always a hash, whatever the type of the items
+        _ if reducer == HashConcatenate => Ok(InferredType::of(Some(Bytes))),
+        // Arrays are reduced element-wise, so the type of the result is not known
+        Some(Array) | None => Ok(InferredType::default()),
+        Some(kind) => Ok(InferredType::of(match reducer {
+            Mode | Min | Max | AverageMean | AverageMedian => Some(kind),
+            AverageMeanWeighted | AverageMedianWeighted => None,
+            DeviationStandard
+            | DeviationAverageAbsolute
+            | DeviationMedianAbsolute
+            | DeviationMaximumAbsolute => Some(Float),
+            HashConcatenate => Some(Bytes),
+        })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use witnet_data_structures::chain::{RADAggregate, RADRetrieve, RADTally, RADType};
+
+    use super::*;
+    use crate::tests::all_wips_active;
+
+    fn script(calls: Vec<Value>) -> Vec<u8> {
+        serde_cbor::to_vec(&Value::Array(calls)).unwrap()
+    }
+
+    fn op(op_code: RadonOpCodes) -> Value {
+        Value::Integer(i128::from(op_code as u8))
+    }
+
+    fn call(op_code: RadonOpCodes, args: Vec<Value>) -> Value {
+        let mut call = vec![op(op_code)];
+        call.extend(args);
+
+        Value::Array(call)
+    }
+
+    fn text(s: &str) -> Value {
+        Value::Text(s.to_string())
+    }
+
+    fn rad_request(scripts: Vec<Vec<u8>>, aggregate: RADAggregate, tally: RADTally) -> RADRequest {
+        RADRequest {
+            time_lock: 0,
+            retrieve: scripts
+                .into_iter()
+                .map(|script| RADRetrieve {
+                    kind: RADType::HttpGet,
+                    url: "https://example.com/".to_string(),
+                    script,
+                    ..RADRetrieve::default()
+                })
+                .collect(),
+            aggregate,
+            tally,
+        }
+    }
+
+    fn price_script() -> Vec<u8> {
+        script(vec![
+            op(RadonOpCodes::StringParseJSONMap),
+            call(RadonOpCodes::MapGetFloat, vec![text("price")]),
+        ])
+    }
+
+    fn mean_aggregate() -> RADAggregate {
+        RADAggregate {
+            filters: vec![RADFilter {
+                op: RadonFilters::DeviationStandard as u32,
+                args: serde_cbor::to_vec(&Value::Float(1.5)).unwrap(),
+            }],
+            reducer: RadonReducers::AverageMean as u32,
+        }
+    }
+
+    fn mean_tally() -> RADTally {
+        RADTally {
+            filters: vec![],
+            reducer: RadonReducers::AverageMean as u32,
+        }
+    }
+
+    #[test]
+    fn test_check_valid_request() {
+        let request = rad_request(
+            vec![price_script(), price_script()],
+            mean_aggregate(),
+            mean_tally(),
+        );
+
+        assert_eq!(check_rad_request(&request, &all_wips_active()), vec![]);
+    }
+
+    #[test]
+    fn test_check_mismatching_types() {
+        let request = rad_request(
+            vec![script(vec![
+                op(RadonOpCodes::StringParseJSONArray),
+                call(RadonOpCodes::MapGetFloat, vec![text("price")]),
+                op(RadonOpCodes::FloatRound),
+            ])],
+            mean_aggregate(),
+            mean_tally(),
+        );
+
+        // Only the first mistake is reported, the following calls are not checked
+        assert_eq!(
+            check_rad_request(&request, &all_wips_active()),
+            vec![TypeIssue {
+                stage: ScriptStage::Retrieval(0),
+                call: vec![1],
+                error: RadError::MismatchingTypes {
+                    method: RadonOpCodes::MapGetFloat.to_string(),
+                    expected: "RadonMap",
+                    found: "RadonArray",
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn test_check_wrong_arguments() {
+        let issues = check_script(
+            &[
+                (RadonOpCodes::StringParseJSONMap, None),
+                (RadonOpCodes::MapGetFloat, Some(vec![Value::Integer(1)])),
+            ],
+            Some(RadonTypeKind::String),
+        );
+        assert_eq!(
+            issues,
+            vec![TypeIssue {
+                stage: ScriptStage::Retrieval(0),
+                call: vec![1],
+                error: RadError::WrongArguments {
+                    input_type: "RadonMap",
+                    operator: RadonOpCodes::MapGetFloat.to_string(),
+                    args: vec![Value::Integer(1)],
+                },
+            }]
+        );
+
+        // Operators without arguments cannot be called with an empty list of arguments
+        let issues = check_script(
+            &[(RadonOpCodes::StringLength, Some(vec![]))],
+            Some(RadonTypeKind::String),
+        );
+        assert_eq!(issues.len(), 1);
+
+        let issues = check_script(
+            &[(RadonOpCodes::StringRegexCapture, Some(vec![text("(")]))],
+            Some(RadonTypeKind::String),
+        );
+        match &issues[..] {
+            [TypeIssue {
+                error: RadError::RegexCompile { .. },
+                ..
+            }] => {}
+            issues => panic!("Unexpected issues: {:?}", issues),
+        }
+    }
+
+    #[test]
+    fn test_check_subscripts() {
+        let get_price = Value::Array(vec![call(RadonOpCodes::MapGetFloat, vec![text("price")])]);
+        let script = vec![
+            (RadonOpCodes::StringParseJSONArray, None),
+            (RadonOpCodes::ArrayMap, Some(vec![get_price.clone()])),
+            // The items are known to be floats after the map
+            (
+                RadonOpCodes::ArrayFilter,
+                Some(vec![Value::Array(vec![op(RadonOpCodes::StringLength)])]),
+            ),
+        ];
+
+        assert_eq!(
+            check_script(&script, Some(RadonTypeKind::String)),
+            vec![TypeIssue {
+                stage: ScriptStage::Retrieval(0),
+                call: vec![2, 0],
+                error: RadError::MismatchingTypes {
+                    method: RadonOpCodes::StringLength.to_string(),
+                    expected: "RadonString",
+                    found: "RadonFloat",
+                },
+            }]
+        );
+
+        let script = vec![
+            (RadonOpCodes::StringParseJSONArray, None),
+            (RadonOpCodes::ArraySome, Some(vec![get_price])),
+        ];
+        assert_eq!(
+            check_script(&script, Some(RadonTypeKind::String)),
+            vec![TypeIssue {
+                stage: ScriptStage::Retrieval(0),
+                call: vec![1],
+                error: RadError::MismatchingTypes {
+                    method: RadonOpCodes::ArraySome.to_string(),
+                    expected: "RadonBoolean",
+                    found: "RadonFloat",
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn test_check_string_match_output() {
+        let script = vec![
+            (
+                RadonOpCodes::StringMatch,
+                Some(vec![Value::Map(Default::default()), Value::Bool(false)]),
+            ),
+            (RadonOpCodes::BooleanNegate, None),
+            (RadonOpCodes::FloatRound, None),
+        ];
+
+        assert_eq!(
+            check_script(&script, Some(RadonTypeKind::String)),
+            vec![TypeIssue {
+                stage: ScriptStage::Retrieval(0),
+                call: vec![2],
+                error: RadError::MismatchingTypes {
+                    method: RadonOpCodes::FloatRound.to_string(),
+                    expected: "RadonFloat",
+                    found: "RadonBoolean",
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn test_check_arithmetic_output() {
+        let script = vec![
+            (RadonOpCodes::IntegerSum, Some(vec![Value::Integer(1)])),
+            (RadonOpCodes::IntegerReciprocal, None),
+            (RadonOpCodes::FloatSum, Some(vec![Value::Integer(1)])),
+            (RadonOpCodes::IntegerSum, Some(vec![Value::Integer(1)])),
+        ];
+
+        // The reciprocal of an integer is a float
+        assert_eq!(
+            check_script(&script, Some(RadonTypeKind::Integer)),
+            vec![TypeIssue {
+                stage: ScriptStage::Retrieval(0),
+                call: vec![3],
+                error: RadError::MismatchingTypes {
+                    method: RadonOpCodes::IntegerSum.to_string(),
+                    expected: "RadonInteger",
+                    found: "RadonFloat",
+                },
+            }]
+        );
+
+        // The sum of an integer and a float is a float, so the output of `IntegerSum` is unknown
+        let unknown_sum_script = vec![
+            (RadonOpCodes::IntegerSum, Some(vec![Value::Float(0.5)])),
+            (RadonOpCodes::FloatRound, None),
+            (RadonOpCodes::IntegerSum, Some(vec![Value::Integer(1)])),
+            (RadonOpCodes::IntegerAsString, None),
+        ];
+        assert_eq!(
+            check_script(&unknown_sum_script, Some(RadonTypeKind::Integer)),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_check_filters_and_reducers() {
+        let string_script = script(vec![
+            op(RadonOpCodes::StringParseJSONMap),
+            call(RadonOpCodes::MapGetString, vec![text("name")]),
+        ]);
+        let aggregate = RADAggregate {
+            filters: vec![RADFilter {
+                op: 0x42,
+                args: vec![],
+            }],
+            reducer: RadonReducers::AverageMean as u32,
+        };
+        let tally = RADTally {
+            filters: vec![],
+            reducer: RadonReducers::Mode as u32,
+        };
+        let request = rad_request(vec![string_script], aggregate, tally);
+
+        assert_eq!(
+            check_rad_request(&request, &all_wips_active()),
+            vec![
+                TypeIssue {
+                    stage: ScriptStage::Aggregation,
+                    call: vec![0],
+                    error: RadError::UnknownFilter { code: 0x42 },
+                },
+                TypeIssue {
+                    stage: ScriptStage::Aggregation,
+                    call: vec![1],
+                    error: RadError::UnsupportedItemType {
+                        operator: RadonReducers::AverageMean.to_string(),
+                        item_type: "RadonString",
+                    },
+                },
+            ]
+        );
+
+        // Sources with different types of results make the type of the items unknown
+        let request = rad_request(
+            vec![
+                price_script(),
+                script(vec![op(RadonOpCodes::StringParseJSONMap)]),
+            ],
+            RADAggregate {
+                filters: vec![],
+                reducer: RadonReducers::AverageMean as u32,
+            },
+            RADTally {
+                filters: vec![],
+                reducer: 0xFF,
+            },
+        );
+        assert_eq!(
+            check_rad_request(&request, &all_wips_active()),
+            vec![TypeIssue {
+                stage: ScriptStage::Tally,
+                call: vec![0],
+                error: RadError::UnknownReducer { code: 0xFF },
+            }]
+        );
+    }
+
+    #[test]
+    fn test_check_inactive_wips() {
+        let request = rad_request(
+            vec![
+                price_script(),
+                script(vec![op(RadonOpCodes::StringParseXML)]),
+            ],
+            RADAggregate {
+                filters: vec![],
+                reducer: RadonReducers::AverageMedian as u32,
+            },
+            RADTally {
+                filters: vec![RADFilter {
+                    op: RadonFilters::GreaterThan as u32,
+                    args: serde_cbor::to_vec(&Value::Integer(0)).unwrap(),
+                }],
+                reducer: RadonReducers::AverageMeanWeighted as u32,
+            },
+        );
+
+        let no_wips_active = ActiveWips {
+            active_wips: Default::default(),
+            block_epoch: 0,
+        };
+        assert_eq!(
+            check_rad_request(&request, &no_wips_active),
+            vec![
+                TypeIssue {
+                    stage: ScriptStage::Retrieval(1),
+                    call: vec![0],
+                    error: RadError::UnknownOperator {
+                        code: i128::from(RadonOpCodes::StringParseXML as u8),
+                    },
+                },
+                TypeIssue {
+                    stage: ScriptStage::Aggregation,
+                    call: vec![0],
+                    error: RadError::UnsupportedReducerInAT {
+                        operator: RadonReducers::AverageMedian as u8,
+                    },
+                },
+                TypeIssue {
+                    stage: ScriptStage::Tally,
+                    call: vec![0],
+                    error: RadError::UnsupportedFilterInAT {
+                        operator: RadonFilters::GreaterThan as u8,
+                    },
+                },
+                TypeIssue {
+                    stage: ScriptStage::Tally,
+                    call: vec![1],
+                    error: RadError::UnsupportedReducerInAT {
+                        operator: RadonReducers::AverageMeanWeighted as u8,
+                    },
+                },
+            ]
+        );
+
+        // The weighted reducers can never be used in the aggregation and tally stages
+        assert_eq!(
+            check_rad_request(&request, &all_wips_active()),
+            vec![TypeIssue {
+                stage: ScriptStage::Tally,
+                call: vec![1],
+                error: RadError::UnsupportedReducerInAT {
+                    operator: RadonReducers::AverageMeanWeighted as u8,
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn test_type_issue_display() {
+        let issue = TypeIssue {
+            stage: ScriptStage::Retrieval(1),
+            call: vec![2, 0],
+            error: RadError::UnknownFilter { code: 0x42 },
+        };
+
+        assert_eq!(
+            issue.to_string(),
+            "In retrieval source 1, call 2.0: Filter code `66` is unknown"
+        );
+    }
+}
@@ -8,7 +8,7 @@ use witnet_data_structures::radon_report::ReportContext;
 
 use crate::{
     error::RadError,
-    operators::{array as array_operators, identity, Operable, RadonOpCodes},
+    operators::{array as array_operators, identity, path, Operable, RadonOpCodes},
     script::RadonCall,
     types::{RadonType, RadonTypes},
 };
@@ -25,6 +25,15 @@ impl RadonArray {
     pub fn is_homogeneous(&self) -> bool {
         self.is_homogeneous
     }
+
+    /// Number of items, without cloning them as `value()` does.
+    pub fn len(&self) -> usize {
+        self.value.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
 }
 
 impl RadonType<Vec<RadonTypes>> for RadonArray {
@@ -120,6 +129,9 @@ impl Operable for RadonArray {
     fn operate(&self, call: &RadonCall) -> Result<RadonTypes, RadError> {
         match call {
             (RadonOpCodes::Identity, None) => identity(RadonTypes::from(self.clone())),
+            (RadonOpCodes::GetPath, Some(args)) => {
+                path::get_path(RadonTypes::from(self.clone()), args.as_slice())
+            }
             (RadonOpCodes::ArrayCount, None) => Ok(array_operators::count(self).into()),
             (RadonOpCodes::ArrayGetArray, Some(args)) => {
                 array_operators::get_array(self, args.as_slice()).map(RadonTypes::from)
@@ -155,6 +167,18 @@ impl Operable for RadonArray {
                 array_operators::sort(self, args.as_slice(), &mut ReportContext::default())
                     .map(RadonTypes::from)
             }
+            (RadonOpCodes::ArrayFlatten, None) => {
+                array_operators::flatten(self, &[]).map(RadonTypes::from)
+            }
+            (RadonOpCodes::ArrayFlatten, Some(args)) => {
+                array_operators::flatten(self, args.as_slice()).map(RadonTypes::from)
+            }
+            (RadonOpCodes::ArraySome, Some(args)) => {
+                array_operators::some(self, args.as_slice(), &mut ReportContext::default())
+            }
+            (RadonOpCodes::ArrayTake, Some(args)) => {
+                array_operators::take(self, args.as_slice()).map(RadonTypes::from)
+            }
             (op_code, args) => Err(RadError::UnsupportedOperator {
                 input_type: RADON_ARRAY_TYPE_NAME.to_string(),
                 operator: op_code.to_string(),
@@ -178,6 +202,9 @@ impl Operable for RadonArray {
             (RadonOpCodes::ArraySort, Some(args)) => {
                 array_operators::sort(self, args.as_slice(), context)
             }
+            (RadonOpCodes::ArraySome, Some(args)) => {
+                array_operators::some(self, args.as_slice(), context)
+            }
             other => self.operate(other),
         }
     }
@@ -83,6 +83,9 @@ impl Operable for RadonBoolean {
             (RadonOpCodes::BooleanAsString, None) => boolean_operators::to_string(self.clone())
                 .map(RadonTypes::from)
                 .map_err(Into::into),
+            (RadonOpCodes::BooleanMatch, Some(args)) => {
+                boolean_operators::boolean_match(self, args.as_slice())
+            }
             (op_code, args) => Err(RadError::UnsupportedOperator {
                 input_type: RADON_BOOLEAN_TYPE_NAME.to_string(),
                 operator: op_code.to_string(),
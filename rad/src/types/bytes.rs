@@ -24,6 +24,17 @@ impl Default for RadonBytes {
     }
 }
 
+impl RadonBytes {
+    /// Number of bytes, without cloning them as `value()` does.
+    pub fn len(&self) -> usize {
+        self.value.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+}
+
 impl RadonType<Vec<u8>> for RadonBytes {
     fn value(&self) -> Vec<u8> {
         self.value.clone()
@@ -89,9 +100,12 @@ impl Operable for RadonBytes {
         match call {
             // Identity
             (RadonOpCodes::Identity, None) => identity(RadonTypes::from(self.clone())),
-            (RadonOpCodes::BytesAsString, None) => bytes_operators::to_string(&self)
+            (RadonOpCodes::BytesAsString, None) => bytes_operators::to_string(&self, &[])
                 .map(RadonTypes::from)
                 .map_err(Into::into),
+            (RadonOpCodes::BytesAsString, Some(args)) => {
+                bytes_operators::to_string(&self, args.as_slice()).map(RadonTypes::from)
+            }
             (RadonOpCodes::BytesHash, Some(args)) => bytes_operators::hash(&self, args.as_slice())
                 .map(RadonTypes::from)
                 .map_err(Into::into),
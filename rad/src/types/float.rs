@@ -129,7 +129,13 @@ impl Operable for RadonFloat {
             (RadonOpCodes::FloatPower, Some(args)) => {
                 float_operators::power(self, args.as_slice()).map(Into::into)
             }
+            (RadonOpCodes::FloatReciprocal, None) => {
+                float_operators::reciprocal(self).map(Into::into)
+            }
             (RadonOpCodes::FloatRound, None) => Ok(RadonTypes::from(float_operators::round(self))),
+            (RadonOpCodes::FloatSum, Some(args)) => {
+                float_operators::sum(self, args.as_slice()).map(Into::into)
+            }
             (RadonOpCodes::FloatTruncate, None) => {
                 Ok(RadonTypes::from(float_operators::truncate(self)))
             }
@@ -116,6 +116,20 @@ impl<'a> Operable for RadonInteger {
             (RadonOpCodes::IntegerPower, Some(args)) => {
                 integer_operators::power(self, args.as_slice()).map(Into::into)
             }
+            (RadonOpCodes::IntegerMatch, Some(args)) => {
+                integer_operators::integer_match(self, args.as_slice())
+            }
+            (RadonOpCodes::IntegerReciprocal, None) => {
+                integer_operators::reciprocal(self).map(Into::into)
+            }
+            (RadonOpCodes::IntegerSum, Some(args)) => integer_operators::sum(self, args.as_slice()),
+            (RadonOpCodes::IntegerAge, None) => {
+                integer_operators::age(self, &ReportContext::default()).map(Into::into)
+            }
+            (RadonOpCodes::IntegerMaxAge, Some(args)) => {
+                integer_operators::max_age(self, args.as_slice(), &ReportContext::default())
+                    .map(Into::into)
+            }
             // Unsupported / unimplemented
             (op_code, args) => Err(RadError::UnsupportedOperator {
                 input_type: RADON_INTEGER_TYPE_NAME.to_string(),
@@ -128,9 +142,17 @@ impl<'a> Operable for RadonInteger {
     fn operate_in_context(
         &self,
         call: &RadonCall,
-        _context: &mut ReportContext<RadonTypes>,
+        context: &mut ReportContext<RadonTypes>,
     ) -> Result<RadonTypes, RadError> {
-        self.operate(call)
+        match call {
+            (RadonOpCodes::IntegerAge, None) => {
+                integer_operators::age(self, context).map(Into::into)
+            }
+            (RadonOpCodes::IntegerMaxAge, Some(args)) => {
+                integer_operators::max_age(self, args.as_slice(), context).map(Into::into)
+            }
+            other => self.operate(other),
+        }
     }
 }
 
@@ -10,7 +10,7 @@ use witnet_data_structures::radon_report::ReportContext;
 
 use crate::{
     error::RadError,
-    operators::{identity, map as map_operators, Operable, RadonOpCodes},
+    operators::{identity, map as map_operators, path, Operable, RadonOpCodes},
     script::RadonCall,
     types::{RadonType, RadonTypes},
 };
@@ -22,6 +22,17 @@ pub struct RadonMap {
     value: BTreeMap<String, RadonTypes>,
 }
 
+impl RadonMap {
+    /// Number of entries, without cloning them as `value()` does.
+    pub fn len(&self) -> usize {
+        self.value.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+}
+
 impl RadonType<BTreeMap<String, RadonTypes>> for RadonMap {
     fn value(&self) -> BTreeMap<String, RadonTypes> {
         self.value.clone()
@@ -116,6 +127,9 @@ impl Operable for RadonMap {
     fn operate(&self, call: &RadonCall) -> Result<RadonTypes, RadError> {
         match call {
             (RadonOpCodes::Identity, None) => identity(RadonTypes::from(self.clone())),
+            (RadonOpCodes::GetPath, Some(args)) => {
+                path::get_path(RadonTypes::from(self.clone()), args.as_slice())
+            }
             (RadonOpCodes::MapGetArray, Some(args)) => {
                 map_operators::get_array(self, args.as_slice()).map(RadonTypes::from)
             }
@@ -137,6 +151,7 @@ impl Operable for RadonMap {
             (RadonOpCodes::MapGetString, Some(args)) => {
                 map_operators::get_string(self, args.as_slice()).map(RadonTypes::from)
             }
+            (RadonOpCodes::MapEntries, None) => Ok(RadonTypes::from(map_operators::entries(self))),
             (RadonOpCodes::MapKeys, None) => Ok(RadonTypes::from(map_operators::keys(self))),
             (RadonOpCodes::MapValues, None) => Ok(RadonTypes::from(map_operators::values(self))),
             (op_code, args) => Err(RadError::UnsupportedOperator {
@@ -20,7 +20,8 @@ use crate::{
 };
 use witnet_data_structures::{
     chain::Hash,
-    radon_error::{try_from_cbor_value_for_serde_cbor_value, RadonError},
+    mainnet_validations::ActiveWips,
+    radon_error::{try_from_cbor_value_for_serde_cbor_value, RadonError, RadonErrors},
     radon_report::{RadonReport, ReportContext, TypeLike},
 };
 
@@ -451,16 +452,27 @@ impl TryFrom<CborValue> for RadonTypes {
 /// The `err_action` argument allows the caller of this function to decide whether
 /// it should act in a lossy way, i.e. ignoring items that cannot be decoded or replacing them with
 /// default values.
+/// Errors whose codes are not active yet are handled as unknown codes, as older nodes do.
 pub fn serial_iter_decode<T>(
     iter: &mut dyn Iterator<Item = (&[u8], &T)>,
     err_action: fn(RadError, &[u8], &T) -> Option<RadonReport<RadonTypes>>,
+    active_wips: &ActiveWips,
 ) -> Vec<RadonReport<RadonTypes>> {
     iter.filter_map(
         |(slice, inner)| match panic::catch_unwind(|| RadonTypes::try_from(slice)) {
-            Ok(Ok(radon_types)) => Some(RadonReport::from_result(
-                Ok(radon_types),
-                &ReportContext::default(),
-            )),
+            Ok(Ok(radon_types)) => match inactive_error_code(&radon_types, active_wips) {
+                Some(error_code) => err_action(
+                    RadError::DecodeRadonErrorUnknownCode {
+                        error_code: error_code.into(),
+                    },
+                    slice,
+                    inner,
+                ),
+                None => Some(RadonReport::from_result(
+                    Ok(radon_types),
+                    &ReportContext::default(),
+                )),
+            },
             Ok(Err(e)) => err_action(e, slice, inner),
             Err(_e) => {
                 log::error!("Panic found during CBOR conversion");
@@ -471,9 +483,22 @@ pub fn serial_iter_decode<T>(
     .collect()
 }
 
+/// The code of a decoded error that cannot be part of a reveal yet, if any.
+fn inactive_error_code(radon_types: &RadonTypes, active_wips: &ActiveWips) -> Option<RadonErrors> {
+    match radon_types {
+        RadonTypes::RadonError(error) => error
+            .inner()
+            .try_into_error_code()
+            .ok()
+            .filter(|kind| !kind.is_active(active_wips)),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tests::all_wips_active;
     use std::collections::HashSet;
 
     #[test]
@@ -495,31 +520,40 @@ mod tests {
 
         // No reveals: returns empty vector
         let zero_empty_bytes: Vec<(&[u8], &())> = vec![];
-        let empty: Vec<_> =
-            serial_iter_decode(&mut zero_empty_bytes.into_iter(), ignore_invalid_fn)
-                .into_iter()
-                .map(|report| report.into_inner())
-                .collect();
+        let empty: Vec<_> = serial_iter_decode(
+            &mut zero_empty_bytes.into_iter(),
+            ignore_invalid_fn,
+            &all_wips_active(),
+        )
+        .into_iter()
+        .map(|report| report.into_inner())
+        .collect();
         assert_eq!(empty, vec![]);
 
         // One reveal with zero bytes: return err_action
         // In this case, filter out invalid reveals, so it returns empty vector
         let one_empty_bytes: Vec<(&[u8], &())> = vec![(&[], &())];
-        let still_empty: Vec<_> =
-            serial_iter_decode(&mut one_empty_bytes.into_iter(), ignore_invalid_fn)
-                .into_iter()
-                .map(|report| report.into_inner())
-                .collect();
+        let still_empty: Vec<_> = serial_iter_decode(
+            &mut one_empty_bytes.into_iter(),
+            ignore_invalid_fn,
+            &all_wips_active(),
+        )
+        .into_iter()
+        .map(|report| report.into_inner())
+        .collect();
         assert_eq!(still_empty, vec![]);
 
         // One reveal with zero bytes: return err_action
         // In this case, replace invalid reveals with RadError::MalformedReveal
         let one_empty_bytes: Vec<(&[u8], &())> = vec![(&[], &())];
-        let rad_decode_error_as_result: Vec<_> =
-            serial_iter_decode(&mut one_empty_bytes.into_iter(), malformed_reveal_fn)
-                .into_iter()
-                .map(|report| report.into_inner())
-                .collect();
+        let rad_decode_error_as_result: Vec<_> = serial_iter_decode(
+            &mut one_empty_bytes.into_iter(),
+            malformed_reveal_fn,
+            &all_wips_active(),
+        )
+        .into_iter()
+        .map(|report| report.into_inner())
+        .collect();
         assert_eq!(rad_decode_error_as_result, vec![malformed_reveal]);
     }
 
@@ -591,11 +625,68 @@ mod tests {
 
         // One reveal with value RadonErrors(0xE0)
         let cbor_bytes: Vec<(&[u8], &())> = vec![(&[0xD8, 0x27, 0x81, 0x18, 0xE0], &())];
-        let rad_decode_error_as_result: Vec<_> =
-            serial_iter_decode(&mut cbor_bytes.into_iter(), malformed_reveal_fn)
-                .into_iter()
-                .map(|report| report.into_inner())
-                .collect();
+        let rad_decode_error_as_result: Vec<_> = serial_iter_decode(
+            &mut cbor_bytes.into_iter(),
+            malformed_reveal_fn,
+            &all_wips_active(),
+        )
+        .into_iter()
+        .map(|report| report.into_inner())
+        .collect();
         assert_eq!(rad_decode_error_as_result, vec![malformed_reveal]);
     }
+
+    #[test]
+    fn serial_iter_decode_inactive_radon_errors() {
+        #[allow(clippy::trivially_copy_pass_by_ref, clippy::unnecessary_wraps)]
+        fn malformed_reveal_fn(e: RadError, _: &[u8], _: &()) -> Option<RadonReport<RadonTypes>> {
+            assert!(matches!(e, RadError::DecodeRadonErrorUnknownCode { .. }));
+
+            Some(RadonReport::from_result(
+                Err(RadError::MalformedReveal),
+                &ReportContext::default(),
+            ))
+        }
+
+        let malformed_reveal =
+            RadonTypes::RadonError(RadonError::try_from(RadError::MalformedReveal).unwrap());
+        let before_wip0021 = ActiveWips {
+            active_wips: Default::default(),
+            block_epoch: 0,
+        };
+
+        // The errors added by WIP0021 are unknown before it
+        let errors = vec![
+            RadError::ScriptTooExpensive,
+            RadError::ParseDateTime {
+                format: "%Y".to_string(),
+            },
+            RadError::StaleTimestamp { max_age: 60 },
+        ];
+        for error in errors {
+            let error = RadonTypes::RadonError(RadonError::try_from(error).unwrap());
+            let cbor_bytes = error.encode().unwrap();
+            let reveals: Vec<(&[u8], &())> = vec![(&cbor_bytes, &())];
+
+            let rad_decode_error_as_result: Vec<_> = serial_iter_decode(
+                &mut reveals.clone().into_iter(),
+                malformed_reveal_fn,
+                &before_wip0021,
+            )
+            .into_iter()
+            .map(|report| report.into_inner())
+            .collect();
+            assert_eq!(rad_decode_error_as_result, vec![malformed_reveal.clone()]);
+
+            let rad_decode_result: Vec<_> = serial_iter_decode(
+                &mut reveals.into_iter(),
+                malformed_reveal_fn,
+                &all_wips_active(),
+            )
+            .into_iter()
+            .map(|report| report.into_inner())
+            .collect();
+            assert_eq!(rad_decode_result, vec![error]);
+        }
+    }
 }
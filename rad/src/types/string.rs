@@ -21,6 +21,17 @@ pub struct RadonString {
     value: String,
 }
 
+impl RadonString {
+    /// Number of bytes, without cloning them as `value()` does.
+    pub fn len(&self) -> usize {
+        self.value.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+}
+
 impl RadonType<String> for RadonString {
     fn value(&self) -> String {
         self.value.clone()
@@ -82,6 +93,12 @@ impl Operable for RadonString {
     fn operate(&self, call: &RadonCall) -> Result<RadonTypes, RadError> {
         match call {
             (RadonOpCodes::Identity, None) => identity(RadonTypes::from(self.clone())),
+            (RadonOpCodes::StringAsBytes, None) => {
+                string_operators::to_bytes(self, &[]).map(RadonTypes::from)
+            }
+            (RadonOpCodes::StringAsBytes, Some(args)) => {
+                string_operators::to_bytes(self, args.as_slice()).map(RadonTypes::from)
+            }
             (RadonOpCodes::StringAsFloat, None) => string_operators::to_float(self)
                 .map(RadonTypes::from)
                 .map_err(Into::into),
@@ -97,6 +114,9 @@ impl Operable for RadonString {
             (RadonOpCodes::StringParseJSONMap, None) => string_operators::parse_json_map(self)
                 .map(RadonTypes::from)
                 .map_err(Into::into),
+            (RadonOpCodes::StringParseXML, None) => string_operators::parse_xml(self)
+                .map(RadonTypes::from)
+                .map_err(Into::into),
             (RadonOpCodes::StringMatch, Some(args)) => {
                 string_operators::string_match(self, args.as_slice()).map(RadonTypes::from)
             }
@@ -109,6 +129,18 @@ impl Operable for RadonString {
             (RadonOpCodes::StringToUpperCase, None) => {
                 Ok(RadonTypes::from(string_operators::to_uppercase(self)))
             }
+            (RadonOpCodes::StringRegexCapture, Some(args)) => {
+                string_operators::regex_capture(self, args.as_slice()).map(RadonTypes::from)
+            }
+            (RadonOpCodes::StringRegexFindAll, Some(args)) => {
+                string_operators::regex_find_all(self, args.as_slice()).map(RadonTypes::from)
+            }
+            (RadonOpCodes::StringRegexReplace, Some(args)) => {
+                string_operators::regex_replace(self, args.as_slice()).map(RadonTypes::from)
+            }
+            (RadonOpCodes::StringParseDateTime, Some(args)) => {
+                string_operators::parse_date_time(self, args.as_slice()).map(RadonTypes::from)
+            }
             (op_code, args) => Err(RadError::UnsupportedOperator {
                 input_type: RADON_STRING_TYPE_NAME.to_string(),
                 operator: op_code.to_string(),
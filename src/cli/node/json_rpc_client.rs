@@ -5,13 +5,13 @@ use prettytable::{cell, row, Table};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     convert::{TryFrom, TryInto},
     fmt,
     fs::File,
     io::{self, BufRead, BufReader, Read, Write},
     net::{SocketAddr, TcpStream},
-    path::Path,
+    path::{Path, PathBuf},
     str::FromStr,
 };
 
@@ -22,9 +22,10 @@ use witnet_crypto::{
 use witnet_data_structures::{
     chain::{
         Block, ConsensusConstants, DataRequestInfo, DataRequestOutput, Environment, Epoch,
-        KeyedSignature, NodeStats, OutputPointer, PublicKey, PublicKeyHash, StateMachine,
-        SyncStatus, ValueTransferOutput,
+        KeyedSignature, NodeStats, OutputPointer, PublicKey, PublicKeyHash, RADRetrieve, RADType,
+        StateMachine, SyncStatus, ValueTransferOutput,
     },
+    mainnet_validations::{current_active_wips, ActiveWips, TapiEngine},
     proto::ProtobufConvert,
     transaction::Transaction,
     transaction_factory::NodeBalance,
@@ -36,9 +37,18 @@ use witnet_node::actors::{
     },
     messages::{BuildVtt, GetReputationResult, SignalingInfo},
 };
-use witnet_rad::types::RadonTypes;
+use witnet_rad::{
+    error::RadError,
+    language,
+    trace::{self, ScriptTrace},
+    transport::RetrievalTransport,
+    type_check,
+    types::RadonTypes,
+};
 use witnet_util::{credentials::create_credentials_file, timestamp::pretty_print};
-use witnet_validations::validations::{validate_data_request_output, validate_rad_request, Wit};
+use witnet_validations::validations::{
+    self, validate_data_request_output, validate_rad_request, Wit,
+};
 
 pub fn raw(addr: SocketAddr) -> Result<(), failure::Error> {
     let mut stream = start_client(addr)?;
@@ -511,20 +521,30 @@ pub fn send_vtt(
     Ok(())
 }
 
-fn run_dr_locally(dr: &DataRequestOutput) -> Result<RadonTypes, failure::Error> {
+fn run_dr_locally(
+    dr: &DataRequestOutput,
+    transport: &dyn RetrievalTransport,
+) -> Result<RadonTypes, failure::Error> {
+    let active_wips = current_active_wips();
     // Block on data request retrieval because the CLI application blocks everywhere anyway
-    let run_retrieval_blocking =
-        |retrieve| futures::executor::block_on(witnet_rad::run_retrieval(retrieve));
+    let run_retrieval_blocking = |retrieve| {
+        futures::executor::block_on(witnet_rad::run_retrieval_with_transport(
+            retrieve,
+            transport,
+            None,
+            &active_wips,
+        ))
+    };
 
     let mut retrieval_results = vec![];
     for r in &dr.data_request.retrieve {
-        log::info!("Running retrieval for {}", r.url);
+        log::info!("Running retrieval for {}", RetrievalSource(r));
         retrieval_results.push(run_retrieval_blocking(r)?);
     }
 
     log::info!("Running aggregation with values {:?}", retrieval_results);
     let aggregation_result =
-        witnet_rad::run_aggregation(retrieval_results, &dr.data_request.aggregate)?;
+        witnet_rad::run_aggregation(retrieval_results, &dr.data_request.aggregate, &active_wips)?;
     log::info!("Aggregation result: {:?}", aggregation_result);
 
     // Assume that all the required witnesses will report the same value
@@ -534,7 +554,8 @@ fn run_dr_locally(dr: &DataRequestOutput) -> Result<RadonTypes, failure::Error>
             .map(RadonTypes::try_from)
             .collect();
     log::info!("Running tally with values {:?}", reported_values);
-    let tally_result = witnet_rad::run_tally(reported_values?, &dr.data_request.tally)?;
+    let tally_result =
+        witnet_rad::run_tally(reported_values?, &dr.data_request.tally, &active_wips)?;
     log::info!("Tally result: {:?}", tally_result);
 
     Ok(tally_result)
@@ -548,7 +569,7 @@ fn deserialize_and_validate_hex_dr(hex_bytes: String) -> Result<DataRequestOutpu
     log::debug!("{}", serde_json::to_string(&dr)?);
 
     validate_data_request_output(&dr)?;
-    validate_rad_request(&dr.data_request)?;
+    validate_rad_request(&dr.data_request, &current_active_wips())?;
 
     // Is the data request serialized correctly?
     // Check that serializing the deserialized struct results in exactly the same bytes
@@ -574,10 +595,12 @@ pub fn send_dr(
     hex_bytes: String,
     fee: u64,
     run: bool,
+    transport: &dyn RetrievalTransport,
 ) -> Result<(), failure::Error> {
     let dr_output = deserialize_and_validate_hex_dr(hex_bytes)?;
+    check_dr_types(&dr_output)?;
     if run {
-        run_dr_locally(&dr_output)?;
+        run_dr_locally(&dr_output, transport)?;
     }
 
     let bdr_params = json!({"dro": dr_output, "fee": fee});
@@ -593,6 +616,314 @@ pub fn send_dr(
     Ok(())
 }
 
+/// Print the issues found by the static type checker in the RADON scripts of a data request, and
+/// fail if there are any.
+fn check_dr_types(dr_output: &DataRequestOutput) -> Result<(), failure::Error> {
+    let issues = type_check::check_rad_request(&dr_output.data_request, &current_active_wips());
+    for issue in &issues {
+        println!("{}", issue);
+    }
+    if !issues.is_empty() {
+        bail!(
+            "Found {} issues in the RADON scripts of the data request",
+            issues.len()
+        );
+    }
+
+    Ok(())
+}
+
+/// Check a serialized data request for type errors in its RADON scripts, without sending it.
+pub fn check_request(hex_bytes: String) -> Result<(), failure::Error> {
+    let dr_output = deserialize_and_validate_hex_dr(hex_bytes)?;
+    check_dr_types(&dr_output)?;
+
+    println!("No issues found");
+
+    Ok(())
+}
+
+/// Compile a script written in the RADON script language, read from a file or from the standard
+/// input, and print its CBOR serialization in hex format.
+pub fn compile_script(source_path: Option<&Path>) -> Result<(), failure::Error> {
+    let mut source = String::new();
+    match source_path {
+        Some(path) => {
+            File::open(path)?.read_to_string(&mut source)?;
+        }
+        None => {
+            io::stdin().read_to_string(&mut source)?;
+        }
+    }
+    let script = language::compile(&source)?;
+
+    println!("{}", hex::encode(script));
+
+    Ok(())
+}
+
+/// Decompile the CBOR serialization of a RADON script, in hex format, into the RADON script
+/// language.
+pub fn decompile_script(hex_bytes: String) -> Result<(), failure::Error> {
+    let script = hex::decode(hex_bytes)?;
+
+    println!("{}", language::decompile(&script)?);
+
+    Ok(())
+}
+
+/// Where to read the data request to be traced from.
+pub enum DataRequestSource {
+    /// Serialized data request in hex format
+    Hex(String),
+    /// File containing a data request in JSON format, or a `sendRequest` JSON-RPC request
+    JsonFile(PathBuf),
+    /// Hash of a data request transaction, to be fetched from the node
+    Transaction(SocketAddr, String),
+}
+
+impl DataRequestSource {
+    /// Choose the source from the command line options, exactly one of which must be given.
+    pub fn from_options(
+        hex: Option<String>,
+        json_file: Option<PathBuf>,
+        dr_tx_hash: Option<String>,
+        node: SocketAddr,
+    ) -> Result<Self, failure::Error> {
+        match (hex, json_file, dr_tx_hash) {
+            (Some(hex), None, None) => Ok(DataRequestSource::Hex(hex)),
+            (None, Some(path), None) => Ok(DataRequestSource::JsonFile(path)),
+            (None, None, Some(hash)) => Ok(DataRequestSource::Transaction(node, hash)),
+            _ => bail!("Exactly one of --hex, --json-file or --tx must be given"),
+        }
+    }
+
+    fn read(self) -> Result<DataRequestOutput, failure::Error> {
+        match self {
+            DataRequestSource::Hex(hex_bytes) => deserialize_and_validate_hex_dr(hex_bytes),
+            DataRequestSource::JsonFile(path) => read_json_dr(&path),
+            DataRequestSource::Transaction(addr, hash) => get_transaction_dr(addr, hash),
+        }
+    }
+}
+
+fn read_json_dr(path: &Path) -> Result<DataRequestOutput, failure::Error> {
+    let mut value: serde_json::Value = serde_json::from_reader(File::open(path)?)?;
+    // The examples of data requests are `sendRequest` JSON-RPC requests, take the data request
+    // from their params
+    if let Some(dro) = value.pointer_mut("/params/dro") {
+        value = dro.take();
+    }
+    let dr: DataRequestOutput = serde_json::from_value(value)?;
+
+    validate_data_request_output(&dr)?;
+    validate_rad_request(&dr.data_request, &current_active_wips())?;
+
+    Ok(dr)
+}
+
+fn get_transaction_dr(addr: SocketAddr, hash: String) -> Result<DataRequestOutput, failure::Error> {
+    let mut stream = start_client(addr)?;
+    let request = format!(
+        r#"{{"jsonrpc": "2.0","method": "getTransaction", "params": [{:?}], "id": "1"}}"#,
+        hash,
+    );
+    let response = send_request(&mut stream, &request)?;
+    let transaction: GetTransactionOutput = parse_response(&response)?;
+
+    match transaction.transaction {
+        Transaction::DataRequest(dr_tx) => Ok(dr_tx.body.dr_output),
+        _ => bail!("This is not a data request transaction"),
+    }
+}
+
+/// Shorten a value so that it fits in a table cell.
+fn truncate_cell(text: String) -> String {
+    const MAX_LENGTH: usize = 60;
+
+    if text.chars().count() > MAX_LENGTH {
+        format!("{}...", text.chars().take(MAX_LENGTH).collect::<String>())
+    } else {
+        text
+    }
+}
+
+fn add_script_trace_rows(table: &mut Table, script: &ScriptTrace) {
+    let stage = script.stage.to_string();
+    for (i, call) in script.calls.iter().enumerate() {
+        let operator = match &call.arguments {
+            Some(arguments) => format!("{}({:?})", call.operator, arguments),
+            None => call.operator.to_string(),
+        };
+        let output = match &call.output {
+            Ok(value) => value.to_string(),
+            Err(error) => error.to_string(),
+        };
+        // Only show the stage in the first row of each script
+        let stage = if i == 0 { stage.as_str() } else { "" };
+        table.add_row(row![
+            stage,
+            i,
+            truncate_cell(operator),
+            truncate_cell(call.input.to_string()),
+            truncate_cell(output),
+            format!("{:?}", call.elapsed),
+            call.cost,
+        ]);
+    }
+    if script.calls.is_empty() {
+        let input = script
+            .input
+            .as_ref()
+            .map(ToString::to_string)
+            .unwrap_or_default();
+        let output = match &script.result {
+            Ok(value) => value.to_string(),
+            Err(error) => error.to_string(),
+        };
+        table.add_row(row![
+            stage,
+            "",
+            "",
+            truncate_cell(input),
+            truncate_cell(output),
+            "",
+            ""
+        ]);
+    }
+}
+
+/// Run a data request locally, and print the input, output and execution time of every call of
+/// its RADON scripts.
+pub fn trace_request(
+    source: DataRequestSource,
+    json: bool,
+    transport: &dyn RetrievalTransport,
+) -> Result<(), failure::Error> {
+    let dr_output = source.read()?;
+
+    let trace =
+        trace::trace_data_request(&dr_output.data_request, transport, &current_active_wips());
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&trace)?);
+    } else {
+        let mut table = Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+        table.set_titles(row![
+            "Stage", "Call", "Operator", "Input", "Output", "Time", "Cost"
+        ]);
+        for script in trace
+            .retrieve
+            .iter()
+            .chain(vec![&trace.aggregate, &trace.tally])
+        {
+            add_script_trace_rows(&mut table, script);
+        }
+        table.printstd();
+
+        println!("Result: {}", trace.tally.intercepted_result());
+    }
+
+    Ok(())
+}
+
+/// Compute locally the tally of a data request for the given reveals, and print which reveals
+/// would be considered liars or errors and how the rewards and the collateral would be
+/// distributed.
+pub fn simulate_tally(
+    source: DataRequestSource,
+    reveals: Vec<String>,
+    commits: Option<usize>,
+    min_consensus_percentage: Option<u32>,
+    collateral_minimum: u64,
+    environment: Environment,
+    json: bool,
+) -> Result<(), failure::Error> {
+    let mut dr_output = source.read()?;
+    if let Some(min_consensus_percentage) = min_consensus_percentage {
+        dr_output.min_consensus_percentage = min_consensus_percentage;
+    }
+    let reveals = reveals
+        .iter()
+        .map(|reveal| Ok(RadonTypes::try_from(hex::decode(reveal)?.as_slice())?))
+        .collect::<Result<Vec<RadonTypes>, failure::Error>>()?;
+    let commits = commits.unwrap_or(reveals.len());
+
+    // Simulate the tally with the latest protocol rules
+    let mut tapi_engine = TapiEngine::default();
+    tapi_engine.initialize_wip_information(environment);
+    let active_wips = ActiveWips {
+        active_wips: tapi_engine.wip_activation,
+        block_epoch: Epoch::MAX,
+    };
+    let simulation = validations::simulate_tally(
+        &dr_output,
+        reveals.clone(),
+        commits,
+        collateral_minimum,
+        &active_wips,
+    )?;
+
+    if json {
+        let reveals: Vec<_> = reveals
+            .iter()
+            .zip(simulation.liars.iter().zip(simulation.errors.iter()))
+            .zip(simulation.payouts.iter())
+            .map(|((value, (liar, error)), payout)| {
+                json!({"value": value, "liar": liar, "error": error, "payout": payout})
+            })
+            .collect();
+        let output = json!({
+            "result": simulation.result,
+            "consensus": simulation.consensus,
+            "min_consensus": f64::from(dr_output.min_consensus_percentage) / 100.0,
+            "reveals": reveals,
+            "non_revealers": commits - reveals.len(),
+            "non_revealer_payout": simulation.non_revealer_payout,
+            "collateral_remainder": simulation.collateral_remainder,
+            "tally_change": simulation.tally_change,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+    } else {
+        let mut table = Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_NO_BORDER_LINE_SEPARATOR);
+        table.set_titles(row!["Reveal", "Value", "Liar", "Error", "Payout (in wits)"]);
+        for (i, value) in reveals.iter().enumerate() {
+            table.add_row(row![
+                i,
+                truncate_cell(value.to_string()),
+                simulation.liars[i],
+                simulation.errors[i],
+                Wit::from_nanowits(simulation.payouts[i]).to_string(),
+            ]);
+        }
+        table.printstd();
+
+        println!("Result: {}", simulation.result);
+        println!(
+            "Consensus: {:.2}% (minimum {}%)",
+            simulation.consensus * 100.0,
+            dr_output.min_consensus_percentage
+        );
+        println!(
+            "Payout to each of the {} non-revealers: {} wits",
+            commits - reveals.len(),
+            Wit::from_nanowits(simulation.non_revealer_payout)
+        );
+        println!(
+            "Collateral remainder for the miner: {} wits",
+            Wit::from_nanowits(simulation.collateral_remainder)
+        );
+        println!(
+            "Change for the data request creator: {} wits",
+            Wit::from_nanowits(simulation.tally_change)
+        );
+    }
+
+    Ok(())
+}
+
 pub fn master_key_export(
     addr: SocketAddr,
     write_to_path: Option<&Path>,
@@ -643,6 +974,11 @@ struct DataRequestTransactionInfo {
     reveals: Option<Vec<(String, String, String)>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tally: Option<String>,
+    // The reveals and the tally result in the lossless JSON encoding of RADON values
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reveal_results: Option<BTreeMap<String, serde_json::Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tally_result: Option<serde_json::Value>,
     #[serde(skip)]
     print_data_request: bool,
 }
@@ -654,6 +990,36 @@ struct DataRequestState {
     current_reveal_round: u16,
 }
 
+/// Retrieval source of a data request, displayed as its method and URL followed by the rest of
+/// the HTTP request: the content type, the extra headers and the body.
+struct RetrievalSource<'a>(&'a RADRetrieve);
+
+impl fmt::Display for RetrievalSource<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let retrieve = self.0;
+        match retrieve.kind {
+            RADType::HttpGet => write!(f, "GET {}", retrieve.url)?,
+            RADType::HttpPost => write!(f, "POST {}", retrieve.url)?,
+            RADType::Rng => write!(f, "RNG")?,
+        }
+        if !retrieve.content_type.is_empty() {
+            write!(f, "\n        Content-Type: {}", retrieve.content_type)?;
+        }
+        for (name, value) in &retrieve.headers {
+            write!(f, "\n        {}: {}", name, value)?;
+        }
+        if !retrieve.body.is_empty() {
+            write!(
+                f,
+                "\n        Body: {}",
+                String::from_utf8_lossy(&retrieve.body)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
 impl fmt::Display for DataRequestTransactionInfo {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(
@@ -729,6 +1095,11 @@ impl fmt::Display for DataRequestTransactionInfo {
             )?;
         }
 
+        writeln!(f, "Sources:")?;
+        for retrieve in &self.data_request_output.data_request.retrieve {
+            writeln!(f, "    {}", RetrievalSource(retrieve))?;
+        }
+
         if let Some(reveals) = &self.reveals {
             let data_request_state = self.data_request_state.as_ref().unwrap();
             if data_request_state.stage == "COMMIT" {
@@ -843,6 +1214,8 @@ pub fn data_request_report(
         dr_output.collateral = consensus_constants.collateral_minimum;
     }
 
+    let mut reveal_results = None;
+    let mut tally_result = None;
     let (data_request_state, reveals, tally, block_hash_tally_tx) = if transaction_block_hash
         .is_none()
     {
@@ -885,6 +1258,15 @@ pub fn data_request_report(
             .map(|t| RadonTypes::try_from(t.tally.as_slice()))
             .transpose()?;
 
+        reveal_results = Some(
+            reveals
+                .iter()
+                .filter_map(|(pkh, reveal)| Some((pkh.to_string(), reveal.as_ref()?)))
+                .map(|(pkh, reveal)| Ok((pkh, witnet_rad::json::to_json(reveal)?)))
+                .collect::<Result<BTreeMap<_, _>, RadError>>()?,
+        );
+        tally_result = tally.as_ref().map(witnet_rad::json::to_json).transpose()?;
+
         (
             Some(data_request_state),
             Some(
@@ -939,6 +1321,8 @@ pub fn data_request_report(
         data_request_state,
         reveals,
         tally,
+        reveal_results,
+        tally_result,
         print_data_request,
     };
 
@@ -1486,4 +1870,33 @@ mod tests {
 
         assert!(verify(&secp, &public_key, signed_data.as_ref(), &signature).is_ok());
     }
+
+    #[test]
+    fn display_retrieval_source() {
+        let get = RADRetrieve {
+            kind: RADType::HttpGet,
+            url: "https://example.com/price".to_string(),
+            ..RADRetrieve::default()
+        };
+        assert_eq!(
+            RetrievalSource(&get).to_string(),
+            "GET https://example.com/price"
+        );
+
+        let post = RADRetrieve {
+            kind: RADType::HttpPost,
+            url: "https://example.com/rpc".to_string(),
+            body: br#"{"id":1}"#.to_vec(),
+            content_type: "application/json".to_string(),
+            headers: vec![("X-Api-Version".to_string(), "2".to_string())],
+            ..RADRetrieve::default()
+        };
+        assert_eq!(
+            RetrievalSource(&post).to_string(),
+            "POST https://example.com/rpc\n        \
+             Content-Type: application/json\n        \
+             X-Api-Version: 2\n        \
+             Body: {\"id\":1}"
+        );
+    }
 }
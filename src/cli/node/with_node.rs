@@ -10,6 +10,10 @@ use structopt::StructOpt;
 use witnet_config::config::Config;
 use witnet_data_structures::chain::Epoch;
 use witnet_node as node;
+use witnet_rad::{
+    proxy::RetrievalProxy,
+    transport::{Cassette, HttpTransport, RetrievalTransport},
+};
 
 use super::json_rpc_client as rpc;
 
@@ -153,7 +157,65 @@ pub fn exec_cmd(
             hex,
             fee,
             run,
-        } => rpc::send_dr(node.unwrap_or(config.jsonrpc.server_address), hex, fee, run),
+            record,
+            replay,
+        } => {
+            let transport = retrieval_transport(record, replay, &config)?;
+            rpc::send_dr(
+                node.unwrap_or(config.jsonrpc.server_address),
+                hex,
+                fee,
+                run,
+                transport.as_ref(),
+            )
+        }
+        Command::CheckRequest { hex } => rpc::check_request(hex),
+        Command::TraceRequest {
+            node,
+            hex,
+            json_file,
+            dr_tx_hash,
+            json,
+            record,
+            replay,
+        } => {
+            let source = rpc::DataRequestSource::from_options(
+                hex,
+                json_file,
+                dr_tx_hash,
+                node.unwrap_or(config.jsonrpc.server_address),
+            )?;
+            let transport = retrieval_transport(record, replay, &config)?;
+            rpc::trace_request(source, json, transport.as_ref())
+        }
+        Command::SimulateTally {
+            node,
+            hex,
+            json_file,
+            dr_tx_hash,
+            reveals,
+            commits,
+            min_consensus_percentage,
+            json,
+        } => {
+            let source = rpc::DataRequestSource::from_options(
+                hex,
+                json_file,
+                dr_tx_hash,
+                node.unwrap_or(config.jsonrpc.server_address),
+            )?;
+            rpc::simulate_tally(
+                source,
+                reveals,
+                commits,
+                min_consensus_percentage,
+                config.consensus_constants.collateral_minimum,
+                config.environment,
+                json,
+            )
+        }
+        Command::CompileScript { source } => rpc::compile_script(source.as_deref()),
+        Command::DecompileScript { hex } => rpc::decompile_script(hex),
         Command::Raw { node } => rpc::raw(node.unwrap_or(config.jsonrpc.server_address)),
         Command::ShowConfig => {
             let serialized = toml::to_string(&config.to_partial()).unwrap();
@@ -250,6 +312,36 @@ pub fn exec_cmd(
     }
 }
 
+/// Transport for running the retrievals of a data request locally: a cassette for recording or
+/// replaying them, if any of the paths is given, or the network otherwise. The requests are sent
+/// through the data request proxy of the configuration, if any.
+fn retrieval_transport(
+    record: Option<PathBuf>,
+    replay: Option<PathBuf>,
+    config: &Config,
+) -> Result<Box<dyn RetrievalTransport>, failure::Error> {
+    let mining = &config.mining;
+    let proxy = mining
+        .data_request_proxy_url
+        .as_ref()
+        .map(|url| {
+            RetrievalProxy::new(
+                url,
+                mining.data_request_proxy_username.as_deref(),
+                mining.data_request_proxy_password.as_deref(),
+                mining.data_request_proxy_bypass.clone(),
+            )
+        })
+        .transpose()?;
+    let http_transport = HttpTransport::default().with_proxy(proxy);
+
+    Ok(match (record, replay) {
+        (Some(path), _) => Box::new(Cassette::record(path, http_transport)?),
+        (None, Some(path)) => Box::new(Cassette::replay(path)?),
+        (None, None) => Box::new(http_transport),
+    })
+}
+
 #[derive(Debug, StructOpt)]
 pub enum Command {
     #[structopt(name = "server", about = "Run a Witnet node server", alias = "run")]
@@ -525,6 +617,105 @@ pub enum Command {
         /// Run the data request locally before sending, to ensure correctness of RADON scripts
         #[structopt(long = "run")]
         run: bool,
+        /// Record the HTTP responses of the retrievals performed by `--run` into this file
+        #[structopt(long = "record", requires = "run", conflicts_with = "replay")]
+        record: Option<PathBuf>,
+        /// Replay the HTTP responses of the retrievals performed by `--run` from this file,
+        /// instead of accessing the network
+        #[structopt(long = "replay", requires = "run")]
+        replay: Option<PathBuf>,
+    },
+    #[structopt(
+        name = "checkRequest",
+        alias = "check-request",
+        about = "Check the RADON scripts of a serialized data request for type errors"
+    )]
+    CheckRequest {
+        #[structopt(long = "hex")]
+        hex: String,
+    },
+    #[structopt(
+        name = "traceRequest",
+        alias = "trace-request",
+        about = "Run a data request locally and show the input, output and execution time of every call of its RADON scripts"
+    )]
+    TraceRequest {
+        /// Socket address of the Witnet node to query when using `--tx`
+        #[structopt(short = "n", long = "node")]
+        node: Option<SocketAddr>,
+        /// Serialized data request in hex format
+        #[structopt(long = "hex")]
+        hex: Option<String>,
+        /// File containing the data request in JSON format. A `sendRequest` JSON-RPC request, like
+        /// the ones in the `examples` folder, is also accepted
+        #[structopt(long = "json-file")]
+        json_file: Option<PathBuf>,
+        /// Hash of a data request transaction, which is fetched from the node
+        #[structopt(long = "tx")]
+        dr_tx_hash: Option<String>,
+        /// Show output in JSON format
+        #[structopt(long = "json")]
+        json: bool,
+        /// Record the HTTP responses of the retrievals into this file
+        #[structopt(long = "record", conflicts_with = "replay")]
+        record: Option<PathBuf>,
+        /// Replay the HTTP responses of the retrievals from this file, instead of accessing the
+        /// network
+        #[structopt(long = "replay")]
+        replay: Option<PathBuf>,
+    },
+    #[structopt(
+        name = "simulateTally",
+        alias = "simulate-tally",
+        about = "Compute locally the tally of a data request for the given reveals, showing the liars, the errors and the distribution of rewards and collateral"
+    )]
+    SimulateTally {
+        /// Socket address of the Witnet node to query when using `--tx`
+        #[structopt(short = "n", long = "node")]
+        node: Option<SocketAddr>,
+        /// Serialized data request in hex format
+        #[structopt(long = "hex")]
+        hex: Option<String>,
+        /// File containing the data request in JSON format. A `sendRequest` JSON-RPC request, like
+        /// the ones in the `examples` folder, is also accepted
+        #[structopt(long = "json-file")]
+        json_file: Option<PathBuf>,
+        /// Hash of a data request transaction, which is fetched from the node
+        #[structopt(long = "tx")]
+        dr_tx_hash: Option<String>,
+        /// Revealed value, serialized in hex format as in reveal transactions. Can be used many
+        /// times, once for each reveal
+        #[structopt(long = "reveal")]
+        reveals: Vec<String>,
+        /// Number of witnesses that committed. If omitted, defaults to the number of reveals
+        #[structopt(long = "commits")]
+        commits: Option<usize>,
+        /// Use this minimum consensus percentage instead of the one of the data request
+        #[structopt(long = "min-consensus")]
+        min_consensus_percentage: Option<u32>,
+        /// Show output in JSON format
+        #[structopt(long = "json")]
+        json: bool,
+    },
+    #[structopt(
+        name = "compileScript",
+        alias = "compile-script",
+        about = "Compile a retrieval script written in the RADON script language into hex format"
+    )]
+    CompileScript {
+        /// File containing the source code of the script. If omitted, it is read from the
+        /// standard input
+        #[structopt(name = "source")]
+        source: Option<PathBuf>,
+    },
+    #[structopt(
+        name = "decompileScript",
+        alias = "decompile-script",
+        about = "Decompile a retrieval script in hex format into the RADON script language"
+    )]
+    DecompileScript {
+        #[structopt(long = "hex")]
+        hex: String,
     },
     #[structopt(
         name = "config",
@@ -39,6 +39,7 @@ mod compare_block_candidates;
 mod randpoe;
 mod reppoe;
 mod tally_precondition;
+mod tally_simulation;
 
 static ONE_WIT: u64 = 1_000_000_000;
 const MAX_VT_WEIGHT: u32 = 20_000;
@@ -1337,6 +1338,7 @@ fn data_request_no_inputs() {
         &mut signatures_to_verify,
         ONE_WIT,
         MAX_DR_WEIGHT,
+        &all_wips_active(),
     );
     assert_eq!(
         x.unwrap_err().downcast::<TransactionError>().unwrap(),
@@ -1372,6 +1374,7 @@ fn data_request_no_inputs_but_one_signature() {
         &mut signatures_to_verify,
         ONE_WIT,
         MAX_DR_WEIGHT,
+        &all_wips_active(),
     );
     assert_eq!(
         x.unwrap_err().downcast::<TransactionError>().unwrap(),
@@ -1416,6 +1419,7 @@ fn data_request_one_input_but_no_signature() {
         &mut signatures_to_verify,
         ONE_WIT,
         MAX_DR_WEIGHT,
+        &all_wips_active(),
     );
     assert_eq!(
         x.unwrap_err().downcast::<TransactionError>().unwrap(),
@@ -1461,6 +1465,7 @@ fn data_request_one_input_signatures() {
             &mut signatures_to_verify,
             ONE_WIT,
             MAX_DR_WEIGHT,
+            &all_wips_active(),
         )?;
         verify_signatures_test(signatures_to_verify)?;
 
@@ -1502,6 +1507,7 @@ fn data_request_input_double_spend() {
         &mut signatures_to_verify,
         ONE_WIT,
         MAX_DR_WEIGHT,
+        &all_wips_active(),
     );
     assert_eq!(
         x.unwrap_err().downcast::<TransactionError>().unwrap(),
@@ -1540,6 +1546,7 @@ fn data_request_input_not_in_utxo() {
         &mut signatures_to_verify,
         ONE_WIT,
         MAX_DR_WEIGHT,
+        &all_wips_active(),
     );
     assert_eq!(
         x.unwrap_err().downcast::<TransactionError>().unwrap(),
@@ -1583,6 +1590,7 @@ fn data_request_input_not_enough_value() {
         &mut signatures_to_verify,
         ONE_WIT,
         MAX_DR_WEIGHT,
+        &all_wips_active(),
     );
     assert_eq!(
         x.unwrap_err().downcast::<TransactionError>().unwrap(),
@@ -1650,6 +1658,7 @@ fn data_request_output_value_overflow() {
         &mut signatures_to_verify,
         ONE_WIT,
         MAX_DR_WEIGHT,
+        &all_wips_active(),
     );
     assert_eq!(
         x.unwrap_err().downcast::<TransactionError>().unwrap(),
@@ -1685,6 +1694,7 @@ fn test_drtx(dr_output: DataRequestOutput) -> Result<(), failure::Error> {
         &mut signatures_to_verify,
         ONE_WIT,
         u32::max_value(),
+        &all_wips_active(),
     )
     .map(|_| ())
 }
@@ -1710,6 +1720,10 @@ fn example_data_request() -> RADRequest {
             kind: RADType::HttpGet,
             url: "https://blockchain.info/q/latesthash".to_string(),
             script: vec![128],
+            body: vec![],
+            content_type: String::new(),
+            headers: vec![],
+            binary: false,
         }],
         aggregate: RADAggregate {
             filters: vec![],
@@ -1729,6 +1743,10 @@ fn example_data_request_average_mean_reducer() -> RADRequest {
             kind: RADType::HttpGet,
             url: "https://blockchain.info/q/latesthash".to_string(),
             script: vec![128],
+            body: vec![],
+            content_type: String::new(),
+            headers: vec![],
+            binary: false,
         }],
         aggregate: RADAggregate {
             filters: vec![],
@@ -1748,6 +1766,10 @@ fn example_data_request_with_mode_filter() -> RADRequest {
             kind: RADType::HttpGet,
             url: "".to_string(),
             script: vec![0x80],
+            body: vec![],
+            content_type: String::new(),
+            headers: vec![],
+            binary: false,
         }],
         aggregate: RADAggregate {
             filters: vec![],
@@ -1826,6 +1848,10 @@ fn data_request_empty_scripts() {
             kind: RADType::HttpGet,
             url: "".to_string(),
             script: vec![0x80],
+            body: vec![],
+            content_type: String::new(),
+            headers: vec![],
+            binary: false,
         }],
         aggregate: RADAggregate {
             filters: vec![],
@@ -1845,6 +1871,348 @@ fn data_request_empty_scripts() {
     );
 }
 
+#[test]
+fn data_request_http_get_with_body() {
+    let mut data_request = example_data_request();
+    data_request.retrieve[0].body = b"{}".to_vec();
+
+    let x = test_rad_request(data_request);
+    // The data request should be invalid since HTTP-GET sources cannot have a body
+    assert_eq!(
+        x.unwrap_err().downcast::<DataRequestError>().unwrap(),
+        DataRequestError::UnexpectedRetrievalField {
+            kind: RADType::HttpGet,
+            field: "body",
+        },
+    );
+}
+
+#[test]
+fn data_request_http_post() {
+    let mut data_request = example_data_request();
+    data_request.retrieve[0].kind = RADType::HttpPost;
+    data_request.retrieve[0].body =
+        br#"{"jsonrpc":"2.0","method":"getblockcount","id":1}"#.to_vec();
+    data_request.retrieve[0].content_type = "application/json".to_string();
+
+    let x = test_rad_request(data_request);
+    x.unwrap();
+}
+
+#[test]
+fn data_request_http_post_before_wip0020() {
+    let mut data_request = example_data_request();
+    data_request.retrieve[0].kind = RADType::HttpPost;
+    data_request.retrieve[0].content_type = "application/json".to_string();
+
+    let x = validate_rad_request(&data_request, &active_wips_from_mainnet(E));
+    // HTTP-POST sources are only valid after WIP0020
+    assert_eq!(
+        x.unwrap_err().downcast::<DataRequestError>().unwrap(),
+        DataRequestError::UnsupportedRetrievalKind {
+            kind: RADType::HttpPost,
+        },
+    );
+}
+
+#[test]
+fn data_request_http_post_invalid_content_type() {
+    let mut data_request = example_data_request();
+    data_request.retrieve[0].kind = RADType::HttpPost;
+    data_request.retrieve[0].content_type = "application/json\r\nX-Injected: true".to_string();
+
+    let x = test_rad_request(data_request);
+    assert_eq!(
+        x.unwrap_err().downcast::<DataRequestError>().unwrap(),
+        DataRequestError::InvalidContentType {
+            content_type: "application/json\r\nX-Injected: true".to_string(),
+        },
+    );
+}
+
+#[test]
+fn data_request_http_headers() {
+    let mut data_request = example_data_request();
+    data_request.retrieve[0].headers = vec![
+        ("Accept".to_string(), "application/json".to_string()),
+        ("X-Api-Version".to_string(), "2".to_string()),
+    ];
+
+    let x = test_rad_request(data_request);
+    x.unwrap();
+}
+
+#[test]
+fn data_request_http_headers_before_wip0020() {
+    let mut data_request = example_data_request();
+    data_request.retrieve[0].headers = vec![("Accept".to_string(), "application/json".to_string())];
+
+    let x = validate_rad_request(&data_request, &active_wips_from_mainnet(E));
+    // Extra HTTP headers are only valid after WIP0020
+    assert_eq!(
+        x.unwrap_err().downcast::<DataRequestError>().unwrap(),
+        DataRequestError::UnexpectedRetrievalField {
+            kind: RADType::HttpGet,
+            field: "headers",
+        },
+    );
+}
+
+#[test]
+fn data_request_binary_before_wip0020() {
+    let mut data_request = example_data_request();
+    data_request.retrieve[0].binary = true;
+
+    let x = validate_rad_request(&data_request, &active_wips_from_mainnet(E));
+    // Binary responses are only valid after WIP0020
+    assert_eq!(
+        x.unwrap_err().downcast::<DataRequestError>().unwrap(),
+        DataRequestError::UnexpectedRetrievalField {
+            kind: RADType::HttpGet,
+            field: "binary",
+        },
+    );
+}
+
+#[test]
+fn data_request_new_operators_before_wip0021() {
+    let scripts = vec![
+        // [[GetPath, "a"]]
+        (vec![0x81, 0x82, 0x01, 0x61, 0x61], 0x01),
+        // [ArrayFlatten]
+        (vec![0x81, 0x12], 0x12),
+        // [[ArraySome, []]]
+        (vec![0x81, 0x82, 0x18, 0x1C, 0x80], 0x1C),
+        // [[ArrayTake, 1]]
+        (vec![0x81, 0x82, 0x18, 0x1E, 0x01], 0x1E),
+        // [[BooleanMatch, {}, false]]
+        (vec![0x81, 0x83, 0x18, 0x21, 0xA0, 0xF4], 0x21),
+        // [[IntegerMatch, {}, 0]]
+        (vec![0x81, 0x83, 0x18, 0x45, 0xA0, 0x00], 0x45),
+        // [IntegerReciprocal]
+        (vec![0x81, 0x18, 0x4A], 0x4A),
+        // [[IntegerSum, 1]]
+        (vec![0x81, 0x82, 0x18, 0x4B, 0x01], 0x4B),
+        // [IntegerAge]
+        (vec![0x81, 0x18, 0x4C], 0x4C),
+        // [[IntegerMaxAge, 1]]
+        (vec![0x81, 0x82, 0x18, 0x4D, 0x01], 0x4D),
+        // [FloatReciprocal]
+        (vec![0x81, 0x18, 0x5A], 0x5A),
+        // [[FloatSum, 1]]
+        (vec![0x81, 0x82, 0x18, 0x5C, 0x01], 0x5C),
+        // [MapEntries]
+        (vec![0x81, 0x18, 0x60], 0x60),
+        // [StringAsBytes]
+        (vec![0x81, 0x18, 0x71], 0x71),
+        // [StringParseXML]
+        (vec![0x81, 0x18, 0x78], 0x78),
+        // [[StringRegexCapture, "a"]]
+        (vec![0x81, 0x82, 0x18, 0x7B, 0x61, 0x61], 0x7B),
+        // [[StringRegexFindAll, "a"]]
+        (vec![0x81, 0x82, 0x18, 0x7C, 0x61, 0x61], 0x7C),
+        // [[StringRegexReplace, "a", "b"]]
+        (vec![0x81, 0x83, 0x18, 0x7D, 0x61, 0x61, 0x61, 0x62], 0x7D),
+        // [[StringParseDateTime, "a"]]
+        (vec![0x81, 0x82, 0x18, 0x7E, 0x61, 0x61], 0x7E),
+    ];
+    for (script, code) in scripts {
+        let mut data_request = example_data_request();
+        data_request.retrieve[0].script = script;
+
+        let x = validate_rad_request(&data_request, &active_wips_from_mainnet(E));
+        // The new operators are only valid after WIP0021
+        assert_eq!(
+            x.unwrap_err().downcast::<RadError>().unwrap(),
+            RadError::UnknownOperator { code },
+        );
+
+        let x = test_rad_request(data_request);
+        x.unwrap();
+    }
+}
+
+#[test]
+fn data_request_new_operators_in_subscripts_before_wip0021() {
+    let mut data_request = example_data_request();
+    // [[ArrayMap, [StringParseXML]]]
+    data_request.retrieve[0].script = vec![0x81, 0x82, 0x18, 0x1A, 0x81, 0x18, 0x78];
+
+    // Subscripts were never validated, so they cannot be rejected now
+    let x = validate_rad_request(&data_request, &active_wips_from_mainnet(E));
+    x.unwrap();
+}
+
+#[test]
+fn data_request_http_headers_invalid() {
+    let mut data_request = example_data_request();
+    data_request.retrieve[0].headers = vec![("Bad Header".to_string(), "value".to_string())];
+
+    let x = test_rad_request(data_request.clone());
+    assert_eq!(
+        x.unwrap_err().downcast::<DataRequestError>().unwrap(),
+        DataRequestError::InvalidHttpHeader {
+            name: "Bad Header".to_string(),
+            value: "value".to_string(),
+        },
+    );
+
+    data_request.retrieve[0].headers = vec![("Content-Type".to_string(), "text/xml".to_string())];
+    let x = test_rad_request(data_request);
+    assert_eq!(
+        x.unwrap_err().downcast::<DataRequestError>().unwrap(),
+        DataRequestError::ContentTypeInHeaders,
+    );
+}
+
+#[test]
+fn data_request_http_headers_too_heavy() {
+    let mut data_request = example_data_request();
+    data_request.retrieve[0].headers = vec![(
+        "X-Padding".to_string(),
+        "a".repeat(MAX_RETRIEVAL_HEADERS_WEIGHT as usize),
+    )];
+
+    let x = test_rad_request(data_request);
+    assert_eq!(
+        x.unwrap_err().downcast::<DataRequestError>().unwrap(),
+        DataRequestError::HeadersTooHeavy {
+            weight: MAX_RETRIEVAL_HEADERS_WEIGHT + 9,
+            max_weight: MAX_RETRIEVAL_HEADERS_WEIGHT,
+        },
+    );
+}
+
+fn example_rng_data_request() -> RADRequest {
+    RADRequest {
+        time_lock: 0,
+        retrieve: vec![RADRetrieve {
+            kind: RADType::Rng,
+            script: vec![128],
+            ..RADRetrieve::default()
+        }],
+        aggregate: RADAggregate {
+            filters: vec![],
+            reducer: RadonReducers::Mode as u32,
+        },
+        tally: RADTally {
+            filters: vec![],
+            reducer: RadonReducers::HashConcatenate as u32,
+        },
+    }
+}
+
+#[test]
+fn data_request_rng() {
+    let x = test_rad_request(example_rng_data_request());
+    x.unwrap();
+}
+
+#[test]
+fn data_request_rng_before_wip0019() {
+    let x = validate_rad_request(&example_rng_data_request(), &active_wips_from_mainnet(E));
+    // RNG sources are only valid after WIP0019
+    assert_eq!(
+        x.unwrap_err().downcast::<DataRequestError>().unwrap(),
+        DataRequestError::UnsupportedRetrievalKind { kind: RADType::Rng },
+    );
+}
+
+#[test]
+fn data_request_rng_with_url() {
+    let mut data_request = example_rng_data_request();
+    data_request.retrieve[0].url = "https://blockchain.info/q/latesthash".to_string();
+
+    let x = test_rad_request(data_request);
+    // The data request should be invalid since RNG sources do not perform any request
+    assert_eq!(
+        x.unwrap_err().downcast::<DataRequestError>().unwrap(),
+        DataRequestError::UnexpectedRetrievalField {
+            kind: RADType::Rng,
+            field: "url",
+        },
+    );
+}
+
+#[test]
+fn data_request_rng_mixed_with_http() {
+    let mut data_request = example_rng_data_request();
+    data_request
+        .retrieve
+        .push(example_data_request().retrieve[0].clone());
+
+    let x = test_rad_request(data_request);
+    assert_eq!(
+        x.unwrap_err().downcast::<DataRequestError>().unwrap(),
+        DataRequestError::InvalidRngRequest {
+            reason: "an RNG source must be the only source",
+        },
+    );
+}
+
+#[test]
+fn data_request_rng_invalid_tally() {
+    let mut data_request = example_rng_data_request();
+    data_request.tally.reducer = RadonReducers::Mode as u32;
+
+    let x = test_rad_request(data_request.clone());
+    // The random bytes of the witnesses never match, so they must be hashed together
+    assert_eq!(
+        x.unwrap_err().downcast::<DataRequestError>().unwrap(),
+        DataRequestError::InvalidRngRequest {
+            reason: "the tally stage must be a hash concatenation without filters",
+        },
+    );
+
+    data_request.tally = RADTally {
+        filters: vec![RADFilter {
+            op: RadonFilters::Mode as u32,
+            args: vec![],
+        }],
+        reducer: RadonReducers::HashConcatenate as u32,
+    };
+    let x = test_rad_request(data_request);
+    assert_eq!(
+        x.unwrap_err().downcast::<DataRequestError>().unwrap(),
+        DataRequestError::InvalidRngRequest {
+            reason: "the tally stage must be a hash concatenation without filters",
+        },
+    );
+}
+
+#[test]
+fn data_request_median_reducer() {
+    let mut data_request = example_data_request();
+    data_request.tally.reducer = RadonReducers::AverageMedian as u32;
+
+    let x = validate_rad_request(&data_request, &active_wips_from_mainnet(E));
+    // The median can only be used in the aggregation and tally stages after WIP0017
+    assert_eq!(
+        x.unwrap_err().downcast::<RadError>().unwrap(),
+        RadError::UnsupportedReducerInAT {
+            operator: RadonReducers::AverageMedian as u8,
+        },
+    );
+
+    let x = test_rad_request(data_request);
+    x.unwrap();
+}
+
+#[test]
+fn data_request_weighted_reducer() {
+    let mut data_request = example_data_request();
+    data_request.aggregate.reducer = RadonReducers::AverageMeanWeighted as u32;
+
+    let x = test_rad_request(data_request);
+    // The weighted reducers need the weights, which cannot be passed in the aggregation stage
+    assert_eq!(
+        x.unwrap_err().downcast::<RadError>().unwrap(),
+        RadError::UnsupportedReducerInAT {
+            operator: RadonReducers::AverageMeanWeighted as u8,
+        },
+    );
+}
+
 #[test]
 fn data_request_witnesses_0() {
     // A data request with 0 witnesses is invalid
@@ -1975,6 +2343,7 @@ fn dr_validation_weight_limit_exceeded() {
         &mut signatures_to_verify,
         ONE_WIT,
         1625 - 1,
+        &all_wips_active(),
     );
 
     assert_eq!(
@@ -2061,6 +2430,7 @@ fn data_request_miner_fee() {
         &mut signatures_to_verify,
         ONE_WIT,
         MAX_DR_WEIGHT,
+        &all_wips_active(),
     )
     .map(|(_, _, fee)| fee)
     .unwrap();
@@ -2107,6 +2477,7 @@ fn data_request_miner_fee_with_change() {
         &mut signatures_to_verify,
         ONE_WIT,
         MAX_DR_WEIGHT,
+        &all_wips_active(),
     )
     .map(|(_, _, fee)| fee)
     .unwrap();
@@ -2154,6 +2525,7 @@ fn data_request_change_to_different_pkh() {
         &mut signatures_to_verify,
         ONE_WIT,
         MAX_DR_WEIGHT,
+        &all_wips_active(),
     );
 
     assert_eq!(
@@ -2213,6 +2585,7 @@ fn data_request_two_change_outputs() {
         &mut signatures_to_verify,
         ONE_WIT,
         MAX_DR_WEIGHT,
+        &all_wips_active(),
     );
 
     assert_eq!(
@@ -2264,6 +2637,7 @@ fn data_request_miner_fee_with_too_much_change() {
         &mut signatures_to_verify,
         ONE_WIT,
         MAX_DR_WEIGHT,
+        &all_wips_active(),
     );
     assert_eq!(
         x.unwrap_err().downcast::<TransactionError>().unwrap(),
@@ -2311,6 +2685,7 @@ fn data_request_zero_value_output() {
         &mut signatures_to_verify,
         ONE_WIT,
         MAX_DR_WEIGHT,
+        &all_wips_active(),
     );
     assert_eq!(
         x.unwrap_err().downcast::<TransactionError>().unwrap(),
@@ -4346,6 +4721,10 @@ fn generic_tally_test_stddev_dr(
             kind: RADType::HttpGet,
             url: "".to_string(),
             script: vec![0x80],
+            body: vec![],
+            content_type: String::new(),
+            headers: vec![],
+            binary: false,
         }],
         aggregate: RADAggregate {
             filters: vec![],
@@ -0,0 +1,101 @@
+use std::convert::TryFrom;
+
+use witnet_data_structures::{error::DataRequestError, radon_error::RadonError};
+use witnet_rad::{
+    error::RadError,
+    types::{integer::RadonInteger, RadonTypes},
+};
+
+use crate::{
+    tests::{all_wips_active, example_data_request_output_with_mode_filter, ONE_WIT},
+    validations::*,
+};
+
+#[test]
+fn test_simulate_tally_1_liar_1_error() {
+    let value = RadonTypes::from(RadonInteger::from(1));
+    let liar = RadonTypes::from(RadonInteger::from(5));
+    let error = RadonTypes::from(RadonError::try_from(RadError::RetrieveTimeout).unwrap());
+    let dr_output = example_data_request_output_with_mode_filter(5, 200, 20);
+
+    let simulation = simulate_tally(
+        &dr_output,
+        vec![value.clone(), value.clone(), value.clone(), liar, error],
+        5,
+        ONE_WIT,
+        &all_wips_active(),
+    )
+    .unwrap();
+
+    let reward = 200 + ONE_WIT + ONE_WIT / 3;
+    assert_eq!(simulation.result, value);
+    assert_eq!(simulation.liars, vec![false, false, false, true, true]);
+    assert_eq!(simulation.errors, vec![false, false, false, false, true]);
+    assert!((simulation.consensus - 0.6).abs() < f64::EPSILON);
+    assert_eq!(simulation.payouts, vec![reward, reward, reward, 0, ONE_WIT]);
+    assert_eq!(simulation.non_revealer_payout, 0);
+    assert_eq!(simulation.collateral_remainder, 1);
+    assert_eq!(simulation.tally_change, 2 * 200);
+}
+
+#[test]
+fn test_simulate_tally_insufficient_consensus() {
+    let dr_output = example_data_request_output_with_mode_filter(4, 200, 20);
+
+    // Only 2 out of 4 witnesses reveal, and the minimum consensus is 51%
+    let simulation = simulate_tally(
+        &dr_output,
+        vec![
+            RadonTypes::from(RadonInteger::from(1)),
+            RadonTypes::from(RadonInteger::from(5)),
+        ],
+        4,
+        ONE_WIT,
+        &all_wips_active(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        simulation.result,
+        RadonTypes::from(
+            RadonError::try_from(RadError::InsufficientConsensus {
+                achieved: 0.5,
+                required: 0.51,
+            })
+            .unwrap()
+        )
+    );
+    assert_eq!(simulation.liars, vec![true, true]);
+    assert_eq!(simulation.errors, vec![true, true]);
+    assert!(simulation.consensus.abs() < f64::EPSILON);
+    // Nobody is honest, so the collateral is returned to all the committers
+    assert_eq!(simulation.payouts, vec![ONE_WIT, ONE_WIT]);
+    assert_eq!(simulation.non_revealer_payout, ONE_WIT);
+    assert_eq!(simulation.collateral_remainder, 0);
+    assert_eq!(simulation.tally_change, 4 * 200 + 2 * 20);
+}
+
+#[test]
+fn test_simulate_tally_more_reveals_than_commits() {
+    let dr_output = example_data_request_output_with_mode_filter(2, 200, 20);
+
+    let error = simulate_tally(
+        &dr_output,
+        vec![
+            RadonTypes::from(RadonInteger::from(1)),
+            RadonTypes::from(RadonInteger::from(1)),
+        ],
+        1,
+        ONE_WIT,
+        &all_wips_active(),
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        error.downcast::<DataRequestError>().unwrap(),
+        DataRequestError::MoreRevealsThanCommits {
+            reveals: 2,
+            commits: 1,
+        }
+    );
+}
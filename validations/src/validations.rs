@@ -17,11 +17,12 @@ use witnet_data_structures::{
     chain::{
         Block, BlockMerkleRoots, CheckpointBeacon, CheckpointVRF, ConsensusConstants,
         DataRequestOutput, DataRequestStage, DataRequestState, Epoch, EpochConstants, Hash,
-        Hashable, Input, KeyedSignature, OutputPointer, PublicKeyHash, RADRequest, RADTally,
-        Reputation, ReputationEngine, SignaturesToVerify, ValueTransferOutput,
+        Hashable, Input, KeyedSignature, OutputPointer, PublicKeyHash, RADRequest, RADRetrieve,
+        RADTally, RADType, Reputation, ReputationEngine, SignaturesToVerify, ValueTransferOutput,
+        MAX_RETRIEVAL_HEADERS_WEIGHT,
     },
     data_request::{
-        calculate_tally_change, calculate_witness_reward,
+        calculate_errors_and_liars_count, calculate_tally_change, calculate_witness_reward,
         calculate_witness_reward_before_second_hard_fork, create_tally, DataRequestPool,
     },
     error::{BlockError, DataRequestError, TransactionError},
@@ -38,11 +39,11 @@ use witnet_data_structures::{
 };
 use witnet_rad::{
     error::RadError,
-    reducers::mode::mode,
+    reducers::{mode::mode, RadonReducers},
     run_tally_report,
     script::{
-        create_radon_script_from_filters_and_reducer, unpack_radon_script,
-        RadonScriptExecutionSettings,
+        check_radon_script_operators, create_radon_script_from_filters_and_reducer,
+        unpack_radon_script, RadonScriptExecutionSettings,
     },
     types::{array::RadonArray, serial_iter_decode, RadonType, RadonTypes},
 };
@@ -238,7 +239,10 @@ pub fn validate_mint_transaction(
 }
 
 /// Function to validate a rad request
-pub fn validate_rad_request(rad_request: &RADRequest) -> Result<(), failure::Error> {
+pub fn validate_rad_request(
+    rad_request: &RADRequest,
+    active_wips: &ActiveWips,
+) -> Result<(), failure::Error> {
     let retrieval_paths = &rad_request.retrieve;
     // If the data request has no sources to retrieve, it is set as invalid
     if retrieval_paths.is_empty() {
@@ -246,25 +250,188 @@ pub fn validate_rad_request(rad_request: &RADRequest) -> Result<(), failure::Err
     }
     for path in retrieval_paths {
         // If the sources are empty the data request is set as invalid
-        if path.url.is_empty() {
+        if path.url.is_empty() && path.kind != RADType::Rng {
             return Err(DataRequestError::NoRetrievalSources.into());
         }
-        unpack_radon_script(path.script.as_slice())?;
+        validate_rad_retrieve_kind(path, active_wips)?;
+        validate_rad_retrieve_headers(path, active_wips)?;
+        let script = unpack_radon_script(path.script.as_slice())?;
+        // Only the top level calls can be checked, as that is all that nodes validated before the
+        // new operators existed: subscripts are plain CBOR arguments, so rejecting them here would
+        // make already accepted data requests invalid. They are checked when executed instead.
+        check_radon_script_operators(&script, active_wips)?;
     }
+    validate_rng_request(rad_request)?;
 
     let aggregate = &rad_request.aggregate;
     let filters = aggregate.filters.as_slice();
     let reducer = aggregate.reducer;
-    create_radon_script_from_filters_and_reducer(filters, reducer)?;
+    create_radon_script_from_filters_and_reducer(filters, reducer, active_wips)?;
 
     let consensus = &rad_request.tally;
     let filters = consensus.filters.as_slice();
     let reducer = consensus.reducer;
-    create_radon_script_from_filters_and_reducer(filters, reducer)?;
+    create_radon_script_from_filters_and_reducer(filters, reducer, active_wips)?;
+
+    Ok(())
+}
+
+/// Function to validate that the fields of a retrieval source are consistent with its kind
+fn validate_rad_retrieve_kind(
+    retrieve: &RADRetrieve,
+    active_wips: &ActiveWips,
+) -> Result<(), DataRequestError> {
+    match retrieve.kind {
+        RADType::HttpGet => {
+            // HTTP-GET requests have no body
+            if !retrieve.body.is_empty() {
+                return Err(DataRequestError::UnexpectedRetrievalField {
+                    kind: retrieve.kind.clone(),
+                    field: "body",
+                });
+            }
+            if !retrieve.content_type.is_empty() {
+                return Err(DataRequestError::UnexpectedRetrievalField {
+                    kind: retrieve.kind.clone(),
+                    field: "content_type",
+                });
+            }
+            // Binary responses are only supported after WIP0020
+            if retrieve.binary && !active_wips.wip0020() {
+                return Err(DataRequestError::UnexpectedRetrievalField {
+                    kind: retrieve.kind.clone(),
+                    field: "binary",
+                });
+            }
+        }
+        RADType::HttpPost => {
+            // HTTP-POST requests are only supported after WIP0020
+            if !active_wips.wip0020() {
+                return Err(DataRequestError::UnsupportedRetrievalKind {
+                    kind: retrieve.kind.clone(),
+                });
+            }
+            if !is_valid_http_header_value(&retrieve.content_type) {
+                return Err(DataRequestError::InvalidContentType {
+                    content_type: retrieve.content_type.clone(),
+                });
+            }
+        }
+        RADType::Rng => {
+            // RNG sources are only supported after WIP0019
+            if !active_wips.wip0019() {
+                return Err(DataRequestError::UnsupportedRetrievalKind {
+                    kind: retrieve.kind.clone(),
+                });
+            }
+            // RNG sources do not perform any request, and their response is always binary
+            let unexpected_field = [
+                ("url", !retrieve.url.is_empty()),
+                ("body", !retrieve.body.is_empty()),
+                ("content_type", !retrieve.content_type.is_empty()),
+                ("headers", !retrieve.headers.is_empty()),
+                ("binary", retrieve.binary),
+            ]
+            .iter()
+            .find_map(|(field, is_set)| if *is_set { Some(*field) } else { None });
+            if let Some(field) = unexpected_field {
+                return Err(DataRequestError::UnexpectedRetrievalField {
+                    kind: retrieve.kind.clone(),
+                    field,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Function to validate that the random bytes of an RNG data request are combined properly.
+///
+/// The random bytes revealed by the witnesses are different from each other, so they cannot be
+/// filtered, and they must be combined using the `HashConcatenate` reducer in the tally stage.
+/// As there is nothing to aggregate, an RNG source cannot be mixed with other sources.
+fn validate_rng_request(rad_request: &RADRequest) -> Result<(), DataRequestError> {
+    let invalid_rng_request = |reason| Err(DataRequestError::InvalidRngRequest { reason });
+
+    if !rad_request
+        .retrieve
+        .iter()
+        .any(|retrieve| retrieve.kind == RADType::Rng)
+    {
+        return Ok(());
+    }
+    if rad_request.retrieve.len() != 1 {
+        return invalid_rng_request("an RNG source must be the only source");
+    }
+    if !rad_request.aggregate.filters.is_empty()
+        || rad_request.aggregate.reducer != RadonReducers::Mode as u32
+    {
+        return invalid_rng_request("the aggregation stage must be a mode without filters");
+    }
+    if !rad_request.tally.filters.is_empty()
+        || rad_request.tally.reducer != RadonReducers::HashConcatenate as u32
+    {
+        return invalid_rng_request("the tally stage must be a hash concatenation without filters");
+    }
+
+    Ok(())
+}
+
+/// Function to validate the extra HTTP headers of a retrieval source
+fn validate_rad_retrieve_headers(
+    retrieve: &RADRetrieve,
+    active_wips: &ActiveWips,
+) -> Result<(), DataRequestError> {
+    // Extra HTTP headers are only supported after WIP0020
+    if !retrieve.headers.is_empty() && !active_wips.wip0020() {
+        return Err(DataRequestError::UnexpectedRetrievalField {
+            kind: retrieve.kind.clone(),
+            field: "headers",
+        });
+    }
+
+    let weight = retrieve.headers_weight();
+    if weight > MAX_RETRIEVAL_HEADERS_WEIGHT {
+        return Err(DataRequestError::HeadersTooHeavy {
+            weight,
+            max_weight: MAX_RETRIEVAL_HEADERS_WEIGHT,
+        });
+    }
+
+    for (name, value) in &retrieve.headers {
+        if !is_valid_http_header_name(name) || !is_valid_http_header_value(value) {
+            return Err(DataRequestError::InvalidHttpHeader {
+                name: name.clone(),
+                value: value.clone(),
+            });
+        }
+        // The content type has its own field, so it cannot be overridden by an extra header
+        if name.eq_ignore_ascii_case("Content-Type") {
+            return Err(DataRequestError::ContentTypeInHeaders);
+        }
+    }
 
     Ok(())
 }
 
+/// Returns true if the string can be used as the name of an HTTP header, i.e. it is a non-empty
+/// token as defined in RFC 7230.
+fn is_valid_http_header_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+}
+
+/// Returns true if the string can be used as the value of an HTTP header, i.e. it only contains
+/// visible ASCII characters, spaces and tabs.
+fn is_valid_http_header_value(value: &str) -> bool {
+    value
+        .bytes()
+        .all(|b| b == b'\t' || (b' '..=b'~').contains(&b))
+}
+
 /// An histogram-like counter that helps counting occurrences of different numeric categories.
 struct Counter {
     /// Tracks the position inside `values` of the category that appears the most.
@@ -564,6 +731,7 @@ pub fn construct_report_from_clause_result(
                 Some(liars),
                 Some(errors),
                 RadonScriptExecutionSettings::all_but_partial_results(),
+                active_wips,
             ) {
                 Ok(x) => x,
                 Err(e) => {
@@ -704,6 +872,7 @@ pub fn validate_genesis_vt_transaction(
 }
 
 /// Function to validate a data request transaction
+#[allow(clippy::too_many_arguments)]
 pub fn validate_dr_transaction<'a>(
     dr_tx: &'a DRTransaction,
     utxo_diff: &UtxoDiff<'_>,
@@ -712,6 +881,7 @@ pub fn validate_dr_transaction<'a>(
     signatures_to_verify: &mut Vec<SignaturesToVerify>,
     collateral_minimum: u64,
     max_dr_weight: u32,
+    active_wips: &ActiveWips,
 ) -> Result<(Vec<&'a Input>, Vec<&'a ValueTransferOutput>, u64), failure::Error> {
     if dr_tx.weight() > max_dr_weight {
         return Err(TransactionError::DataRequestWeightLimitExceeded {
@@ -788,7 +958,7 @@ pub fn validate_dr_transaction<'a>(
         .into());
     }
 
-    validate_rad_request(&dr_tx.body.dr_output.data_request)?;
+    validate_rad_request(&dr_tx.body.dr_output.data_request, active_wips)?;
 
     Ok((
         dr_tx.body.inputs.iter().collect(),
@@ -1010,18 +1180,10 @@ fn create_expected_report(
                     &ReportContext::default(),
                 ))
             },
+            active_wips,
         );
 
-        let results_len = results.len();
-        let clause_result =
-            evaluate_tally_precondition_clause(results, non_error_min, commits_count, active_wips);
-        let report =
-            construct_report_from_clause_result(clause_result, &tally, results_len, active_wips);
-        if active_wips.wips_0009_0011_0012() {
-            evaluate_tally_postcondition_clause(report, non_error_min, commits_count)
-        } else {
-            report
-        }
+        evaluate_tally(results, tally, non_error_min, commits_count, active_wips)
     }) {
         Ok(x) => x,
         Err(_e) => {
@@ -1047,6 +1209,162 @@ pub fn radon_report_from_error(rad_error: RadError, num_reveals: usize) -> Radon
     )
 }
 
+/// Run the tally stage on the reported values, including the precondition and postcondition
+/// clauses, and return the report that determines the tally transaction.
+pub fn evaluate_tally(
+    reports: Vec<RadonReport<RadonTypes>>,
+    tally: &RADTally,
+    non_error_min: f64,
+    commits_count: usize,
+    active_wips: &ActiveWips,
+) -> RadonReport<RadonTypes> {
+    let reports_len = reports.len();
+    let clause_result =
+        evaluate_tally_precondition_clause(reports, non_error_min, commits_count, active_wips);
+    let report =
+        construct_report_from_clause_result(clause_result, tally, reports_len, active_wips);
+    if active_wips.wips_0009_0011_0012() {
+        evaluate_tally_postcondition_clause(report, non_error_min, commits_count)
+    } else {
+        report
+    }
+}
+
+/// The outcome of a tally computed by `simulate_tally`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TallySimulation {
+    /// Result of the tally, as it would be included in the tally transaction
+    pub result: RadonTypes,
+    /// Whether each of the reveals is out of consensus
+    pub liars: Vec<bool>,
+    /// Whether each of the reveals is considered an error
+    pub errors: Vec<bool>,
+    /// Ratio of the commits whose reveal is in consensus
+    pub consensus: f64,
+    /// Value paid to each of the revealers: the reward plus the collateral if it is in consensus,
+    /// the collateral if it is refunded, or 0 if the collateral is slashed
+    pub payouts: Vec<u64>,
+    /// Value paid to each of the committers that did not reveal
+    pub non_revealer_payout: u64,
+    /// Part of the slashed collateral that cannot be evenly divided among the honest witnesses,
+    /// which goes to the miner of the tally
+    pub collateral_remainder: u64,
+    /// Value returned to the creator of the data request
+    pub tally_change: u64,
+}
+
+/// Compute locally the tally of a data request for the given reveals, as the miner of the tally
+/// transaction would do, in order to know in advance which witnesses would be considered liars
+/// or errors and how the rewards and the collateral would be distributed.
+///
+/// `commits_count` is the number of witnesses that committed, which must be at least the number of
+/// reveals, or an error is returned. The difference is the number of witnesses that committed but
+/// did not reveal.
+// FIXME: Allow for now, since there is no safe cast function from a usize to float yet
+#[allow(clippy::cast_precision_loss)]
+pub fn simulate_tally(
+    dr_output: &DataRequestOutput,
+    reveals: Vec<RadonTypes>,
+    commits_count: usize,
+    collateral_minimum: u64,
+    active_wips: &ActiveWips,
+) -> Result<TallySimulation, failure::Error> {
+    let reveals_count = reveals.len();
+    if reveals_count > commits_count {
+        return Err(DataRequestError::MoreRevealsThanCommits {
+            reveals: reveals_count,
+            commits: commits_count,
+        }
+        .into());
+    }
+
+    let reports = reveals
+        .into_iter()
+        .map(|reveal| RadonReport::from_result(Ok(reveal), &ReportContext::default()))
+        .collect();
+    let non_error_min = f64::from(dr_output.min_consensus_percentage) / 100.0;
+    let report = evaluate_tally(
+        reports,
+        &dr_output.data_request.tally,
+        non_error_min,
+        commits_count,
+        active_wips,
+    );
+    let (liars, errors) = match &report.context.stage {
+        Stage::Tally(metadata) => (metadata.liars.clone(), metadata.errors.clone()),
+        _ => (vec![false; reveals_count], vec![false; reveals_count]),
+    };
+    let (liars_count, errors_count) = calculate_errors_and_liars_count(&errors, &liars);
+    let non_reveals_count = commits_count - reveals_count;
+
+    let collateral = if dr_output.collateral == 0 {
+        collateral_minimum
+    } else {
+        dr_output.collateral
+    };
+    // The rewards are distributed in the same way as in `create_tally`
+    let is_after_second_hard_fork = active_wips.wips_0009_0011_0012();
+    let (reward, collateral_remainder) = if is_after_second_hard_fork {
+        calculate_witness_reward(
+            commits_count,
+            liars_count + non_reveals_count,
+            errors_count,
+            dr_output.witness_reward,
+            collateral,
+        )
+    } else {
+        calculate_witness_reward_before_second_hard_fork(
+            commits_count,
+            reveals_count,
+            liars_count + non_reveals_count,
+            errors_count,
+            dr_output.witness_reward,
+            collateral,
+        )
+    };
+    let any_honest_revealers = if is_after_second_hard_fork {
+        commits_count - liars_count - errors_count - non_reveals_count > 0
+    } else {
+        reveals_count > 0
+    };
+    let (payouts, non_revealer_payout) = if any_honest_revealers {
+        let payouts = liars
+            .iter()
+            .zip(errors.iter())
+            .map(|(liar, error)| match (liar, error) {
+                (true, true) => collateral,
+                (true, false) => 0,
+                (false, _) => reward,
+            })
+            .collect();
+
+        (payouts, 0)
+    } else {
+        // In case of no honests, collateral returns to their owners
+        (vec![collateral; reveals_count], collateral)
+    };
+
+    let honests_count = reveals_count - liars_count - errors_count;
+    let tally_change =
+        calculate_tally_change(commits_count, reveals_count, honests_count, dr_output);
+    let consensus = if commits_count == 0 {
+        0.0
+    } else {
+        liars.iter().filter(|liar| !**liar).count() as f64 / commits_count as f64
+    };
+
+    Ok(TallySimulation {
+        result: report.result,
+        liars,
+        errors,
+        consensus,
+        payouts,
+        non_revealer_payout,
+        collateral_remainder,
+        tally_change,
+    })
+}
+
 fn create_expected_tally_transaction(
     ta_tx: &TallyTransaction,
     dr_pool: &DataRequestPool,
@@ -1858,6 +2176,7 @@ pub fn validate_block_transactions(
             signatures_to_verify,
             consensus_constants.collateral_minimum,
             consensus_constants.max_dr_weight,
+            active_wips,
         )?;
         total_fee += fee;
 
@@ -2050,6 +2369,7 @@ pub fn validate_new_transaction(
             signatures_to_verify,
             collateral_minimum,
             max_dr_weight,
+            active_wips,
         )
         .map(|(_, _, fee)| fee),
         Transaction::Commit(tx) => validate_commit_transaction(
@@ -10,6 +10,7 @@ use crate::{
 };
 use witnet_data_structures::{
     chain::{DataRequestOutput, Hashable},
+    mainnet_validations::current_active_wips,
     proto::ProtobufConvert,
     transaction::Transaction,
     transaction_factory::FeeType,
@@ -95,14 +96,31 @@ impl Handler<CreateDataReqRequest> for app::App {
 /// To be valid it must pass these checks:
 /// - value is greater that the sum of `witnesses` times the sum of the fees
 /// - value minus all the fees must divisible by the number of witnesses
+/// - the RADON scripts pass the static type checks, so that they do not fail for sure when
+///   executed by the witnesses
 fn validate(request: DataRequestOutput) -> Result<DataRequestOutput, app::ValidationErrors> {
     let req = request;
 
     let request = witnet_validations::validations::validate_data_request_output(&req)
         .map_err(|err| app::field_error("request", format!("{}", err)));
 
-    let data_request = witnet_validations::validations::validate_rad_request(&req.data_request)
-        .map_err(|err| app::field_error("dataRequest", format!("{}", err)));
+    let data_request = witnet_validations::validations::validate_rad_request(
+        &req.data_request,
+        &current_active_wips(),
+    )
+    .map_err(|err| app::field_error("dataRequest", format!("{}", err)))
+    .and_then(|()| {
+        let issues =
+            witnet_rad::type_check::check_rad_request(&req.data_request, &current_active_wips());
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(issues
+                .iter()
+                .map(|issue| ("dataRequest".to_string(), issue.to_string()))
+                .collect())
+        }
+    });
 
     app::combine_field_errors(request, data_request, move |_, _| req)
 }
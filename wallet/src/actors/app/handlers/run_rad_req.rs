@@ -14,6 +14,8 @@ pub struct RunRadReqRequest {
 #[derive(Debug, Serialize)]
 pub struct RunRadReqResponse {
     pub result: RADRequestExecutionReport,
+    /// The results of every stage, in the lossless JSON encoding of RADON values
+    pub values: serde_json::Value,
 }
 
 impl Message for RunRadReqRequest {
@@ -25,8 +27,12 @@ impl Handler<RunRadReqRequest> for app::App {
 
     fn handle(&mut self, msg: RunRadReqRequest, _ctx: &mut Self::Context) -> Self::Result {
         let f = self.run_rad_request(msg.rad_request).map(|res| {
-            res.map_err(app::internal_error)
-                .map(|result| RunRadReqResponse { result })
+            res.map_err(app::internal_error).and_then(|result| {
+                let values = witnet_rad::json::execution_report_to_json(&result)
+                    .map_err(app::internal_error)?;
+
+                Ok(RunRadReqResponse { result, values })
+            })
         });
 
         Box::pin(f)
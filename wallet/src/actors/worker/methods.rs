@@ -18,6 +18,7 @@ use witnet_data_structures::{
         Block, CheckpointBeacon, DataRequestInfo, Hashable, OutputPointer, RADRequest,
         StateMachine, ValueTransferOutput,
     },
+    mainnet_validations::current_active_wips,
     transaction::Transaction,
 };
 use witnet_futures_utils::TryFutureExt2;
@@ -54,7 +55,13 @@ impl Worker {
     }
 
     pub fn run_rad_request(&self, request: RADRequest) -> RADRequestExecutionReport {
-        witnet_rad::try_data_request(&request, RadonScriptExecutionSettings::enable_all(), None)
+        witnet_rad::try_data_request_with_transport(
+            &request,
+            RadonScriptExecutionSettings::enable_all(),
+            None,
+            self.params.rad_transport.as_ref(),
+            &current_active_wips(),
+        )
     }
 
     pub fn gen_mnemonic(&self, length: mnemonic::Length) -> String {
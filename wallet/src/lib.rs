@@ -21,9 +21,13 @@ use actix::prelude::*;
 use failure::Error;
 use rand::seq::SliceRandom;
 
-use witnet_config::config::Config;
+use witnet_config::config::{Config, RadCassetteMode};
 use witnet_data_structures::chain::{CheckpointBeacon, EpochConstants};
 use witnet_net::client::tcp::JsonRpcClient;
+use witnet_rad::{
+    proxy::RetrievalProxy,
+    transport::{Cassette, HttpTransport, RetrievalTransport},
+};
 
 use crate::actors::app;
 use crate::actors::app::NodeClient;
@@ -76,6 +80,30 @@ pub fn run(conf: Config) -> Result<(), Error> {
     // Wallet concurrency
     let concurrency = conf.wallet.concurrency.unwrap_or_else(num_cpus::get);
 
+    // Transport used for data request retrievals, optionally recording or replaying a cassette
+    // The requests are sent through the data request proxy of the configuration, if any
+    let mining = &conf.mining;
+    let proxy = mining
+        .data_request_proxy_url
+        .as_ref()
+        .map(|url| {
+            RetrievalProxy::new(
+                url,
+                mining.data_request_proxy_username.as_deref(),
+                mining.data_request_proxy_password.as_deref(),
+                mining.data_request_proxy_bypass.clone(),
+            )
+        })
+        .transpose()?;
+    let http_transport = HttpTransport::default().with_proxy(proxy);
+    let rad_transport: Arc<dyn RetrievalTransport> = match conf.wallet.rad_cassette {
+        Some(cassette) => Arc::new(match cassette.mode {
+            RadCassetteMode::Record => Cassette::record(cassette.path, http_transport)?,
+            RadCassetteMode::Replay => Cassette::replay(cassette.path)?,
+        }),
+        None => Arc::new(http_transport),
+    };
+
     // How many blocks to ask a Witnet node for when synchronizing
     let node_sync_batch_size = conf.wallet.node_sync_batch_size;
 
@@ -138,6 +166,7 @@ pub fn run(conf: Config) -> Result<(), Error> {
             max_vt_weight,
             max_dr_weight,
             consensus_constants,
+            rad_transport,
         };
 
         let last_beacon = Arc::new(RwLock::new(CheckpointBeacon {
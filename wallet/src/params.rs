@@ -7,6 +7,7 @@ use std::{
 use witnet_crypto::hash::HashFunction;
 use witnet_data_structures::chain::{CheckpointBeacon, ConsensusConstants, EpochConstants, Hash};
 use witnet_net::client::tcp::jsonrpc::Subscribe;
+use witnet_rad::transport::RetrievalTransport;
 
 use crate::{actors::app::NodeClient, types};
 
@@ -29,6 +30,8 @@ pub struct Params {
     pub max_vt_weight: u32,
     pub max_dr_weight: u32,
     pub consensus_constants: ConsensusConstants,
+    /// Transport used for the retrievals of the data requests run by the wallet.
+    pub rad_transport: Arc<dyn RetrievalTransport>,
 }
 
 #[derive(Clone)]
@@ -3,6 +3,7 @@ use witnet_data_structures::chain::{ConsensusConstants, Hash};
 use super::*;
 use crate::db::HashMapDb;
 use witnet_crypto::{hash::HashFunction, mnemonic};
+use witnet_rad::transport::HttpTransport;
 
 pub fn wallet(data: Option<HashMapDb>) -> (Wallet<db::HashMapDb>, db::HashMapDb) {
     wallet_inner(data, true)
@@ -63,6 +64,7 @@ fn wallet_inner(
             initial_block_reward: 250 * 1_000_000_000,
             halving_period: 3_500_000,
         },
+        rad_transport: Arc::new(HttpTransport::default()),
     };
     let mnemonic = mnemonic::MnemonicGen::new()
         .with_len(mnemonic::Length::Words12)
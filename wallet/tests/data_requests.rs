@@ -63,7 +63,10 @@ fn test_radon_error_json_serialization() {
 /// final `RadonReport` complies with the Witnet Wallet API.
 #[test]
 fn test_data_request_report_json_serialization() {
-    use witnet_data_structures::chain::{RADAggregate, RADRequest, RADRetrieve, RADTally, RADType};
+    use witnet_data_structures::{
+        chain::{RADAggregate, RADRequest, RADRetrieve, RADTally, RADType},
+        mainnet_validations::current_active_wips,
+    };
     use witnet_rad::{
         script::{unpack_radon_script, RadonScriptExecutionSettings},
         try_data_request,
@@ -76,6 +79,10 @@ fn test_data_request_report_json_serialization() {
                 kind: RADType::HttpGet,
                 url: String::from("https://www.bitstamp.net/api/ticker/"),
                 script: vec![130, 24, 119, 130, 24, 100, 100, 108, 97, 115, 116],
+                body: vec![],
+                content_type: String::new(),
+                headers: vec![],
+                binary: false,
             },
             RADRetrieve {
                 kind: RADType::HttpGet,
@@ -84,6 +91,10 @@ fn test_data_request_report_json_serialization() {
                     132, 24, 119, 130, 24, 102, 99, 98, 112, 105, 130, 24, 102, 99, 85, 83, 68,
                     130, 24, 100, 106, 114, 97, 116, 101, 95, 102, 108, 111, 97, 116,
                 ],
+                body: vec![],
+                content_type: String::new(),
+                headers: vec![],
+                binary: false,
             },
         ],
         aggregate: RADAggregate {
@@ -104,6 +115,7 @@ fn test_data_request_report_json_serialization() {
         &request,
         RadonScriptExecutionSettings::enable_all(),
         Some(&inputs),
+        &current_active_wips(),
     );
 
     // Number of retrieval reports should match number of sources